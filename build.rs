@@ -0,0 +1,52 @@
+//! Build metadata for `bal version`
+//!
+//! Exposes the git commit, build time and enabled cargo features as
+//! compile-time env vars so an image's binary can be matched to its source.
+//! `BAL_GIT_COMMIT` overrides the commit when building outside a checkout;
+//! `SOURCE_DATE_EPOCH` pins the build time for reproducible builds.
+
+use std::env;
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn main() {
+    println!("cargo:rerun-if-changed=.git/HEAD");
+    println!("cargo:rerun-if-changed=.git/refs");
+    println!("cargo:rerun-if-env-changed=BAL_GIT_COMMIT");
+    println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+
+    let git_commit = env::var("BAL_GIT_COMMIT")
+        .ok()
+        .filter(|commit| !commit.is_empty())
+        .or_else(|| {
+            let output = Command::new("git")
+                .args(["rev-parse", "--short=12", "HEAD"])
+                .output()
+                .ok()?;
+            output
+                .status
+                .success()
+                .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        })
+        .unwrap_or_else(|| "unknown".to_string());
+
+    let build_epoch = env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|epoch| epoch.parse::<i64>().ok())
+        .unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|elapsed| elapsed.as_secs() as i64)
+                .unwrap_or(0)
+        });
+
+    let mut features: Vec<String> = env::vars()
+        .filter_map(|(key, _)| key.strip_prefix("CARGO_FEATURE_").map(str::to_string))
+        .map(|feature| feature.to_lowercase().replace('_', "-"))
+        .collect();
+    features.sort();
+
+    println!("cargo:rustc-env=BAL_GIT_COMMIT={}", git_commit);
+    println!("cargo:rustc-env=BAL_BUILD_EPOCH={}", build_epoch);
+    println!("cargo:rustc-env=BAL_BUILD_FEATURES={}", features.join(","));
+}
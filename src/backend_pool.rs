@@ -4,11 +4,22 @@
 //! Tracks each backend's health status, active connection count, and consecutive
 //! failure count, sharing state in a thread-safe manner.
 
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
 use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicUsize, Ordering};
-use std::sync::Arc;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use tokio::sync::Notify;
 
 use crate::config::BackendConfig;
+use crate::keepalive::KeepalivePool;
+
+/// Minimum slow-start share so a just-recovered backend still receives some traffic
+const SLOW_START_MIN_FACTOR: f64 = 0.01;
+
+/// Health transitions remembered for flap counting; older ones are dropped
+const MAX_TRANSITION_HISTORY: usize = 64;
 
 /// Backend server runtime state
 ///
@@ -22,6 +33,14 @@ pub enum BackendErrorKind {
     Other,
 }
 
+/// Most recent connect or health probe error, kept for `bal status`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LastError {
+    pub message: String,
+    /// Unix epoch ms when it happened
+    pub at_ms: u64,
+}
+
 #[derive(Debug)]
 pub struct BackendState {
     /// Backend configuration (immutable)
@@ -30,6 +49,8 @@ pub struct BackendState {
     healthy: AtomicBool,
     /// Current active connection count
     active_connections: AtomicUsize,
+    /// Connects selected for this backend that haven't resolved yet
+    pending_connects: AtomicUsize,
     /// Consecutive health check failure count
     consecutive_failures: AtomicU32,
     /// Consecutive health check success count
@@ -40,12 +61,34 @@ pub struct BackendState {
     cooldown_until_ms: AtomicU64,
     /// Last health check timestamp (unix epoch ms)
     last_check_ms: AtomicU64,
+    /// Last unhealthy -> healthy transition timestamp (unix epoch ms)
+    recovered_at_ms: AtomicU64,
+    /// Last health transition in either direction (unix epoch ms, 0 = none yet)
+    last_transition_ms: AtomicU64,
+    /// Recent health transition timestamps, oldest first; only touched on flips
+    transitions: Mutex<VecDeque<u64>>,
     /// Connection timeout counter
     timeout_count: AtomicU64,
     /// Connection refused counter
     refused_count: AtomicU64,
     /// Other connection error counter
     other_error_count: AtomicU64,
+    /// Bytes relayed in both directions by finished sessions
+    bytes_total: AtomicU64,
+    /// Since when active connections have been at/over the saturation limit (0 = not saturated)
+    saturated_since_ms: AtomicU64,
+    /// Saturated for longer than the degrade window; deprioritized in selection
+    degraded: AtomicBool,
+    /// Smoothed health probe connect latency in microseconds (0 = no sample yet)
+    latency_ewma_us: AtomicU64,
+    /// Most recent error text; only touched on failures, so a lock is fine
+    last_error: Mutex<Option<LastError>>,
+    /// Set once a removed backend's drain deadline has passed
+    retired: AtomicBool,
+    /// Wakes relays of a retired backend so they close
+    retired_notify: Notify,
+    /// Pre-opened spare connections (`backend_keepalive`)
+    keepalive: KeepalivePool,
 }
 
 impl BackendState {
@@ -56,14 +99,26 @@ impl BackendState {
             // Initially considered healthy (until health checks start)
             healthy: AtomicBool::new(true),
             active_connections: AtomicUsize::new(0),
+            pending_connects: AtomicUsize::new(0),
             consecutive_failures: AtomicU32::new(0),
             consecutive_successes: AtomicU32::new(0),
             failover_failure_streak: AtomicU32::new(0),
             cooldown_until_ms: AtomicU64::new(0),
             last_check_ms: AtomicU64::new(0),
+            recovered_at_ms: AtomicU64::new(0),
+            last_transition_ms: AtomicU64::new(0),
+            transitions: Mutex::new(VecDeque::new()),
             timeout_count: AtomicU64::new(0),
             refused_count: AtomicU64::new(0),
             other_error_count: AtomicU64::new(0),
+            bytes_total: AtomicU64::new(0),
+            saturated_since_ms: AtomicU64::new(0),
+            degraded: AtomicBool::new(false),
+            latency_ewma_us: AtomicU64::new(0),
+            last_error: Mutex::new(None),
+            retired: AtomicBool::new(false),
+            retired_notify: Notify::new(),
+            keepalive: KeepalivePool::default(),
         }
     }
 
@@ -75,6 +130,7 @@ impl BackendState {
 
     /// Set health status
     #[inline]
+    #[cfg(test)]
     pub fn set_healthy(&self, healthy: bool) {
         self.healthy.store(healthy, Ordering::Relaxed);
     }
@@ -85,6 +141,22 @@ impl BackendState {
         self.active_connections.load(Ordering::Relaxed)
     }
 
+    /// Add a finished session's relayed bytes
+    pub fn record_bytes(&self, bytes: u64) {
+        self.bytes_total.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    /// Bytes relayed by finished sessions to this backend
+    pub fn bytes_total(&self) -> u64 {
+        self.bytes_total.load(Ordering::Relaxed)
+    }
+
+    /// Get count of connects in flight to this backend
+    #[inline]
+    pub fn pending_connects(&self) -> usize {
+        self.pending_connects.load(Ordering::Relaxed)
+    }
+
     /// Increment active connection count
     ///
     /// Called when a new client connection connects to the backend.
@@ -116,6 +188,7 @@ impl BackendState {
     ///
     /// Called on health check failure, transitions to unhealthy if threshold exceeded.
     #[inline]
+    #[allow(dead_code)]
     pub fn increment_failures(&self) {
         self.consecutive_failures.fetch_add(1, Ordering::Relaxed);
         self.consecutive_successes.store(0, Ordering::Relaxed);
@@ -125,6 +198,7 @@ impl BackendState {
     ///
     /// Called on health check success, recovers to healthy if threshold exceeded.
     #[inline]
+    #[allow(dead_code)]
     pub fn increment_successes(&self) {
         self.consecutive_successes.fetch_add(1, Ordering::Relaxed);
         self.consecutive_failures.store(0, Ordering::Relaxed);
@@ -132,6 +206,7 @@ impl BackendState {
 
     /// Get consecutive success count
     #[inline]
+    #[allow(dead_code)]
     pub fn consecutive_successes(&self) -> u32 {
         self.consecutive_successes.load(Ordering::Relaxed)
     }
@@ -146,10 +221,10 @@ impl BackendState {
         if failures >= max_failures {
             let was_healthy = self.healthy.swap(false, Ordering::Relaxed);
             if was_healthy {
+                self.record_transition(Self::now_unix_ms());
                 log::warn!(
-                    "Backend {}:{} marked as unhealthy ({} consecutive failures)",
-                    self.config.host,
-                    self.config.port,
+                    "Backend {} marked as unhealthy ({} consecutive failures)",
+                    self.address(),
                     failures
                 );
             }
@@ -164,19 +239,54 @@ impl BackendState {
         self.consecutive_failures.store(0, Ordering::Relaxed);
 
         if successes >= min_successes && !self.is_healthy() {
+            let now_ms = Self::now_unix_ms();
             self.healthy.store(true, Ordering::Relaxed);
+            self.recovered_at_ms.store(now_ms, Ordering::Relaxed);
+            self.record_transition(now_ms);
             log::info!(
-                "Backend {}:{} recovered to healthy ({} consecutive successes)",
-                self.config.host,
-                self.config.port,
+                "Backend {} recovered to healthy ({} consecutive successes)",
+                self.address(),
                 successes
             );
         }
     }
 
-    /// Get backend address string (host:port format)
+    /// Remember a healthy <-> unhealthy flip at `now_ms`
+    fn record_transition(&self, now_ms: u64) {
+        self.last_transition_ms.store(now_ms, Ordering::Relaxed);
+        if let Ok(mut transitions) = self.transitions.lock() {
+            if transitions.len() == MAX_TRANSITION_HISTORY {
+                transitions.pop_front();
+            }
+            transitions.push_back(now_ms);
+        }
+    }
+
+    /// Last health transition (unix epoch ms, 0 = never flipped)
+    pub fn last_transition_ms(&self) -> u64 {
+        self.last_transition_ms.load(Ordering::Relaxed)
+    }
+
+    /// Health transitions within `window_ms` before `now_ms`
+    ///
+    /// A stably up or stably down backend reports 0; a flapping one keeps
+    /// climbing.
+    pub fn flap_count(&self, window_ms: u64, now_ms: u64) -> u32 {
+        let since = now_ms.saturating_sub(window_ms);
+        self.transitions
+            .lock()
+            .map(|transitions| transitions.iter().filter(|at| **at >= since).count() as u32)
+            .unwrap_or(0)
+    }
+
+    /// Spare connections kept warm for new sessions
+    pub fn keepalive(&self) -> &KeepalivePool {
+        &self.keepalive
+    }
+
+    /// Get backend address string (host:port, or unix:<path>)
     pub fn address(&self) -> String {
-        format!("{}:{}", self.config.host, self.config.port)
+        self.config.address()
     }
 
     pub fn mark_checked_now(&self) {
@@ -184,6 +294,7 @@ impl BackendState {
             .store(Self::now_unix_ms(), Ordering::Relaxed);
     }
 
+    #[cfg(test)]
     pub fn last_check_ms(&self) -> u64 {
         self.last_check_ms.load(Ordering::Relaxed)
     }
@@ -196,6 +307,93 @@ impl BackendState {
         self.cooldown_until_ms() > Self::now_unix_ms()
     }
 
+    pub fn recovered_at_ms(&self) -> u64 {
+        self.recovered_at_ms.load(Ordering::Relaxed)
+    }
+
+    /// Whether it has been healthy for at least `window_ms` since its last recovery
+    ///
+    /// Backends that never recovered count as stable.
+    pub fn is_stable(&self, window_ms: u64, now_ms: u64) -> bool {
+        let recovered_at = self.recovered_at_ms();
+        recovered_at == 0 || now_ms.saturating_sub(recovered_at) >= window_ms
+    }
+
+    /// Share of traffic this backend should receive while slow-starting
+    ///
+    /// Ramps linearly from near-zero right after recovery to 1.0 once
+    /// `slow_start_ms` has elapsed. Backends that never recovered get full share.
+    pub fn slow_start_factor(&self, slow_start_ms: u64) -> f64 {
+        let recovered_at = self.recovered_at_ms();
+        if slow_start_ms == 0 || recovered_at == 0 {
+            return 1.0;
+        }
+
+        let elapsed = Self::now_unix_ms().saturating_sub(recovered_at);
+        if elapsed >= slow_start_ms {
+            return 1.0;
+        }
+
+        (elapsed as f64 / slow_start_ms as f64).max(SLOW_START_MIN_FACTOR)
+    }
+
+    /// Whether sustained saturation has marked this backend degraded
+    pub fn is_degraded(&self) -> bool {
+        self.degraded.load(Ordering::Relaxed)
+    }
+
+    /// Re-evaluate saturation against `max_connections`
+    ///
+    /// A backend at or over the limit for `degrade_after_ms` becomes degraded;
+    /// it leaves that state as soon as it drops below the limit.
+    pub fn update_saturation(&self, max_connections: usize, degrade_after_ms: u64, now_ms: u64) {
+        if self.active_connections() < max_connections {
+            self.saturated_since_ms.store(0, Ordering::Relaxed);
+            self.degraded.store(false, Ordering::Relaxed);
+            return;
+        }
+
+        let since = match self.saturated_since_ms.compare_exchange(
+            0,
+            now_ms,
+            Ordering::Relaxed,
+            Ordering::Relaxed,
+        ) {
+            Ok(_) => now_ms,
+            Err(existing) => existing,
+        };
+        self.degraded.store(
+            now_ms.saturating_sub(since) >= degrade_after_ms,
+            Ordering::Relaxed,
+        );
+    }
+
+    /// Fold a successful probe's connect latency into the moving average
+    ///
+    /// Uses an exponentially weighted average (new sample weighted 30%) so a
+    /// single slow probe doesn't swing selection but a persistently slow
+    /// backend is noticed within a few intervals.
+    pub fn record_latency(&self, latency: Duration) {
+        let sample = (latency.as_micros() as u64).max(1);
+        let _ =
+            self.latency_ewma_us
+                .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |current| {
+                    Some(if current == 0 {
+                        sample
+                    } else {
+                        (current * 7 + sample * 3) / 10
+                    })
+                });
+    }
+
+    /// Smoothed probe latency, if any probe has succeeded yet
+    pub fn smoothed_latency(&self) -> Option<Duration> {
+        match self.latency_ewma_us.load(Ordering::Relaxed) {
+            0 => None,
+            us => Some(Duration::from_micros(us)),
+        }
+    }
+
     pub fn timeout_count(&self) -> u64 {
         self.timeout_count.load(Ordering::Relaxed)
     }
@@ -208,12 +406,50 @@ impl BackendState {
         self.other_error_count.load(Ordering::Relaxed)
     }
 
+    /// Zero the error and byte counters; health, cooldown and routing state are kept
+    pub fn reset_counters(&self) {
+        self.timeout_count.store(0, Ordering::Relaxed);
+        self.refused_count.store(0, Ordering::Relaxed);
+        self.other_error_count.store(0, Ordering::Relaxed);
+        self.bytes_total.store(0, Ordering::Relaxed);
+    }
+
+    pub fn is_retired(&self) -> bool {
+        self.retired.load(Ordering::Relaxed)
+    }
+
+    /// Force-close remaining connections of a removed backend
+    ///
+    /// Relays waiting on `retired()` stop as soon as this is called.
+    pub fn retire(&self) {
+        self.retired.store(true, Ordering::Relaxed);
+        self.retired_notify.notify_waiters();
+    }
+
+    /// Resolves once the backend has been retired
+    pub async fn retired(&self) {
+        loop {
+            let notified = self.retired_notify.notified();
+            tokio::pin!(notified);
+            // Register before checking the flag so a concurrent retire() isn't missed
+            notified.as_mut().enable();
+            if self.is_retired() {
+                return;
+            }
+            notified.await;
+        }
+    }
+
     pub fn mark_connect_success(&self, min_successes: u32) {
         self.failover_failure_streak.store(0, Ordering::Relaxed);
         self.cooldown_until_ms.store(0, Ordering::Relaxed);
         self.mark_success(min_successes);
     }
 
+    /// Record a failed connect and push the cooldown window out
+    ///
+    /// With `jitter`, the window is drawn from [window/2, window] ("equal
+    /// jitter") so clients failing on the same backend don't retry in lockstep.
     pub fn mark_connect_failure(
         &self,
         kind: BackendErrorKind,
@@ -221,6 +457,7 @@ impl BackendState {
         backoff_initial_ms: u64,
         backoff_max_ms: u64,
         cooldown_ms: u64,
+        jitter: bool,
     ) {
         match kind {
             BackendErrorKind::Timeout => {
@@ -238,13 +475,74 @@ impl BackendState {
         let multiplier = 1u64.checked_shl((streak - 1).min(20)).unwrap_or(u64::MAX);
         let exp_backoff = backoff_initial_ms.saturating_mul(multiplier);
         let backoff = exp_backoff.min(backoff_max_ms);
-        let until = Self::now_unix_ms().saturating_add(backoff.max(cooldown_ms));
+        let mut window = backoff.max(cooldown_ms);
+        if jitter {
+            let half = window / 2;
+            window = half + fastrand::u64(0..=window - half);
+        }
+        let until = Self::now_unix_ms().saturating_add(window);
         self.cooldown_until_ms.store(until, Ordering::Relaxed);
 
         self.mark_failure(max_failures);
     }
 
-    fn now_unix_ms() -> u64 {
+    /// Remember `message` as this backend's most recent error
+    pub fn record_error(&self, message: impl Into<String>) {
+        self.record_error_entry(LastError {
+            message: message.into(),
+            at_ms: Self::now_unix_ms(),
+        });
+    }
+
+    fn record_error_entry(&self, entry: LastError) {
+        if let Ok(mut last_error) = self.last_error.lock() {
+            *last_error = Some(entry);
+        }
+    }
+
+    /// Most recent connect or health probe error, if any
+    pub fn last_error(&self) -> Option<LastError> {
+        self.last_error.lock().ok().and_then(|entry| entry.clone())
+    }
+
+    /// Copy health and error history from the state this backend replaces
+    ///
+    /// Active connections stay with the previous state, whose guards are
+    /// still held by in-flight relays.
+    fn inherit_from(&self, previous: &BackendState) {
+        self.healthy.store(previous.is_healthy(), Ordering::Relaxed);
+        for (dst, src) in [
+            (&self.consecutive_failures, &previous.consecutive_failures),
+            (&self.consecutive_successes, &previous.consecutive_successes),
+            (
+                &self.failover_failure_streak,
+                &previous.failover_failure_streak,
+            ),
+        ] {
+            dst.store(src.load(Ordering::Relaxed), Ordering::Relaxed);
+        }
+        for (dst, src) in [
+            (&self.cooldown_until_ms, &previous.cooldown_until_ms),
+            (&self.last_check_ms, &previous.last_check_ms),
+            (&self.recovered_at_ms, &previous.recovered_at_ms),
+            (&self.last_transition_ms, &previous.last_transition_ms),
+            (&self.timeout_count, &previous.timeout_count),
+            (&self.refused_count, &previous.refused_count),
+            (&self.other_error_count, &previous.other_error_count),
+            (&self.bytes_total, &previous.bytes_total),
+            (&self.latency_ewma_us, &previous.latency_ewma_us),
+        ] {
+            dst.store(src.load(Ordering::Relaxed), Ordering::Relaxed);
+        }
+        if let Some(entry) = previous.last_error() {
+            self.record_error_entry(entry);
+        }
+        if let (Ok(mut dst), Ok(src)) = (self.transitions.lock(), previous.transitions.lock()) {
+            dst.clone_from(&src);
+        }
+    }
+
+    pub(crate) fn now_unix_ms() -> u64 {
         SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .map(|d| d.as_millis() as u64)
@@ -273,6 +571,29 @@ impl Drop for ConnectionGuard {
     }
 }
 
+/// In-flight connect RAII guard
+///
+/// Held from backend selection until the connect attempt resolves, so
+/// least-connections sees backends a burst is already connecting to.
+pub struct PendingConnectGuard {
+    backend: Arc<BackendState>,
+}
+
+impl PendingConnectGuard {
+    pub fn new(backend: Arc<BackendState>) -> Self {
+        backend.pending_connects.fetch_add(1, Ordering::Relaxed);
+        Self { backend }
+    }
+}
+
+impl Drop for PendingConnectGuard {
+    fn drop(&mut self) {
+        self.backend
+            .pending_connects
+            .fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
 /// Backend pool
 ///
 /// Manages all backend states and provides list of healthy backends.
@@ -280,17 +601,77 @@ impl Drop for ConnectionGuard {
 pub struct BackendPool {
     /// List of all backend states
     backends: Vec<Arc<BackendState>>,
+    /// Last logged aggregate state: true once every backend was seen unhealthy
+    all_down: AtomicBool,
 }
 
 impl BackendPool {
     /// Create new backend pool
+    ///
+    /// Disabled backends are left out entirely.
     pub fn new(configs: Vec<BackendConfig>) -> Self {
         let backends = configs
             .into_iter()
+            .filter(|config| config.enabled)
             .map(|config| Arc::new(BackendState::new(config)))
             .collect();
 
-        Self { backends }
+        Self {
+            backends,
+            all_down: AtomicBool::new(false),
+        }
+    }
+
+    /// Build a pool for the same backends that keeps state from `previous`
+    ///
+    /// Backends with an unchanged config reuse the previous `BackendState`
+    /// outright (health, counters and active connections). Backends whose
+    /// host:port matches but whose settings changed get fresh state that
+    /// inherits health and error history. New backends start healthy.
+    pub fn carry_over_from(&self, previous: &BackendPool) -> BackendPool {
+        let backends = self
+            .backends
+            .iter()
+            .map(|backend| match previous.find_backend(&backend.address()) {
+                Some(existing) if existing.config == backend.config => existing,
+                Some(existing) => {
+                    backend.inherit_from(&existing);
+                    Arc::clone(backend)
+                }
+                None => Arc::clone(backend),
+            })
+            .collect();
+
+        BackendPool {
+            backends,
+            all_down: AtomicBool::new(previous.all_down.load(Ordering::Relaxed)),
+        }
+    }
+
+    /// Note whether any backend is healthy, logging only on a change
+    ///
+    /// Selection calls this on every connection, so the "all down" warning
+    /// and the recovery line are edge-triggered rather than repeated per
+    /// client. Returns whether the aggregate state flipped.
+    pub fn record_availability(&self, any_healthy: bool) -> bool {
+        // An empty pool (allow_empty_backends) is parked, not down
+        let all_down = !any_healthy && !self.backends.is_empty();
+        if self.all_down.swap(all_down, Ordering::Relaxed) == all_down {
+            return false;
+        }
+        if any_healthy {
+            log::info!(
+                "Backends available again: {}/{} healthy",
+                self.healthy_count(),
+                self.total_count()
+            );
+        } else {
+            log::warn!(
+                "All {} backends are unhealthy; new connections will fail until one recovers",
+                self.total_count()
+            );
+        }
+        true
     }
 
     /// Get all backend states
@@ -319,11 +700,11 @@ impl BackendPool {
         self.backends.len()
     }
 
-    /// Find specific backend (by host:port)
-    pub fn find_backend(&self, host: &str, port: u16) -> Option<Arc<BackendState>> {
+    /// Find specific backend by its `address()`
+    pub fn find_backend(&self, address: &str) -> Option<Arc<BackendState>> {
         self.backends
             .iter()
-            .find(|b| b.config.host == host && b.config.port == port)
+            .find(|b| b.address() == address)
             .cloned()
     }
 
@@ -341,9 +722,8 @@ impl BackendPool {
             };
             let conn = backend.active_connections();
             log::debug!(
-                "  - {}:{} [{}] (connections: {})",
-                backend.config.host,
-                backend.config.port,
+                "  - {} [{}] (connections: {})",
+                backend.address(),
                 status,
                 conn
             );
@@ -358,8 +738,60 @@ mod tests {
     fn create_test_backend(host: &str, port: u16) -> BackendConfig {
         BackendConfig {
             host: host.to_string(),
-            port,
+            ..BackendConfig::local(port)
+        }
+    }
+
+    #[test]
+    fn test_sustained_saturation_enters_and_leaves_degraded() {
+        let state = BackendState::new(create_test_backend("127.0.0.1", 8080));
+        for _ in 0..3 {
+            state.increment_connections();
         }
+
+        // Saturated, but not for long enough yet
+        state.update_saturation(3, 5_000, 10_000);
+        assert!(!state.is_degraded());
+        state.update_saturation(3, 5_000, 14_999);
+        assert!(!state.is_degraded());
+
+        state.update_saturation(3, 5_000, 15_000);
+        assert!(state.is_degraded());
+        assert!(state.is_healthy());
+
+        // Dropping below the limit clears it and restarts the window
+        state.decrement_connections();
+        state.update_saturation(3, 5_000, 16_000);
+        assert!(!state.is_degraded());
+        state.increment_connections();
+        state.update_saturation(3, 5_000, 17_000);
+        assert!(!state.is_degraded());
+    }
+
+    #[test]
+    fn health_transitions_are_timestamped_and_counted_as_flaps() {
+        let state = BackendState::new(create_test_backend("127.0.0.1", 8080));
+        assert_eq!(state.last_transition_ms(), 0);
+        assert_eq!(state.flap_count(60_000, BackendState::now_unix_ms()), 0);
+
+        // Failures below the threshold aren't a transition
+        state.mark_failure(2);
+        assert_eq!(state.last_transition_ms(), 0);
+
+        let before = BackendState::now_unix_ms();
+        state.mark_failure(2);
+        assert!(state.last_transition_ms() >= before);
+        state.mark_success(1);
+        state.mark_failure(1);
+        // Staying down is not another flip
+        state.mark_failure(1);
+
+        let now = BackendState::now_unix_ms();
+        assert_eq!(state.flap_count(60_000, now), 3);
+        assert!(state.last_transition_ms() <= now);
+
+        // Transitions age out of the window
+        assert_eq!(state.flap_count(60_000, now + 120_000), 0);
     }
 
     #[test]
@@ -400,11 +832,25 @@ mod tests {
         let config = create_test_backend("127.0.0.1", 8080);
         let state = BackendState::new(config);
 
-        state.mark_connect_failure(BackendErrorKind::Timeout, 1, 100, 1000, 200);
+        state.mark_connect_failure(BackendErrorKind::Timeout, 1, 100, 1000, 200, false);
         assert!(state.is_in_cooldown());
         assert_eq!(state.timeout_count(), 1);
     }
 
+    #[test]
+    fn test_slow_start_factor_ramps_after_recovery() {
+        let config = create_test_backend("127.0.0.1", 8080);
+        let state = BackendState::new(config);
+
+        // Never recovered: full share
+        assert_eq!(state.slow_start_factor(10_000), 1.0);
+
+        state.mark_failure(1);
+        state.mark_success(1);
+        assert!(state.slow_start_factor(10_000) < 0.1);
+        assert_eq!(state.slow_start_factor(0), 1.0);
+    }
+
     #[test]
     fn test_failure_tracking() {
         let config = create_test_backend("127.0.0.1", 8080);
@@ -430,4 +876,30 @@ mod tests {
         state.mark_success(3);
         assert!(state.is_healthy()); // Recovered
     }
+
+    #[test]
+    fn test_connect_failure_jitter_spreads_cooldown_windows() {
+        let windows: Vec<u64> = (0..8)
+            .map(|i| {
+                let state = BackendState::new(create_test_backend("127.0.0.1", 8080 + i));
+                let before = BackendState::now_unix_ms();
+                state.mark_connect_failure(
+                    BackendErrorKind::ConnectionRefused,
+                    1,
+                    10_000,
+                    10_000,
+                    0,
+                    true,
+                );
+                state.cooldown_until_ms() - before
+            })
+            .collect();
+
+        // Equal jitter keeps every window within [half, full]
+        assert!(windows.iter().all(|w| (5_000..=10_010).contains(w)));
+        assert!(
+            windows.iter().any(|w| *w != windows[0]),
+            "jittered windows should differ: {windows:?}"
+        );
+    }
 }
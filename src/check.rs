@@ -1,16 +1,101 @@
-use anyhow::{bail, Result};
-use serde::Serialize;
-use std::path::PathBuf;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::net::TcpListener;
+use std::path::{Path, PathBuf};
 
-use crate::config::Config;
+use crate::config::{unix_listen_path, Config, RuntimeSource, RuntimeTuning};
+use crate::config_store::ConfigStore;
+use crate::doctor::resolve_bind_target;
+use crate::error::BalError;
+use crate::health::for_each_bounded;
 use crate::operator_message::render_operator_message;
+use crate::output::OutputFormat;
+use crate::process::ProcessManager;
+use crate::proxy::unix_listen_problem;
 
-#[derive(Debug, Clone, Serialize)]
+/// Documented exit codes for `bal check` and `bal doctor`, so CI can branch on the outcome
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitStatus {
+    /// 0: no errors (warnings allowed unless `--strict`)
+    Ok,
+    /// 1: validation errors or critical findings
+    Errors,
+    /// 2: only warnings, but `--strict` was given
+    StrictWarnings,
+    /// 3: the configuration file does not exist
+    ConfigNotFound,
+}
+
+impl ExitStatus {
+    pub fn code(self) -> i32 {
+        match self {
+            ExitStatus::Ok => 0,
+            ExitStatus::Errors => 1,
+            ExitStatus::StrictWarnings => 2,
+            ExitStatus::ConfigNotFound => 3,
+        }
+    }
+
+    /// Map a `run_check` outcome to its exit status
+    pub fn for_check(outcome: &Result<CheckReport>, strict: bool) -> Self {
+        match outcome {
+            Err(err) if is_config_not_found(err) => ExitStatus::ConfigNotFound,
+            Err(_) => ExitStatus::Errors,
+            Ok(report) if report.has_errors() => ExitStatus::Errors,
+            Ok(report) if strict && report.has_warnings() => ExitStatus::StrictWarnings,
+            Ok(_) => ExitStatus::Ok,
+        }
+    }
+}
+
+fn is_config_not_found(err: &anyhow::Error) -> bool {
+    matches!(
+        err.downcast_ref::<BalError>(),
+        Some(BalError::ConfigNotFound(_))
+    )
+}
+
+/// Result of a one-shot TCP connect to a configured backend
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Connectivity {
+    Reachable,
+    Unreachable,
+    /// The host did not resolve, so no connect was attempted
+    Unresolved,
+    /// `enabled: false`, so not probed
+    Disabled,
+}
+
+impl Connectivity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Connectivity::Reachable => "reachable",
+            Connectivity::Unreachable => "unreachable",
+            Connectivity::Unresolved => "unresolved",
+            Connectivity::Disabled => "disabled",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackendCheck {
+    pub address: String,
+    pub connectivity: Connectivity,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CheckReport {
     pub config_path: String,
     pub errors: Vec<String>,
     pub warnings: Vec<String>,
     pub backend_count: usize,
+    pub backends: Vec<BackendCheck>,
+    /// Runtime tuning in effect, including auto-tuned values
+    pub runtime: RuntimeTuning,
+    pub runtime_source: RuntimeSource,
 }
 
 impl CheckReport {
@@ -64,6 +149,29 @@ impl CheckReport {
             }
         }
 
+        lines.push("  backend_connectivity:".to_string());
+        for backend in &self.backends {
+            let status = backend.connectivity.as_str();
+            match &backend.error {
+                Some(error) => {
+                    lines.push(format!("    - {}: {} ({})", backend.address, status, error))
+                }
+                None => lines.push(format!("    - {}: {}", backend.address, status)),
+            }
+        }
+
+        lines.push(format!(
+            "  runtime: {}{}",
+            self.runtime_source,
+            match self.runtime_source {
+                RuntimeSource::Explicit => "",
+                RuntimeSource::AutoTuned => " (runtime omitted; profile picked by backend count)",
+            }
+        ));
+        if let Ok(rendered) = serde_yaml::to_string(&self.runtime) {
+            lines.extend(rendered.lines().map(|line| format!("    {}", line)));
+        }
+
         if self.warnings.is_empty() {
             lines.push("  warning_details: none".to_string());
         } else {
@@ -77,7 +185,11 @@ impl CheckReport {
     }
 }
 
-pub async fn run_check(config_path: Option<PathBuf>) -> Result<CheckReport> {
+/// Statically check a config file
+///
+/// With `reload`, also run the daemon's pre-reload validation (load, parse,
+/// backend connectivity) against it without signaling the daemon.
+pub async fn run_check(config_path: Option<PathBuf>, reload: bool) -> Result<CheckReport> {
     let path = if let Some(path) = config_path {
         path
     } else {
@@ -85,43 +197,146 @@ pub async fn run_check(config_path: Option<PathBuf>) -> Result<CheckReport> {
     };
 
     if !path.exists() {
-        bail!("Configuration file not found: {}", path.display());
+        return Err(BalError::ConfigNotFound(path.display().to_string()).into());
     }
 
     let config = Config::load_from_file(&path).await?;
+    let mut errors = Vec::new();
     let mut warnings = Vec::new();
 
+    if reload {
+        if let Err(err) = ConfigStore::validate_reload_candidate(&path).await {
+            errors.push(format!("reload would be rejected: {:#}", err));
+        }
+    }
+
     if config.bind_address == "0.0.0.0" {
         warnings.push("bind_address is 0.0.0.0 (listens on all interfaces)".to_string());
     }
 
+    // A running daemon legitimately holds the port, so only probe when stopped
+    if !reload && !ProcessManager::is_daemon_running() {
+        if let Some(warning) = probe_bind(&config.bind_address, config.port) {
+            warnings.push(warning);
+        }
+    }
+
+    let backends = check_backend_connectivity(&config).await;
+    for backend in &backends {
+        if let Some(error) = &backend.error {
+            warnings.push(format!(
+                "backend {} is {}: {}",
+                backend.address,
+                backend.connectivity.as_str(),
+                error
+            ));
+        }
+    }
+
     Ok(CheckReport {
         config_path: path.display().to_string(),
-        errors: Vec::new(),
+        errors,
         warnings,
         backend_count: config.backends.len(),
+        backends,
+        runtime: config.runtime,
+        runtime_source: config.runtime_source,
     })
 }
 
+/// Probe every enabled backend once; unreachable backends are warnings, not errors
+///
+/// Probes run concurrently, at most `health_check_concurrency` at a time, so
+/// a few timing-out backends don't add up. Results keep the config order.
+pub(crate) async fn check_backend_connectivity(config: &Config) -> Vec<BackendCheck> {
+    for_each_bounded(
+        config.backends.clone(),
+        config.runtime.health_check_concurrency,
+        |backend| async move {
+            let address = backend.address();
+            let (connectivity, error) = if !backend.enabled {
+                (Connectivity::Disabled, None)
+            } else {
+                // Unix socket backends have no host to resolve
+                let resolved = match backend.path {
+                    Some(_) => Ok(()),
+                    None => backend.resolve_socket_addr().await.map(drop),
+                };
+                match resolved {
+                    Err(err) => (Connectivity::Unresolved, Some(err.to_string())),
+                    Ok(()) => match backend.check_connectivity().await {
+                        Ok(()) => (Connectivity::Reachable, None),
+                        Err(err) => (Connectivity::Unreachable, Some(err.to_string())),
+                    },
+                }
+            };
+            BackendCheck {
+                address,
+                connectivity,
+                error,
+            }
+        },
+    )
+    .await
+}
+
+/// Try binding the listen address and describe why it is unavailable, if it is.
+fn probe_bind(bind_address: &str, port: u16) -> Option<String> {
+    if let Some(path) = unix_listen_path(bind_address) {
+        return unix_listen_problem(Path::new(path));
+    }
+
+    let bind_target = format!("{}:{}", bind_address, port);
+
+    let socket_addr = match resolve_bind_target(&bind_target) {
+        Ok(addr) => addr,
+        Err(err) => return Some(format!("bind probe failed: {}", err)),
+    };
+
+    match TcpListener::bind(socket_addr) {
+        Ok(_) => None,
+        Err(err) if err.kind() == std::io::ErrorKind::AddrInUse => Some(format!(
+            "{} is already in use (run 'bal doctor' to inspect)",
+            bind_target
+        )),
+        Err(err) => Some(format!("cannot bind {}: {}", bind_target, err)),
+    }
+}
+
+/// Print the check report and return the exit status `main` should use
 pub async fn run_and_print(
     config_path: Option<PathBuf>,
     strict: bool,
-    json: bool,
+    format: OutputFormat,
     verbose: bool,
-) -> Result<()> {
-    let report = run_check(config_path).await?;
+    reload: bool,
+) -> Result<ExitStatus> {
+    let outcome = run_check(config_path, reload).await;
+    let status = ExitStatus::for_check(&outcome, strict);
 
-    if json {
-        println!("{}", serde_json::to_string_pretty(&report)?);
-    } else {
-        println!("{}", report.to_plain_text(verbose));
+    let report = match outcome {
+        Ok(report) => report,
+        Err(err) if status == ExitStatus::ConfigNotFound => {
+            eprintln!("Error: {}", err);
+            return Ok(status);
+        }
+        Err(err) => return Err(err),
+    };
+
+    match format.serialize(&report)? {
+        Some(rendered) => println!("{}", rendered),
+        None => println!("{}", report.to_plain_text(verbose)),
     }
 
-    if report.has_errors() || (strict && report.has_warnings()) {
-        bail!("static check failed")
+    if status != ExitStatus::Ok {
+        if reload {
+            eprintln!("reload validation failed");
+        } else {
+            eprintln!("static check failed");
+        }
     }
 
-    Ok(())
+    Ok(status)
 }
 
 #[cfg(test)]
@@ -134,6 +349,20 @@ mod tests {
             errors: Vec::new(),
             warnings: vec!["bind_address is 0.0.0.0 (listens on all interfaces)".to_string()],
             backend_count: 2,
+            backends: vec![
+                BackendCheck {
+                    address: "127.0.0.1:9000".to_string(),
+                    connectivity: Connectivity::Reachable,
+                    error: None,
+                },
+                BackendCheck {
+                    address: "127.0.0.1:9100".to_string(),
+                    connectivity: Connectivity::Unreachable,
+                    error: Some("Connection failed: connection refused".to_string()),
+                },
+            ],
+            runtime: RuntimeTuning::default(),
+            runtime_source: RuntimeSource::Explicit,
         }
     }
 
@@ -153,6 +382,7 @@ mod tests {
         assert!(rendered.contains("config: /tmp/bal.yaml"));
         assert!(rendered.contains("warning_details:"));
         assert!(rendered.contains("bind_address is 0.0.0.0"));
+        assert!(rendered.contains("127.0.0.1:9100: unreachable (Connection failed"));
         assert!(!rendered.contains("mode:"));
     }
 
@@ -163,4 +393,197 @@ mod tests {
         assert!(rendered.contains("why_likely:"));
         assert!(rendered.contains("do_this_now:"));
     }
+
+    #[test]
+    fn bind_probe_warns_when_port_is_in_use() {
+        let holder = TcpListener::bind("127.0.0.1:0").expect("bind holder");
+        let port = holder.local_addr().unwrap().port();
+
+        let warning = probe_bind("127.0.0.1", port).expect("in-use port should warn");
+        assert!(warning.contains("already in use"));
+
+        drop(holder);
+        assert!(probe_bind("127.0.0.1", port).is_none());
+    }
+
+    fn write_candidate(dir: &std::path::Path, backend_port: u16) -> PathBuf {
+        let path = dir.join("candidate.yaml");
+        std::fs::write(
+            &path,
+            format!(
+                "port: 9295\nbackends:\n  - host: \"127.0.0.1\"\n    port: {}\n",
+                backend_port
+            ),
+        )
+        .expect("candidate should be written");
+        path
+    }
+
+    #[tokio::test]
+    async fn reload_check_passes_for_reachable_candidate() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let backend = TcpListener::bind("127.0.0.1:0").expect("bind backend");
+        let path = write_candidate(dir.path(), backend.local_addr().unwrap().port());
+
+        let report = run_check(Some(path), true).await.expect("check should run");
+        assert!(!report.has_errors(), "errors: {:?}", report.errors);
+    }
+
+    #[tokio::test]
+    async fn reload_check_fails_when_no_backend_is_reachable() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let port = {
+            let listener = TcpListener::bind("127.0.0.1:0").expect("bind backend");
+            listener.local_addr().unwrap().port()
+        };
+        let path = write_candidate(dir.path(), port);
+
+        let report = run_check(Some(path.clone()), true)
+            .await
+            .expect("check should run");
+        assert!(report.has_errors());
+        assert!(report.errors[0].contains("reload would be rejected"));
+
+        // A plain check reports unreachable backends as warnings only
+        let report = run_check(Some(path), false)
+            .await
+            .expect("check should run");
+        assert!(!report.has_errors());
+        assert_eq!(report.backends[0].connectivity, Connectivity::Unreachable);
+    }
+
+    #[tokio::test]
+    async fn verbose_report_shows_effective_runtime_and_its_source() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let backends = "backends:\n  - host: \"127.0.0.1\"\n    port: 9\n";
+
+        let auto = dir.path().join("auto.yaml");
+        std::fs::write(&auto, format!("port: 9295\n{}", backends)).unwrap();
+        let report = run_check(Some(auto), false)
+            .await
+            .expect("check should run");
+        assert_eq!(report.runtime_source, RuntimeSource::AutoTuned);
+        let text = report.to_plain_text(true);
+        assert!(text.contains("runtime: auto_tuned"));
+        // Auto-tuned values for a single backend are listed
+        assert!(text.contains("    health_check_interval_ms: 500"));
+
+        let explicit = dir.path().join("explicit.yaml");
+        std::fs::write(
+            &explicit,
+            format!(
+                "port: 9295\nruntime:\n  health_check_interval_ms: 1234\n{}",
+                backends
+            ),
+        )
+        .unwrap();
+        let report = run_check(Some(explicit), false)
+            .await
+            .expect("check should run");
+        assert_eq!(report.runtime_source, RuntimeSource::Explicit);
+        let text = report.to_plain_text(true);
+        assert!(text.contains("runtime: explicit"));
+        assert!(text.contains("    health_check_interval_ms: 1234"));
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["runtime_source"], "explicit");
+        assert_eq!(json["runtime"]["health_check_interval_ms"], 1234);
+    }
+
+    #[tokio::test]
+    async fn json_report_includes_per_backend_connectivity() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let reachable = TcpListener::bind("127.0.0.1:0").expect("bind backend");
+        let closed_port = {
+            let listener = TcpListener::bind("127.0.0.1:0").expect("bind backend");
+            listener.local_addr().unwrap().port()
+        };
+        let path = dir.path().join("bal.yaml");
+        std::fs::write(
+            &path,
+            format!(
+                "port: 9295\nbackends:\n  - host: \"127.0.0.1\"\n    port: {}\n  - host: \"127.0.0.1\"\n    port: {}\n  - host: \"127.0.0.1\"\n    port: 9\n    enabled: false\n",
+                reachable.local_addr().unwrap().port(),
+                closed_port
+            ),
+        )
+        .expect("config should be written");
+
+        let report = run_check(Some(path), false)
+            .await
+            .expect("check should run");
+        let json = serde_json::to_value(&report).expect("report should serialize");
+        let backends = json["backends"].as_array().expect("backends array");
+
+        assert_eq!(backends.len(), 3);
+        assert_eq!(backends[0]["connectivity"], "reachable");
+        assert!(backends[0].get("error").is_none());
+        assert_eq!(backends[1]["address"], format!("127.0.0.1:{}", closed_port));
+        assert_eq!(backends[1]["connectivity"], "unreachable");
+        assert!(backends[1]["error"].is_string());
+        assert_eq!(backends[2]["connectivity"], "disabled");
+        assert!(report
+            .warnings
+            .iter()
+            .any(|w| w.contains(&format!("127.0.0.1:{} is unreachable", closed_port))));
+    }
+
+    #[test]
+    fn exit_status_distinguishes_errors_strict_warnings_and_missing_config() {
+        let warned = sample_report();
+        let clean = CheckReport {
+            warnings: Vec::new(),
+            ..sample_report()
+        };
+        let failed = CheckReport {
+            errors: vec!["reload would be rejected".to_string()],
+            ..sample_report()
+        };
+
+        assert_eq!(ExitStatus::for_check(&Ok(clean), true), ExitStatus::Ok);
+        assert_eq!(
+            ExitStatus::for_check(&Ok(warned.clone()), false),
+            ExitStatus::Ok
+        );
+        assert_eq!(
+            ExitStatus::for_check(&Ok(warned), true),
+            ExitStatus::StrictWarnings
+        );
+        assert_eq!(
+            ExitStatus::for_check(&Ok(failed), false),
+            ExitStatus::Errors
+        );
+
+        let missing = Err(BalError::ConfigNotFound("/nope.yaml".to_string()).into());
+        assert_eq!(
+            ExitStatus::for_check(&missing, false),
+            ExitStatus::ConfigNotFound
+        );
+        let unparsable = Err(anyhow::anyhow!("Configuration file parsing failed"));
+        assert_eq!(
+            ExitStatus::for_check(&unparsable, false),
+            ExitStatus::Errors
+        );
+
+        let codes: Vec<i32> = [
+            ExitStatus::Ok,
+            ExitStatus::Errors,
+            ExitStatus::StrictWarnings,
+            ExitStatus::ConfigNotFound,
+        ]
+        .iter()
+        .map(|status| status.code())
+        .collect();
+        assert_eq!(codes, vec![0, 1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn missing_config_file_maps_to_config_not_found() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let outcome = run_check(Some(dir.path().join("missing.yaml")), false).await;
+        assert_eq!(
+            ExitStatus::for_check(&outcome, false),
+            ExitStatus::ConfigNotFound
+        );
+    }
 }
@@ -3,9 +3,13 @@
 //! Uses clap derive macros to declaratively define commands and arguments.
 //! This approach ensures type safety and automatically generates --help and --version.
 
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 use std::path::PathBuf;
 
+use crate::config::LabelSelector;
+use crate::output::OutputFormat;
+use crate::top::TrafficMetric;
+
 /// bal - Ultra-lightweight TCP Load Balancer
 #[derive(Parser, Debug)]
 #[command(
@@ -24,6 +28,7 @@ Service control:
   bal start -d  # Start as background daemon
   bal stop      # Stop running daemon
   bal graceful  # Reload config without downtime
+  bal reload --config <FILE>  # Switch to another config file without downtime
 "#,
     version = env!("CARGO_PKG_VERSION"),
     author = "bal Team"
@@ -33,9 +38,50 @@ pub struct Cli {
     #[command(subcommand)]
     pub command: Commands,
 
-    /// Enable verbose logging (advanced)
+    /// Enable verbose logging (advanced); forces debug level
     #[arg(short, long, help = "[advanced] Enable verbose logging output")]
     pub verbose: bool,
+
+    /// Log level for this run, overriding the config's log_level
+    #[arg(
+        long,
+        global = true,
+        value_name = "LEVEL",
+        value_parser = ["debug", "info", "warn", "error"],
+        help = "Override the config's log_level for this run (debug, info, warn, error)"
+    )]
+    pub log_level: Option<String>,
+
+    /// Directory for PID, log and state files (overrides BAL_RUNTIME_DIR)
+    #[arg(
+        long,
+        global = true,
+        value_name = "DIR",
+        help = "Directory for PID, log and state files (default: $BAL_RUNTIME_DIR or ~/.bal)"
+    )]
+    pub runtime_dir: Option<PathBuf>,
+
+    /// Named instance; namespaces the PID, log and state files
+    #[arg(
+        long,
+        global = true,
+        value_name = "NAME",
+        value_parser = parse_instance_name,
+        help = "Run or control a named instance (files become e.g. bal-<NAME>.pid)"
+    )]
+    pub instance: Option<String>,
+}
+
+/// Instance names end up in file names: letters, digits, '-' and '_' only
+fn parse_instance_name(name: &str) -> Result<String, String> {
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err("use letters, digits, '-' or '_'".to_string());
+    }
+    Ok(name.to_string())
 }
 
 /// Available subcommands
@@ -51,6 +97,24 @@ pub enum Commands {
         /// Run as daemon in background
         #[arg(short, long, help = "Run as daemon in background")]
         daemon: bool,
+
+        /// Retries while the config file is missing or unreadable at startup
+        #[arg(
+            long,
+            value_name = "N",
+            default_value_t = 0,
+            help = "[advanced] Wait for a missing/unreadable config file, retrying N times"
+        )]
+        startup_config_retries: u32,
+
+        /// First delay between startup config retries; doubles per retry
+        #[arg(
+            long,
+            value_name = "MS",
+            default_value_t = 500,
+            help = "[advanced] First delay between startup config retries in milliseconds (doubles per retry, up to 10s)"
+        )]
+        startup_config_retry_ms: u64,
     },
 
     /// Stop running daemon
@@ -58,8 +122,50 @@ pub enum Commands {
     Stop,
 
     /// Reload configuration without downtime (graceful reload)
-    #[command(name = "graceful", about = "Reload configuration without downtime")]
-    Graceful,
+    #[command(
+        name = "graceful",
+        visible_alias = "reload",
+        about = "Reload configuration without downtime"
+    )]
+    Graceful {
+        /// Switch to a different configuration file
+        #[arg(
+            short,
+            long,
+            value_name = "FILE",
+            help = "Switch the running daemon to this configuration file"
+        )]
+        config: Option<PathBuf>,
+    },
+
+    /// Refuse new clients while health checks and open connections continue
+    #[command(
+        name = "maintenance",
+        about = "Turn maintenance mode on or off for the running daemon"
+    )]
+    Maintenance {
+        /// on: close new client connections immediately; off: resume accepting
+        #[arg(value_enum)]
+        mode: MaintenanceMode,
+    },
+
+    /// Change the running daemon's log level without a restart
+    #[command(
+        name = "log-level",
+        about = "Set the running daemon's log level, or toggle debug logging when no level is given (SIGUSR2)"
+    )]
+    LogLevel {
+        /// New level; omit to toggle debug on/off
+        #[arg(value_parser = ["debug", "info", "warn", "error"])]
+        level: Option<String>,
+    },
+
+    /// Zero backend error and byte counters on the running daemon
+    #[command(
+        name = "reset-stats",
+        about = "Zero backend error/byte counters and the protection window (health state is kept)"
+    )]
+    ResetStats,
 
     /// Validate static configuration
     #[command(
@@ -80,13 +186,32 @@ pub enum Commands {
         #[arg(long, help = "[advanced] Return non-zero when warnings are present")]
         strict: bool,
 
-        /// Print check report in JSON format
-        #[arg(long, help = "Print check report in JSON format")]
+        /// Print check report in JSON format (deprecated alias for --format json)
+        #[arg(
+            long,
+            help = "Print check report in JSON format (same as --format json)"
+        )]
         json: bool,
 
+        /// Output format (text, json, yaml)
+        #[arg(
+            long,
+            value_enum,
+            value_name = "FORMAT",
+            help = "Output format: text, json or yaml"
+        )]
+        format: Option<OutputFormat>,
+
         /// Print detailed check output
         #[arg(long, help = "Print detailed check output")]
         verbose: bool,
+
+        /// Dry-run the daemon's pre-reload validation against the file
+        #[arg(
+            long,
+            help = "Validate the file as a reload candidate (parse + backend connectivity) without signaling the daemon"
+        )]
+        reload: bool,
     },
 
     /// Observe local process and backend state
@@ -104,10 +229,19 @@ pub enum Commands {
         )]
         config: Option<PathBuf>,
 
-        /// Print status in JSON format
-        #[arg(long, help = "Print status in JSON format")]
+        /// Print status in JSON format (deprecated alias for --format json)
+        #[arg(long, help = "Print status in JSON format (same as --format json)")]
         json: bool,
 
+        /// Output format (text, json, yaml)
+        #[arg(
+            long,
+            value_enum,
+            value_name = "FORMAT",
+            help = "Output format: text, json or yaml"
+        )]
+        format: Option<OutputFormat>,
+
         /// Print compact status output
         #[arg(long, help = "[advanced] Force compact status output (default)")]
         brief: bool,
@@ -115,6 +249,30 @@ pub enum Commands {
         /// Print detailed status output
         #[arg(long, help = "Print detailed status output")]
         verbose: bool,
+
+        /// Only show backends carrying this label
+        #[arg(
+            long,
+            value_name = "KEY=VALUE",
+            help = "Only show backend details with this label (e.g. zone=us-east)"
+        )]
+        label: Option<LabelSelector>,
+
+        /// Only show the backend with this address
+        #[arg(
+            long,
+            value_name = "HOST:PORT",
+            help = "Only show details for this backend (e.g. 10.0.0.1:9000)"
+        )]
+        backend: Option<String>,
+
+        /// Print nothing; report health through the exit code
+        #[arg(
+            short,
+            long,
+            help = "Print nothing; exit 0 = all backends reachable, 1 = degraded, 2 = not running"
+        )]
+        quiet: bool,
     },
 
     /// Run runtime diagnostics and environment checks
@@ -132,10 +290,22 @@ pub enum Commands {
         )]
         config: Option<PathBuf>,
 
-        /// Print diagnostics in JSON format
-        #[arg(long, help = "Print diagnostics in JSON format")]
+        /// Print diagnostics in JSON format (deprecated alias for --format json)
+        #[arg(
+            long,
+            help = "Print diagnostics in JSON format (same as --format json)"
+        )]
         json: bool,
 
+        /// Output format (text, json, yaml)
+        #[arg(
+            long,
+            value_enum,
+            value_name = "FORMAT",
+            help = "Output format: text, json or yaml"
+        )]
+        format: Option<OutputFormat>,
+
         /// Print compact diagnostics output
         #[arg(long, help = "[advanced] Force compact diagnostics output (default)")]
         brief: bool,
@@ -144,6 +314,168 @@ pub enum Commands {
         #[arg(long, help = "Print detailed diagnostics output")]
         verbose: bool,
     },
+
+    /// Run check and doctor as one deploy gate
+    #[command(
+        name = "preflight",
+        about = "Validate config, bindability and backend connectivity in one report/exit code (CI/deploy gate)"
+    )]
+    Preflight {
+        /// Configuration file path to check
+        #[arg(short, long, value_name = "FILE", help = "Configuration file path")]
+        config: Option<PathBuf>,
+
+        /// Print the report in JSON format (same as --format json)
+        #[arg(long, help = "Print the report in JSON format (same as --format json)")]
+        json: bool,
+
+        /// Output format (text, json, yaml)
+        #[arg(
+            long,
+            value_enum,
+            value_name = "FORMAT",
+            help = "Output format: text, json or yaml"
+        )]
+        format: Option<OutputFormat>,
+
+        /// Include the full check and doctor reports
+        #[arg(long, help = "Include the full check and doctor reports")]
+        verbose: bool,
+    },
+
+    /// Rank backends by live traffic
+    #[command(
+        name = "top-backends",
+        about = "Rank backends by active connections or bytes relayed (who's hot)"
+    )]
+    TopBackends {
+        /// Configuration file path
+        #[arg(short, long, value_name = "FILE", help = "Configuration file path")]
+        config: Option<PathBuf>,
+
+        /// Ranking metric
+        #[arg(long, value_enum, default_value_t = TrafficMetric::Connections)]
+        by: TrafficMetric,
+
+        /// Show at most this many backends
+        #[arg(long, value_name = "N", default_value_t = 10)]
+        limit: usize,
+    },
+
+    /// Print (and follow) the daemon log
+    #[command(
+        name = "logs",
+        about = "Print the tail of the daemon log (bal.log in the runtime directory)"
+    )]
+    Logs {
+        /// Keep printing lines as they are written
+        #[arg(short, long)]
+        follow: bool,
+
+        /// Number of lines to show from the end of the log
+        #[arg(short = 'n', long, value_name = "N", default_value_t = 50)]
+        lines: usize,
+    },
+
+    /// Print version and build metadata
+    #[command(
+        name = "version",
+        about = "Print version, git commit, build date and enabled features"
+    )]
+    Version {
+        /// Print build metadata in JSON format
+        #[arg(long, help = "Print version info in JSON format")]
+        json: bool,
+    },
+
+    /// Print the JSON Schema for the config file
+    #[command(
+        name = "schema",
+        about = "Print the config file JSON Schema (for editor validation/autocomplete)"
+    )]
+    Schema,
+
+    /// Edit a single config field in place
+    #[command(
+        name = "set",
+        about = "Edit one config field in place, keeping comments (e.g. bal set runtime.max_concurrent_connections 20000)"
+    )]
+    Set {
+        /// Dotted config key (e.g. port, runtime.backend_connect_timeout_ms)
+        key: String,
+
+        /// New value, written as a YAML scalar
+        value: String,
+
+        /// Configuration file path to edit
+        #[arg(
+            short,
+            long,
+            value_name = "FILE",
+            help = "Configuration file path to edit"
+        )]
+        config: Option<PathBuf>,
+
+        /// Gracefully reload the running daemon after writing
+        #[arg(long, help = "Gracefully reload the running daemon after writing")]
+        reload: bool,
+    },
+
+    /// Append a backend to the config file
+    #[command(name = "add-backend", about = "Add a backend to the config file")]
+    AddBackend {
+        /// Backend address to add
+        #[arg(value_name = "HOST:PORT")]
+        address: String,
+
+        /// Relative share for weighted_random
+        #[arg(long, value_name = "N", help = "Backend weight (default 1)")]
+        weight: Option<u32>,
+
+        /// Configuration file path to edit
+        #[arg(
+            short,
+            long,
+            value_name = "FILE",
+            help = "Configuration file path to edit"
+        )]
+        config: Option<PathBuf>,
+
+        /// Gracefully reload the running daemon after writing
+        #[arg(long, help = "Gracefully reload the running daemon after writing")]
+        reload: bool,
+    },
+
+    /// Remove a backend from the config file
+    #[command(
+        name = "remove-backend",
+        about = "Remove a backend from the config file"
+    )]
+    RemoveBackend {
+        /// Backend address to remove
+        #[arg(value_name = "HOST:PORT")]
+        address: String,
+
+        /// Configuration file path to edit
+        #[arg(
+            short,
+            long,
+            value_name = "FILE",
+            help = "Configuration file path to edit"
+        )]
+        config: Option<PathBuf>,
+
+        /// Gracefully reload the running daemon after writing
+        #[arg(long, help = "Gracefully reload the running daemon after writing")]
+        reload: bool,
+    },
+}
+
+/// Target state for `bal maintenance`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum MaintenanceMode {
+    On,
+    Off,
 }
 
 impl Cli {
@@ -192,6 +524,13 @@ mod tests {
         }
     }
 
+    #[test]
+    fn status_accepts_quiet() {
+        let cli = Cli::try_parse_from(["bal", "status", "-q", "--backend", "10.0.0.1:9000"])
+            .expect("status --quiet should parse");
+        assert!(matches!(cli.command, Commands::Status { quiet: true, .. }));
+    }
+
     #[test]
     fn doctor_accepts_brief_and_verbose_flags() {
         let cli = Cli::try_parse_from(["bal", "doctor", "--brief", "--verbose"])
@@ -205,4 +544,176 @@ mod tests {
             _ => panic!("expected doctor command"),
         }
     }
+
+    #[test]
+    fn reload_alias_accepts_config_path() {
+        let cli = Cli::try_parse_from(["bal", "reload", "--config", "/tmp/next.yaml"])
+            .expect("reload command should parse");
+
+        match cli.command {
+            Commands::Graceful { config } => {
+                assert_eq!(config, Some(PathBuf::from("/tmp/next.yaml")));
+            }
+            _ => panic!("expected graceful command"),
+        }
+    }
+
+    #[test]
+    fn status_accepts_label_filter() {
+        let cli = Cli::try_parse_from(["bal", "status", "--label", "zone=us-east"])
+            .expect("status command should parse");
+
+        match cli.command {
+            Commands::Status { label, .. } => {
+                let label = label.expect("label should be set");
+                assert_eq!(label.key, "zone");
+                assert_eq!(label.value, "us-east");
+            }
+            _ => panic!("expected status command"),
+        }
+
+        assert!(Cli::try_parse_from(["bal", "status", "--label", "zone"]).is_err());
+    }
+
+    #[test]
+    fn schema_command_parses() {
+        let cli = Cli::try_parse_from(["bal", "schema"]).expect("schema command should parse");
+        assert!(matches!(cli.command, Commands::Schema));
+    }
+
+    #[test]
+    fn top_backends_parses_metric_and_limit() {
+        let cli = Cli::try_parse_from(["bal", "top-backends", "--by", "bytes", "--limit", "3"])
+            .expect("top-backends should parse");
+        assert!(matches!(
+            cli.command,
+            Commands::TopBackends {
+                by: TrafficMetric::Bytes,
+                limit: 3,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn log_level_flag_is_global_and_validated() {
+        let cli = Cli::try_parse_from(["bal", "start", "--log-level", "debug"])
+            .expect("--log-level should be accepted after the subcommand");
+        assert_eq!(cli.log_level.as_deref(), Some("debug"));
+
+        let cli = Cli::try_parse_from(["bal", "-v", "status"]).expect("-v should parse");
+        assert!(cli.verbose);
+
+        assert!(Cli::try_parse_from(["bal", "start", "--log-level", "loud"]).is_err());
+    }
+
+    #[test]
+    fn instance_flag_is_global_and_file_name_safe() {
+        let cli = Cli::try_parse_from(["bal", "graceful", "--instance", "web-1"])
+            .expect("--instance should be accepted after the subcommand");
+        assert_eq!(cli.instance.as_deref(), Some("web-1"));
+        assert!(Cli::try_parse_from(["bal", "stop", "--instance", "../etc"]).is_err());
+        assert!(Cli::try_parse_from(["bal", "stop", "--instance", ""]).is_err());
+    }
+
+    #[test]
+    fn runtime_dir_flag_is_global() {
+        let cli = Cli::try_parse_from(["bal", "status", "--runtime-dir", "/run/bal-a"])
+            .expect("--runtime-dir should be accepted after the subcommand");
+        assert_eq!(cli.runtime_dir, Some(PathBuf::from("/run/bal-a")));
+        let cli = Cli::try_parse_from(["bal", "stop"]).expect("should parse");
+        assert!(cli.runtime_dir.is_none());
+    }
+
+    #[test]
+    fn log_level_command_takes_an_optional_level() {
+        let cli = Cli::try_parse_from(["bal", "log-level", "debug"]).expect("should parse");
+        assert!(matches!(
+            cli.command,
+            Commands::LogLevel { level: Some(ref level) } if level == "debug"
+        ));
+        let cli = Cli::try_parse_from(["bal", "log-level"]).expect("should parse");
+        assert!(matches!(cli.command, Commands::LogLevel { level: None }));
+    }
+
+    #[test]
+    fn logs_parses_follow_and_lines() {
+        let cli = Cli::try_parse_from(["bal", "logs", "-f", "-n", "20"])
+            .expect("logs command should parse");
+        assert!(matches!(
+            cli.command,
+            Commands::Logs {
+                follow: true,
+                lines: 20
+            }
+        ));
+    }
+
+    #[test]
+    fn preflight_parses_json_flag() {
+        let cli =
+            Cli::try_parse_from(["bal", "preflight", "--json"]).expect("preflight should parse");
+        assert!(matches!(
+            cli.command,
+            Commands::Preflight { json: true, .. }
+        ));
+    }
+
+    #[test]
+    fn version_command_parses_json_flag() {
+        let cli = Cli::try_parse_from(["bal", "version", "--json"])
+            .expect("version command should parse");
+        assert!(matches!(cli.command, Commands::Version { json: true }));
+    }
+
+    #[test]
+    fn set_command_parses_key_value_and_reload() {
+        let cli = Cli::try_parse_from([
+            "bal",
+            "set",
+            "runtime.backend_connect_timeout_ms",
+            "500",
+            "--reload",
+        ])
+        .expect("set command should parse");
+
+        match cli.command {
+            Commands::Set {
+                key, value, reload, ..
+            } => {
+                assert_eq!(key, "runtime.backend_connect_timeout_ms");
+                assert_eq!(value, "500");
+                assert!(reload);
+            }
+            _ => panic!("expected set command"),
+        }
+    }
+
+    #[test]
+    fn maintenance_command_takes_on_or_off() {
+        let cli = Cli::try_parse_from(["bal", "maintenance", "on"])
+            .expect("maintenance command should parse");
+        assert!(matches!(
+            cli.command,
+            Commands::Maintenance {
+                mode: MaintenanceMode::On
+            }
+        ));
+
+        assert!(Cli::try_parse_from(["bal", "maintenance", "maybe"]).is_err());
+    }
+
+    #[test]
+    fn format_option_accepts_yaml() {
+        let cli = Cli::try_parse_from(["bal", "doctor", "--format", "yaml"])
+            .expect("doctor command should parse");
+
+        match cli.command {
+            Commands::Doctor { format, json, .. } => {
+                assert_eq!(format, Some(OutputFormat::Yaml));
+                assert!(!json);
+            }
+            _ => panic!("expected doctor command"),
+        }
+    }
 }
@@ -1,40 +1,47 @@
 //! Configuration file management module
 //!
-//! Handles YAML configuration file parsing, validation, and default values.
+//! Handles configuration file parsing (YAML, TOML, JSON), validation, and default values.
 //! Uses Serde to declaratively define configuration structure with
 //! strong validation.
 
 use anyhow::{bail, Context, Result};
+use schemars::JsonSchema;
 use serde::{Deserialize, Deserializer, Serialize};
-use std::collections::HashSet;
-use std::net::SocketAddr;
+use std::collections::{BTreeMap, HashSet};
+use std::net::{IpAddr, SocketAddr};
 use std::path::Path;
 use std::time::Duration;
 use tokio::fs;
-use tokio::net::{lookup_host, TcpStream};
+use tokio::net::{lookup_host, TcpStream, UnixStream};
 
 use crate::constants::{
-    get_home_config_path, get_system_config_path, DEFAULT_PORT, HEALTH_CHECK_INTERVAL_MS,
+    get_home_config_path, get_system_config_path, DEFAULT_ACCEPT_ERROR_BACKOFF_MAX_MS,
+    DEFAULT_BACKEND_KEEPALIVE_IDLE_MS, DEFAULT_BACKEND_KEEPALIVE_POOL_SIZE,
+    DEFAULT_HEALTH_CHECK_CONCURRENCY, DEFAULT_PORT, HEALTH_CHECK_INTERVAL_MS,
     HEALTH_CHECK_MAX_RETRIES, HEALTH_CHECK_MIN_SUCCESS, HEALTH_CHECK_TIMEOUT_MS,
+    MAX_RELAY_BUFFER_SIZE, MIN_RELAY_BUFFER_SIZE, PROXY_BUFFER_SIZE,
 };
+use crate::error::BalError;
+use crate::protection::StormLimits;
+use crate::upstream::{self, UpstreamProxy};
 
 /// Load balancing algorithm types
 ///
-/// Currently only Round Robin is implemented. Defined as enum for future extensions.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+/// Round Robin, Least Connections, Random, Weighted Random and Least Response Time.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum BalanceMethod {
     /// Round Robin: Select backends sequentially
+    #[default]
     RoundRobin,
-    /// Least Connections: Select backend with fewest active connections (future implementation)
-    #[serde(skip)]
+    /// Least Connections: Select the healthy backend with the fewest active and in-flight connections
     LeastConnections,
-}
-
-impl Default for BalanceMethod {
-    fn default() -> Self {
-        BalanceMethod::RoundRobin
-    }
+    /// Random: Select a healthy backend uniformly at random
+    Random,
+    /// Weighted Random: Select a healthy backend with probability proportional to `weight`
+    WeightedRandom,
+    /// Least Response Time: Select the healthy backend with the lowest smoothed probe latency
+    LeastResponseTime,
 }
 
 impl std::fmt::Display for BalanceMethod {
@@ -42,25 +49,166 @@ impl std::fmt::Display for BalanceMethod {
         match self {
             BalanceMethod::RoundRobin => write!(f, "round_robin"),
             BalanceMethod::LeastConnections => write!(f, "least_connections"),
+            BalanceMethod::Random => write!(f, "random"),
+            BalanceMethod::WeightedRandom => write!(f, "weighted_random"),
+            BalanceMethod::LeastResponseTime => write!(f, "least_response_time"),
+        }
+    }
+}
+
+/// `key=value` label selector (e.g. `zone=us-east`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct LabelSelector {
+    pub key: String,
+    pub value: String,
+}
+
+impl std::str::FromStr for LabelSelector {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let (key, value) = s
+            .split_once('=')
+            .ok_or_else(|| format!("expected key=value, got '{}'", s))?;
+        let (key, value) = (key.trim(), value.trim());
+        if key.is_empty() {
+            return Err(format!("label key is empty in '{}'", s));
+        }
+
+        Ok(Self {
+            key: key.to_string(),
+            value: value.to_string(),
+        })
+    }
+}
+
+impl LabelSelector {
+    /// Whether `labels` carries the selected key=value pair
+    pub fn matches(&self, labels: &BTreeMap<String, String>) -> bool {
+        labels.get(&self.key) == Some(&self.value)
+    }
+}
+
+impl TryFrom<String> for LabelSelector {
+    type Error = String;
+
+    fn try_from(value: String) -> std::result::Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<LabelSelector> for String {
+    fn from(selector: LabelSelector) -> Self {
+        selector.to_string()
+    }
+}
+
+impl std::fmt::Display for LabelSelector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}={}", self.key, self.value)
+    }
+}
+
+impl JsonSchema for LabelSelector {
+    fn schema_name() -> String {
+        "LabelSelector".to_string()
+    }
+
+    fn json_schema(_gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        schemars::schema::SchemaObject {
+            instance_type: Some(schemars::schema::InstanceType::String.into()),
+            string: Some(Box::new(schemars::schema::StringValidation {
+                pattern: Some(r"^\s*[^=\s][^=]*=".to_string()),
+                ..Default::default()
+            })),
+            metadata: Some(Box::new(schemars::schema::Metadata {
+                description: Some("`key=value` label selector (e.g. `zone=us-east`)".to_string()),
+                ..Default::default()
+            })),
+            ..Default::default()
         }
+        .into()
     }
 }
 
 /// Individual backend server configuration
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
 pub struct BackendConfig {
-    /// Backend host (IP address or hostname)
+    /// Backend host (IP address or hostname); omitted for `path` backends
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub host: String,
 
-    /// Backend port number
+    /// Backend port number; omitted for `path` backends
+    #[serde(default, skip_serializing_if = "is_zero_port")]
     pub port: u16,
+
+    /// Unix domain socket to relay to instead of `host`/`port`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+
+    /// Relative share for weighted_random (default 1)
+    #[serde(default = "default_backend_weight")]
+    #[schemars(range(min = 1))]
+    pub weight: u32,
+
+    /// Disabled backends stay in the file but receive no traffic or health checks
+    #[serde(default = "default_backend_enabled")]
+    pub enabled: bool,
+
+    /// Free-form grouping labels (e.g. zone: us-east, role: primary)
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
+
+    /// Connect timeout for this backend, overriding the runtime-wide value
+    #[serde(default)]
+    #[schemars(range(min = 1))]
+    pub connect_timeout_ms: Option<u64>,
+
+    /// Health check interval for this backend, overriding the runtime-wide value
+    #[serde(default)]
+    #[schemars(range(min = 1))]
+    pub health_check_interval_ms: Option<u64>,
+
+    /// Failover tier: 0 = primary; a higher tier only gets traffic while
+    /// every backend of all lower tiers is unhealthy (active/standby)
+    #[serde(default)]
+    pub tier: u32,
+}
+
+fn is_zero_port(port: &u16) -> bool {
+    *port == 0
 }
 
 impl BackendConfig {
+    /// `host:port`, or `unix:<path>` for Unix socket backends
+    pub fn address(&self) -> String {
+        match &self.path {
+            Some(path) => format!("unix:{}", path),
+            None => format!("{}:{}", self.host, self.port),
+        }
+    }
+
+    /// Whether this backend carries the label selected by `selector`
+    pub fn matches_label(&self, selector: &LabelSelector) -> bool {
+        selector.matches(&self.labels)
+    }
+
+    /// Connect timeout for this backend, falling back to `default_ms`
+    pub fn connect_timeout(&self, default_ms: u64) -> Duration {
+        Duration::from_millis(self.connect_timeout_ms.unwrap_or(default_ms))
+    }
+
+    /// Health check interval for this backend, falling back to `default_ms`
+    pub fn health_check_interval(&self, default_ms: u64) -> Duration {
+        Duration::from_millis(self.health_check_interval_ms.unwrap_or(default_ms))
+    }
+
     /// Convert to SocketAddr for TCP connection.
     ///
     /// This method validates literal IP:port input.
     /// For hostname support, use `resolve_socket_addr` in async contexts.
+    #[cfg(test)]
     pub fn to_socket_addr(&self) -> Result<SocketAddr> {
         let addr_str = format!("{}:{}", self.host, self.port);
         addr_str
@@ -68,50 +216,93 @@ impl BackendConfig {
             .with_context(|| format!("Invalid backend address: {}", addr_str))
     }
 
+    /// Enabled `127.0.0.1:port` backend with every other field at its default
+    #[cfg(test)]
+    pub fn local(port: u16) -> Self {
+        Self {
+            host: "127.0.0.1".to_string(),
+            port,
+            path: None,
+            weight: default_backend_weight(),
+            enabled: default_backend_enabled(),
+            labels: BTreeMap::new(),
+            connect_timeout_ms: None,
+            health_check_interval_ms: None,
+            tier: 0,
+        }
+    }
+
     /// Resolve backend host to a concrete socket address.
     ///
     /// Supports both literal IPs and DNS hostnames.
     pub async fn resolve_socket_addr(&self) -> Result<SocketAddr> {
+        let addrs = self.resolve_all_socket_addrs().await?;
+        Ok(addrs[0])
+    }
+
+    /// Resolve backend host to every socket address it maps to.
+    ///
+    /// Hostnames with several A/AAAA records yield all of them, in resolver order.
+    /// Never returns an empty list.
+    pub async fn resolve_all_socket_addrs(&self) -> Result<Vec<SocketAddr>> {
         let host_port = format!("{}:{}", self.host, self.port);
-        let mut addrs = lookup_host(&host_port)
+        let addrs = lookup_host(&host_port)
             .await
             .with_context(|| format!("Failed to resolve backend address: {}", host_port))?;
-
-        addrs
-            .next()
-            .with_context(|| format!("No resolved address found for backend: {}", host_port))
-    }
-
-    /// Convert to health check address (same as socket addr for TCP).
-    pub async fn to_health_check_addr(&self) -> Result<SocketAddr> {
-        self.resolve_socket_addr().await
+        collect_resolved(&host_port, addrs)
     }
 
     /// Check connectivity to this backend.
     pub async fn check_connectivity(&self) -> Result<()> {
-        let addr = self.resolve_socket_addr().await?;
-        match tokio::time::timeout(Duration::from_secs(1), TcpStream::connect(&addr)).await {
-            Ok(Ok(_)) => Ok(()),
+        let limit = Duration::from_secs(1);
+        let result = match &self.path {
+            Some(path) => tokio::time::timeout(limit, UnixStream::connect(path))
+                .await
+                .map(|r| r.map(drop)),
+            None => {
+                let addr = self.resolve_socket_addr().await?;
+                tokio::time::timeout(limit, TcpStream::connect(&addr))
+                    .await
+                    .map(|r| r.map(drop))
+            }
+        };
+        match result {
+            Ok(Ok(())) => Ok(()),
             Ok(Err(e)) => Err(anyhow::anyhow!("Connection failed: {}", e)),
             Err(_) => Err(anyhow::anyhow!("Connection timeout")),
         }
     }
 }
 
+/// Gather resolver output, failing with `BalError::DnsNoAddresses` when it is empty
+fn collect_resolved(
+    host_port: &str,
+    addrs: impl Iterator<Item = SocketAddr>,
+) -> Result<Vec<SocketAddr>> {
+    let addrs: Vec<SocketAddr> = addrs.collect();
+    if addrs.is_empty() {
+        return Err(BalError::DnsNoAddresses(host_port.to_string()).into());
+    }
+    Ok(addrs)
+}
+
+/// Whether `err` is a backend host that resolved to zero addresses
+pub fn is_dns_no_addresses(err: &anyhow::Error) -> bool {
+    matches!(
+        err.downcast_ref::<BalError>(),
+        Some(BalError::DnsNoAddresses(_))
+    )
+}
+
 /// Runtime tuning configuration
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum OverloadPolicy {
+    #[default]
     Reject,
 }
 
-impl Default for OverloadPolicy {
-    fn default() -> Self {
-        Self::Reject
-    }
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
 pub struct RuntimeTuning {
     #[serde(default = "default_health_check_interval_ms")]
     pub health_check_interval_ms: u64,
@@ -125,9 +316,36 @@ pub struct RuntimeTuning {
     #[serde(default = "default_health_check_success_threshold")]
     pub health_check_success_threshold: u32,
 
+    /// Probe failures this soon after startup don't count toward
+    /// `health_check_fail_threshold`, so backends still coming up (or bal's
+    /// own first probes) don't flip to unhealthy right away; 0 = off
+    #[serde(default)]
+    pub startup_warmup_ms: u64,
+
+    /// Bytes written to the backend after connecting (`hex:` prefix for binary payloads)
+    #[serde(default)]
+    pub health_check_send: Option<String>,
+
+    /// Bytes the backend's reply must contain, or its greeting when nothing is
+    /// sent (e.g. "220 " for SMTP); unset = connect only
+    #[serde(default, alias = "health_check_expect_banner")]
+    pub health_check_expect: Option<String>,
+
+    /// Maximum health probes in flight at once; larger pools are probed in waves
+    #[serde(default = "default_health_check_concurrency")]
+    #[schemars(range(min = 1))]
+    pub health_check_concurrency: usize,
+
     #[serde(default = "default_backend_connect_timeout_ms")]
     pub backend_connect_timeout_ms: u64,
 
+    /// Bound on pre-relay setup after the TCP connect (today the
+    /// `upstream_proxy` CONNECT/SOCKS handshake), so a proxy that accepts but
+    /// stalls fails the attempt quickly; unset = only the attempt timeout applies
+    #[serde(default)]
+    #[schemars(range(min = 1))]
+    pub handshake_timeout_ms: Option<u64>,
+
     #[serde(default = "default_failover_backoff_initial_ms")]
     pub failover_backoff_initial_ms: u64,
 
@@ -146,6 +364,28 @@ pub struct RuntimeTuning {
     #[serde(default = "default_protection_stable_success_threshold")]
     pub protection_stable_success_threshold: u32,
 
+    /// Trip protection on this many timeouts alone within the window
+    /// (network trouble); unset leaves timeouts to the combined threshold
+    #[serde(default)]
+    #[schemars(range(min = 1))]
+    pub protection_timeout_threshold: Option<u32>,
+
+    /// Window for `protection_timeout_threshold` (default: `protection_window_ms`)
+    #[serde(default)]
+    #[schemars(range(min = 1))]
+    pub protection_timeout_window_ms: Option<u64>,
+
+    /// Trip protection on this many refusals alone within the window
+    /// (service down); unset leaves refusals to the combined threshold
+    #[serde(default)]
+    #[schemars(range(min = 1))]
+    pub protection_refused_threshold: Option<u32>,
+
+    /// Window for `protection_refused_threshold` (default: `protection_window_ms`)
+    #[serde(default)]
+    #[schemars(range(min = 1))]
+    pub protection_refused_window_ms: Option<u64>,
+
     #[serde(default = "default_max_concurrent_connections")]
     pub max_concurrent_connections: usize,
 
@@ -156,7 +396,245 @@ pub struct RuntimeTuning {
     pub overload_policy: OverloadPolicy,
 
     #[serde(default)]
+    #[schemars(range(min = 1))]
     pub tcp_backlog: Option<u32>,
+
+    #[serde(default)]
+    pub slow_start_ms: u64,
+
+    /// How long a recovered backend must stay healthy before it gets traffic
+    /// again, so a flapping backend doesn't oscillate in and out of rotation
+    #[serde(default)]
+    pub stabilization_window_ms: u64,
+
+    #[serde(default)]
+    pub dual_stack: bool,
+
+    /// Set SO_REUSEPORT so several bal processes can share the listen port
+    #[serde(default)]
+    pub reuse_port: bool,
+
+    /// Request TCP Fast Open on backend connects (Linux 4.11+); the handshake
+    /// then rides on the first write, so only client-speaks-first protocols
+    /// benefit and a dead backend surfaces at that write instead of at connect
+    #[serde(default)]
+    pub tcp_fast_open: bool,
+
+    /// Accept TCP Fast Open SYNs on the client-facing listener (Linux)
+    #[serde(default)]
+    pub tcp_fast_open_listener: bool,
+
+    /// Reload automatically when the config file changes on disk
+    #[serde(default)]
+    pub watch_config: bool,
+
+    /// Per-backend connection count treated as saturated; unset disables degrading
+    #[serde(default)]
+    #[schemars(range(min = 1))]
+    pub backend_max_connections: Option<usize>,
+
+    /// How long a backend must stay saturated before it's deprioritized
+    #[serde(default = "default_degrade_after_ms")]
+    pub degrade_after_ms: u64,
+
+    /// Window `bal status` connection/byte rates are averaged over
+    #[serde(default = "default_rate_window_ms")]
+    #[schemars(range(min = 1))]
+    pub rate_window_ms: u64,
+
+    /// Window health transitions are counted over for `bal status` flap counts
+    #[serde(default = "default_flap_window_ms")]
+    #[schemars(range(min = 1))]
+    pub flap_window_ms: u64,
+
+    /// Opt-in: if a fresh backend connection closes or resets within this many
+    /// ms and before either side sent a byte, reconnect the client elsewhere
+    #[serde(default)]
+    #[schemars(range(min = 1))]
+    pub reconnect_on_early_close_ms: Option<u64>,
+
+    /// Opt-in for client-speaks-first protocols: close the session if the
+    /// client sends nothing within this many ms after the backend connect
+    #[serde(default)]
+    #[schemars(range(min = 1))]
+    pub client_first_byte_timeout_ms: Option<u64>,
+
+    /// Log one info line per connection naming the backend it was routed to
+    #[serde(default)]
+    pub log_routing_decisions: bool,
+
+    /// Opt-in: keep spare backend connections open and hand them to new
+    /// clients; only for protocols without per-connection state
+    #[serde(default)]
+    pub backend_keepalive: bool,
+
+    /// Spare connections kept per backend with `backend_keepalive`
+    #[serde(default = "default_backend_keepalive_pool_size")]
+    #[schemars(range(min = 1))]
+    pub backend_keepalive_pool_size: usize,
+
+    /// Spares idle this long are closed instead of handed out
+    #[serde(default = "default_backend_keepalive_idle_ms")]
+    #[schemars(range(min = 1))]
+    pub backend_keepalive_idle_ms: u64,
+
+    /// Local IP backend connections originate from (multi-homed hosts)
+    #[serde(default)]
+    pub backend_source_address: Option<String>,
+
+    /// Egress proxy TCP backends are reached through, as
+    /// `http://[user:password@]host:port` (HTTP CONNECT) or
+    /// `socks5://[user:password@]host:port` (SOCKS5)
+    #[serde(default)]
+    pub upstream_proxy: Option<String>,
+
+    /// `host:port` for the HTTP control endpoint (`/livez`, `/readyz`); off when unset
+    #[serde(default)]
+    pub control_address: Option<String>,
+
+    /// Serve `POST /reset-stats` on the control endpoint; off by default
+    /// since the endpoint is unauthenticated (`bal reset-stats` always works)
+    #[serde(default)]
+    pub control_reset_stats: bool,
+
+    /// File whose mtime the daemon bumps every `heartbeat_interval_ms` while
+    /// its event loop is responsive, for external watchdogs
+    #[serde(default)]
+    pub heartbeat_file: Option<String>,
+
+    #[serde(default = "default_heartbeat_interval_ms")]
+    #[schemars(range(min = 1))]
+    pub heartbeat_interval_ms: u64,
+
+    /// Cap on the exponential sleep after failed accepts (e.g. EMFILE)
+    #[serde(default = "default_accept_error_backoff_max_ms")]
+    #[schemars(range(min = 1))]
+    pub accept_error_backoff_max_ms: u64,
+
+    /// Cap on backend connects in flight at once, so a burst of clients or a
+    /// fleet recovering together doesn't turn into a SYN flood; unset is unbounded
+    #[serde(default)]
+    #[schemars(range(min = 1))]
+    pub max_pending_connects: Option<usize>,
+
+    /// Number of tasks accepting on the shared listener
+    #[serde(default = "default_accept_workers")]
+    #[schemars(range(min = 1))]
+    pub accept_workers: usize,
+
+    /// Per-direction relay buffer in bytes; larger buffers mean fewer syscalls
+    #[serde(default = "default_relay_buffer_size")]
+    #[schemars(range(min = 1024, max = 1048576))]
+    pub relay_buffer_size: usize,
+
+    /// Per-direction in-flight cap: shrinks the kernel send/receive buffers
+    /// of both relay sockets so a fast sender is pushed back once about this
+    /// much (plus `relay_buffer_size` in userspace) is queued for a slow peer
+    #[serde(default)]
+    #[schemars(range(min = 1024))]
+    pub relay_high_water_bytes: Option<usize>,
+
+    /// Reuse backend DNS lookups on the connect path for this long
+    /// (0 = resolve on every connection); health checks always re-resolve
+    #[serde(default)]
+    pub dns_cache_ttl_ms: u64,
+
+    #[serde(default)]
+    pub drain_timeout_ms: u64,
+
+    #[serde(default)]
+    #[schemars(range(min = 1))]
+    pub max_connect_attempts: Option<u32>,
+
+    #[serde(default)]
+    #[schemars(range(min = 1))]
+    pub connect_budget_ms: Option<u64>,
+
+    #[serde(default)]
+    pub prefer_label: Option<LabelSelector>,
+
+    /// TLS passthrough routing: SNI hostname -> label of the backends that
+    /// serve it; clients without SNI or with an unmapped name use all backends
+    #[serde(default)]
+    pub sni_routes: BTreeMap<String, LabelSelector>,
+
+    /// How long to wait for the client's ClientHello when `sni_routes` is set
+    #[serde(default = "default_sni_read_timeout_ms")]
+    #[schemars(range(min = 1))]
+    pub sni_read_timeout_ms: u64,
+
+    /// Restart round robin at the first backend after a reload; `false`
+    /// continues the rotation where the previous config left off
+    #[serde(default = "default_reset_rr_on_reload")]
+    pub reset_rr_on_reload: bool,
+
+    /// Reject a reload unless every enabled backend in the new config is
+    /// reachable (default: only reject when none are)
+    #[serde(default)]
+    pub reload_require_all_healthy: bool,
+
+    #[serde(default)]
+    pub startup_min_healthy: Option<usize>,
+
+    #[serde(default)]
+    pub failover_jitter: bool,
+
+    /// Accept a config with no enabled backends (every client is closed
+    /// right away) instead of rejecting it, e.g. to park an instance
+    #[serde(default)]
+    pub allow_empty_backends: bool,
+}
+
+impl RuntimeTuning {
+    /// Timeout-only storm limits, when `protection_timeout_threshold` is set
+    pub fn protection_timeout_limits(&self) -> Option<StormLimits> {
+        self.protection_timeout_threshold
+            .map(|threshold| StormLimits {
+                threshold,
+                window_ms: self
+                    .protection_timeout_window_ms
+                    .unwrap_or(self.protection_window_ms),
+            })
+    }
+
+    /// Refusal-only storm limits, when `protection_refused_threshold` is set
+    pub fn protection_refused_limits(&self) -> Option<StormLimits> {
+        self.protection_refused_threshold
+            .map(|threshold| StormLimits {
+                threshold,
+                window_ms: self
+                    .protection_refused_window_ms
+                    .unwrap_or(self.protection_window_ms),
+            })
+    }
+
+    /// Decoded `health_check_send` payload (validated by `Config::validate`)
+    pub fn health_check_send_bytes(&self) -> Option<Vec<u8>> {
+        self.health_check_send
+            .as_deref()
+            .and_then(|payload| decode_probe_payload(payload).ok())
+    }
+
+    /// Decoded `health_check_expect` payload (validated by `Config::validate`)
+    pub fn health_check_expect_bytes(&self) -> Option<Vec<u8>> {
+        self.health_check_expect
+            .as_deref()
+            .and_then(|payload| decode_probe_payload(payload).ok())
+    }
+
+    /// Parsed `upstream_proxy` (validated by `Config::validate`)
+    pub fn upstream_proxy(&self) -> Option<UpstreamProxy> {
+        self.upstream_proxy
+            .as_deref()
+            .and_then(|url| UpstreamProxy::parse(url).ok())
+    }
+
+    /// Parsed `backend_source_address` (validated by `Config::validate`)
+    pub fn backend_source_ip(&self) -> Option<IpAddr> {
+        self.backend_source_address
+            .as_deref()
+            .and_then(|source| source.parse().ok())
+    }
 }
 
 impl Default for RuntimeTuning {
@@ -166,6 +644,10 @@ impl Default for RuntimeTuning {
             health_check_timeout_ms: default_health_check_timeout_ms(),
             health_check_fail_threshold: default_health_check_fail_threshold(),
             health_check_success_threshold: default_health_check_success_threshold(),
+            startup_warmup_ms: 0,
+            health_check_concurrency: default_health_check_concurrency(),
+            health_check_send: None,
+            health_check_expect: None,
             backend_connect_timeout_ms: default_backend_connect_timeout_ms(),
             failover_backoff_initial_ms: default_failover_backoff_initial_ms(),
             failover_backoff_max_ms: default_failover_backoff_max_ms(),
@@ -173,16 +655,85 @@ impl Default for RuntimeTuning {
             protection_trigger_threshold: default_protection_trigger_threshold(),
             protection_window_ms: default_protection_window_ms(),
             protection_stable_success_threshold: default_protection_stable_success_threshold(),
+            protection_timeout_threshold: None,
+            protection_timeout_window_ms: None,
+            protection_refused_threshold: None,
+            protection_refused_window_ms: None,
             max_concurrent_connections: default_max_concurrent_connections(),
             connection_idle_timeout_ms: default_connection_idle_timeout_ms(),
             overload_policy: OverloadPolicy::default(),
             tcp_backlog: None,
+            slow_start_ms: 0,
+            stabilization_window_ms: 0,
+            dual_stack: false,
+            reuse_port: false,
+            tcp_fast_open: false,
+            tcp_fast_open_listener: false,
+            watch_config: false,
+            control_address: None,
+            control_reset_stats: false,
+            heartbeat_file: None,
+            heartbeat_interval_ms: default_heartbeat_interval_ms(),
+            reconnect_on_early_close_ms: None,
+            client_first_byte_timeout_ms: None,
+            handshake_timeout_ms: None,
+            log_routing_decisions: false,
+            backend_keepalive: false,
+            backend_keepalive_pool_size: default_backend_keepalive_pool_size(),
+            backend_keepalive_idle_ms: default_backend_keepalive_idle_ms(),
+            backend_source_address: None,
+            upstream_proxy: None,
+            backend_max_connections: None,
+            degrade_after_ms: default_degrade_after_ms(),
+            rate_window_ms: default_rate_window_ms(),
+            flap_window_ms: default_flap_window_ms(),
+            max_pending_connects: None,
+            accept_workers: default_accept_workers(),
+            accept_error_backoff_max_ms: default_accept_error_backoff_max_ms(),
+            relay_buffer_size: default_relay_buffer_size(),
+            relay_high_water_bytes: None,
+            dns_cache_ttl_ms: 0,
+            drain_timeout_ms: 0,
+            max_connect_attempts: None,
+            connect_budget_ms: None,
+            prefer_label: None,
+            sni_routes: BTreeMap::new(),
+            sni_read_timeout_ms: default_sni_read_timeout_ms(),
+            reset_rr_on_reload: default_reset_rr_on_reload(),
+            reload_require_all_healthy: false,
+            startup_min_healthy: None,
+            failover_jitter: false,
+            allow_empty_backends: false,
+        }
+    }
+}
+
+/// Configuration file format, detected from the file extension
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFormat {
+    Yaml,
+    Toml,
+    Json,
+}
+
+impl ConfigFormat {
+    /// Detect format by extension, defaulting to YAML when unknown.
+    pub fn from_path(path: &Path) -> Self {
+        match path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.to_ascii_lowercase())
+            .as_deref()
+        {
+            Some("toml") => ConfigFormat::Toml,
+            Some("json") => ConfigFormat::Json,
+            _ => ConfigFormat::Yaml,
         }
     }
 }
 
 /// Complete configuration structure
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
 pub struct Config {
     /// Port for load balancer to listen on
     #[serde(default = "default_port")]
@@ -200,11 +751,38 @@ pub struct Config {
     #[serde(default = "default_bind_address")]
     pub bind_address: String,
 
-    /// Runtime tuning knobs
+    /// Runtime tuning knobs (omit to auto-tune by backend count)
+    #[schemars(with = "Option<RuntimeTuning>")]
     pub runtime: RuntimeTuning,
 
     /// List of backend servers
+    #[schemars(length(min = 1))]
     pub backends: Vec<BackendConfig>,
+
+    /// Whether `runtime` came from the file or was auto-tuned (not serialized)
+    #[serde(skip)]
+    #[schemars(skip)]
+    pub runtime_source: RuntimeSource,
+}
+
+/// Where the runtime tuning in effect came from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RuntimeSource {
+    /// Written in the config file's `runtime` section
+    #[default]
+    Explicit,
+    /// `runtime` omitted; profile picked from the enabled backend count
+    AutoTuned,
+}
+
+impl std::fmt::Display for RuntimeSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RuntimeSource::Explicit => write!(f, "explicit"),
+            RuntimeSource::AutoTuned => write!(f, "auto_tuned"),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -226,7 +804,12 @@ impl<'de> Deserialize<'de> for Config {
         D: Deserializer<'de>,
     {
         let raw = RawConfig::deserialize(deserializer)?;
-        let backend_count = raw.backends.len();
+        let backend_count = raw.backends.iter().filter(|b| b.enabled).count();
+        let runtime_source = if raw.runtime.is_some() {
+            RuntimeSource::Explicit
+        } else {
+            RuntimeSource::AutoTuned
+        };
 
         Ok(Self {
             port: raw.port.unwrap_or_else(default_port),
@@ -237,10 +820,68 @@ impl<'de> Deserialize<'de> for Config {
                 .runtime
                 .unwrap_or_else(|| auto_tuned_runtime_profile(backend_count)),
             backends: raw.backends,
+            runtime_source,
         })
     }
 }
 
+/// Socket path when `bind_address` is `unix:<path>` (listen on a Unix socket, `port` unused)
+pub fn unix_listen_path(bind_address: &str) -> Option<&str> {
+    bind_address.strip_prefix("unix:")
+}
+
+/// Decode a health probe payload: `hex:` followed by hex digits (whitespace
+/// ignored) for binary protocols, otherwise the string's own bytes
+pub fn decode_probe_payload(payload: &str) -> Result<Vec<u8>> {
+    let Some(hex) = payload.strip_prefix("hex:") else {
+        return Ok(payload.as_bytes().to_vec());
+    };
+    let digits: Vec<u8> = hex.bytes().filter(|b| !b.is_ascii_whitespace()).collect();
+    if digits.len() % 2 != 0 {
+        bail!("hex payload has an odd number of digits: {}", payload);
+    }
+    digits
+        .chunks(2)
+        .map(|pair| {
+            std::str::from_utf8(pair)
+                .ok()
+                .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+                .with_context(|| format!("invalid hex payload: {}", payload))
+        })
+        .collect()
+}
+
+/// Strip a leading UTF-8 BOM and turn CRLF (or lone CR) line endings into LF
+///
+/// Windows editors commonly save configs that way, and the parsers either
+/// reject the BOM outright or report errors at odd positions.
+fn normalize_config_text(raw: &str) -> std::borrow::Cow<'_, str> {
+    let text = raw.strip_prefix('\u{feff}').unwrap_or(raw);
+    if text.contains('\r') {
+        std::borrow::Cow::Owned(text.replace("\r\n", "\n").replace('\r', "\n"))
+    } else {
+        std::borrow::Cow::Borrowed(text)
+    }
+}
+
+/// 1-based line number and text where a parse error points, if it says
+fn offending_line<'a>(content: &'a str, err: &anyhow::Error) -> Option<(usize, &'a str)> {
+    let number = if let Some(err) = err.downcast_ref::<serde_yaml::Error>() {
+        err.location()?.line()
+    } else if let Some(err) = err.downcast_ref::<serde_json::Error>() {
+        err.line()
+    } else if let Some(err) = err.downcast_ref::<toml::de::Error>() {
+        let offset = err.span()?.start.min(content.len());
+        content[..offset].matches('\n').count() + 1
+    } else {
+        return None;
+    };
+    content
+        .lines()
+        .nth(number.checked_sub(1)?)
+        .map(|line| (number, line))
+}
+
 fn auto_tuned_runtime_profile(backend_count: usize) -> RuntimeTuning {
     if backend_count <= 2 {
         RuntimeTuning {
@@ -256,9 +897,7 @@ fn auto_tuned_runtime_profile(backend_count: usize) -> RuntimeTuning {
             protection_window_ms: 30_000,
             protection_stable_success_threshold: 12,
             max_concurrent_connections: 4_000,
-            connection_idle_timeout_ms: default_connection_idle_timeout_ms(),
-            overload_policy: OverloadPolicy::default(),
-            tcp_backlog: None,
+            ..RuntimeTuning::default()
         }
     } else if backend_count <= 5 {
         RuntimeTuning {
@@ -274,9 +913,7 @@ fn auto_tuned_runtime_profile(backend_count: usize) -> RuntimeTuning {
             protection_window_ms: 30_000,
             protection_stable_success_threshold: 14,
             max_concurrent_connections: 8_000,
-            connection_idle_timeout_ms: default_connection_idle_timeout_ms(),
-            overload_policy: OverloadPolicy::default(),
-            tcp_backlog: None,
+            ..RuntimeTuning::default()
         }
     } else {
         RuntimeTuning {
@@ -292,9 +929,7 @@ fn auto_tuned_runtime_profile(backend_count: usize) -> RuntimeTuning {
             protection_window_ms: 30_000,
             protection_stable_success_threshold: 16,
             max_concurrent_connections: 12_000,
-            connection_idle_timeout_ms: default_connection_idle_timeout_ms(),
-            overload_policy: OverloadPolicy::default(),
-            tcp_backlog: None,
+            ..RuntimeTuning::default()
         }
     }
 }
@@ -303,6 +938,14 @@ fn default_port() -> u16 {
     DEFAULT_PORT
 }
 
+fn default_backend_weight() -> u32 {
+    1
+}
+
+fn default_backend_enabled() -> bool {
+    true
+}
+
 fn default_log_level() -> String {
     "info".to_string()
 }
@@ -327,6 +970,10 @@ fn default_health_check_success_threshold() -> u32 {
     HEALTH_CHECK_MIN_SUCCESS
 }
 
+fn default_health_check_concurrency() -> usize {
+    DEFAULT_HEALTH_CHECK_CONCURRENCY
+}
+
 fn default_backend_connect_timeout_ms() -> u64 {
     HEALTH_CHECK_TIMEOUT_MS
 }
@@ -363,6 +1010,50 @@ fn default_connection_idle_timeout_ms() -> u64 {
     120_000
 }
 
+fn default_degrade_after_ms() -> u64 {
+    5_000
+}
+
+fn default_rate_window_ms() -> u64 {
+    10_000
+}
+
+fn default_flap_window_ms() -> u64 {
+    300_000
+}
+
+fn default_heartbeat_interval_ms() -> u64 {
+    5000
+}
+
+fn default_sni_read_timeout_ms() -> u64 {
+    1000
+}
+
+fn default_accept_error_backoff_max_ms() -> u64 {
+    DEFAULT_ACCEPT_ERROR_BACKOFF_MAX_MS
+}
+
+fn default_accept_workers() -> usize {
+    1
+}
+
+fn default_backend_keepalive_pool_size() -> usize {
+    DEFAULT_BACKEND_KEEPALIVE_POOL_SIZE
+}
+
+fn default_backend_keepalive_idle_ms() -> u64 {
+    DEFAULT_BACKEND_KEEPALIVE_IDLE_MS
+}
+
+fn default_reset_rr_on_reload() -> bool {
+    true
+}
+
+fn default_relay_buffer_size() -> usize {
+    PROXY_BUFFER_SIZE
+}
+
 impl Config {
     /// Create new Config with defaults
     pub fn new() -> Self {
@@ -373,6 +1064,7 @@ impl Config {
             bind_address: default_bind_address(),
             runtime: RuntimeTuning::default(),
             backends: Vec::new(),
+            runtime_source: RuntimeSource::Explicit,
         }
     }
 
@@ -406,38 +1098,158 @@ impl Config {
 
     /// Load configuration from file
     pub async fn load_from_file(path: &Path) -> Result<Self> {
-        let content = fs::read_to_string(path)
+        let raw = fs::read_to_string(path)
             .await
             .with_context(|| format!("Cannot read configuration file: {}", path.display()))?;
-
-        let config: Config = serde_yaml::from_str(&content)
-            .with_context(|| format!("Configuration file parsing failed: {}", path.display()))?;
-
+        let content = normalize_config_text(&raw);
+
+        let mut config = Self::parse(&content, ConfigFormat::from_path(path)).map_err(|err| {
+            let context = match offending_line(&content, &err) {
+                Some((number, line)) => format!(
+                    "Configuration file parsing failed: {} (line {}: {})",
+                    path.display(),
+                    number,
+                    line.trim()
+                ),
+                None => format!("Configuration file parsing failed: {}", path.display()),
+            };
+            err.context(context)
+        })?;
+
+        config.apply_env_overrides(|key| std::env::var(key).ok())?;
         config.validate()?;
         Ok(config)
     }
 
-    /// Alias for load_from_file
-    pub async fn load(path: &Path) -> Result<Self> {
-        Self::load_from_file(path).await
-    }
-
-    /// Validate configuration
-    pub fn validate(&self) -> Result<()> {
+    /// Apply `BAL_*` environment variable overrides on top of file values
+    ///
+    /// Supported: BAL_PORT, BAL_BIND_ADDRESS, BAL_LOG_LEVEL,
+    /// BAL_MAX_CONCURRENT_CONNECTIONS, BAL_BACKENDS (`host:port,host:port`).
+    /// The lookup is injected so tests don't depend on process-wide env state.
+    pub fn apply_env_overrides<F>(&mut self, lookup: F) -> Result<()>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        if let Some(value) = lookup("BAL_PORT") {
+            self.port = value
+                .trim()
+                .parse()
+                .with_context(|| format!("Invalid BAL_PORT value: {}", value))?;
+        }
+
+        if let Some(value) = lookup("BAL_BIND_ADDRESS") {
+            self.bind_address = value.trim().to_string();
+        }
+
+        if let Some(value) = lookup("BAL_LOG_LEVEL") {
+            self.log_level = value.trim().to_string();
+        }
+
+        if let Some(value) = lookup("BAL_MAX_CONCURRENT_CONNECTIONS") {
+            self.runtime.max_concurrent_connections = value.trim().parse().with_context(|| {
+                format!("Invalid BAL_MAX_CONCURRENT_CONNECTIONS value: {}", value)
+            })?;
+        }
+
+        if let Some(value) = lookup("BAL_BACKENDS") {
+            self.backends = value
+                .split(',')
+                .map(str::trim)
+                .filter(|entry| !entry.is_empty())
+                .map(parse_backend_entry)
+                .collect::<Result<Vec<_>>>()
+                .context("Invalid BAL_BACKENDS value")?;
+        }
+
+        Ok(())
+    }
+
+    /// Parse configuration content in the given format
+    pub fn parse(content: &str, format: ConfigFormat) -> Result<Self> {
+        let config = match format {
+            ConfigFormat::Yaml => serde_yaml::from_str(content)?,
+            ConfigFormat::Toml => toml::from_str(content)?,
+            ConfigFormat::Json => serde_json::from_str(content)?,
+        };
+        Ok(config)
+    }
+
+    /// Alias for load_from_file
+    pub async fn load(path: &Path) -> Result<Self> {
+        Self::load_from_file(path).await
+    }
+
+    /// Backends that take part in balancing and health checks
+    pub fn enabled_backends(&self) -> impl Iterator<Item = &BackendConfig> {
+        self.backends.iter().filter(|b| b.enabled)
+    }
+
+    /// Validate configuration
+    pub fn validate(&self) -> Result<()> {
         // Validate backend list
-        if self.backends.is_empty() {
-            bail!("At least one backend is required");
+        if !self.runtime.allow_empty_backends {
+            if self.backends.is_empty() {
+                bail!("At least one backend is required (or set runtime.allow_empty_backends)");
+            }
+
+            if self.enabled_backends().next().is_none() {
+                bail!("At least one enabled backend is required (or set runtime.allow_empty_backends)");
+            }
+        }
+
+        for backend in &self.backends {
+            match &backend.path {
+                Some(path) if !backend.host.is_empty() || backend.port != 0 => bail!(
+                    "Backend {} sets both path and host/port; use one or the other",
+                    path
+                ),
+                Some(path) if path.trim().is_empty() => bail!("Backend path cannot be empty"),
+                None if backend.host.trim().is_empty() || backend.port == 0 => bail!(
+                    "Backend {} needs a host and a nonzero port (or a Unix socket path)",
+                    backend.address()
+                ),
+                _ => {}
+            }
         }
 
         // Check for duplicate backends
         let mut seen = HashSet::new();
         for backend in &self.backends {
-            let key = format!("{}:{}", backend.host, backend.port);
+            let key = backend.address();
             if !seen.insert(key.clone()) {
                 bail!("Duplicate backend configuration: {}", key);
             }
         }
 
+        if let Some(backend) = self
+            .backends
+            .iter()
+            .find(|b| b.connect_timeout_ms == Some(0))
+        {
+            bail!(
+                "Backend {} connect_timeout_ms must be greater than 0",
+                backend.address()
+            );
+        }
+
+        if let Some(backend) = self
+            .backends
+            .iter()
+            .find(|b| b.health_check_interval_ms == Some(0))
+        {
+            bail!(
+                "Backend {} health_check_interval_ms must be greater than 0",
+                backend.address()
+            );
+        }
+
+        if let Some(backend) = self.backends.iter().find(|b| b.weight == 0) {
+            bail!(
+                "Backend {} weight must be greater than 0",
+                backend.address()
+            );
+        }
+
         // Validate port number
         if self.port == 0 {
             bail!("Port cannot be 0");
@@ -447,6 +1259,157 @@ impl Config {
             bail!("Bind address cannot be empty");
         }
 
+        if unix_listen_path(&self.bind_address).is_some_and(|path| path.trim().is_empty()) {
+            bail!("bind_address unix: needs a socket path (e.g. unix:/var/run/bal.sock)");
+        }
+
+        if self.runtime.backend_max_connections == Some(0) {
+            bail!("backend_max_connections must be at least 1 when set");
+        }
+
+        if let Some(source) = &self.runtime.backend_source_address {
+            if source.parse::<IpAddr>().is_err() {
+                bail!(
+                    "backend_source_address must be an IP address, got {}",
+                    source
+                );
+            }
+        }
+
+        if let Some(url) = &self.runtime.upstream_proxy {
+            upstream::validate_url(url)?;
+        }
+
+        if let Some(control_address) = &self.runtime.control_address {
+            if control_address.parse::<SocketAddr>().is_err() {
+                bail!(
+                    "control_address must be an ip:port socket address, got {}",
+                    control_address
+                );
+            }
+        }
+
+        for (name, payload) in [
+            ("health_check_send", &self.runtime.health_check_send),
+            ("health_check_expect", &self.runtime.health_check_expect),
+        ] {
+            if let Some(payload) = payload {
+                decode_probe_payload(payload).with_context(|| format!("Invalid {}", name))?;
+            }
+        }
+
+        if self.runtime.accept_error_backoff_max_ms == 0 {
+            bail!("accept_error_backoff_max_ms must be greater than 0");
+        }
+
+        if self.runtime.rate_window_ms == 0 {
+            bail!("rate_window_ms must be greater than 0");
+        }
+
+        if self.runtime.flap_window_ms == 0 {
+            bail!("flap_window_ms must be greater than 0");
+        }
+
+        if self.runtime.health_check_concurrency == 0 {
+            bail!("health_check_concurrency must be at least 1");
+        }
+
+        if self.runtime.accept_workers == 0 {
+            bail!("accept_workers must be at least 1");
+        }
+
+        if !(MIN_RELAY_BUFFER_SIZE..=MAX_RELAY_BUFFER_SIZE)
+            .contains(&self.runtime.relay_buffer_size)
+        {
+            bail!(
+                "relay_buffer_size must be between {} and {} bytes, got {}",
+                MIN_RELAY_BUFFER_SIZE,
+                MAX_RELAY_BUFFER_SIZE,
+                self.runtime.relay_buffer_size
+            );
+        }
+
+        if let Some(high_water) = self.runtime.relay_high_water_bytes {
+            if high_water < self.runtime.relay_buffer_size {
+                bail!(
+                    "relay_high_water_bytes must be at least relay_buffer_size ({}), got {}",
+                    self.runtime.relay_buffer_size,
+                    high_water
+                );
+            }
+        }
+
+        if self.runtime.backend_keepalive_pool_size == 0 {
+            bail!("backend_keepalive_pool_size must be at least 1");
+        }
+
+        if self.runtime.backend_keepalive_idle_ms == 0 {
+            bail!("backend_keepalive_idle_ms must be greater than 0");
+        }
+
+        if self.runtime.max_pending_connects == Some(0) {
+            bail!("max_pending_connects must be at least 1 when set");
+        }
+
+        if self.runtime.client_first_byte_timeout_ms == Some(0) {
+            bail!("client_first_byte_timeout_ms must be at least 1 when set");
+        }
+
+        if self.runtime.heartbeat_interval_ms == 0 {
+            bail!("heartbeat_interval_ms must be greater than 0");
+        }
+
+        if self.runtime.sni_read_timeout_ms == 0 {
+            bail!("sni_read_timeout_ms must be greater than 0");
+        }
+
+        if let Some(host) = self
+            .runtime
+            .sni_routes
+            .keys()
+            .find(|host| host.trim().is_empty())
+        {
+            bail!("sni_routes hostname must not be empty (got {:?})", host);
+        }
+
+        if self.runtime.handshake_timeout_ms == Some(0) {
+            bail!("handshake_timeout_ms must be at least 1 when set");
+        }
+
+        if self.runtime.reconnect_on_early_close_ms == Some(0) {
+            bail!("reconnect_on_early_close_ms must be at least 1 when set");
+        }
+
+        if self.runtime.tcp_backlog == Some(0) {
+            bail!("tcp_backlog must be at least 1 when set");
+        }
+
+        if self.runtime.dual_stack && !matches!(self.bind_address.as_str(), "0.0.0.0" | "::") {
+            bail!(
+                "dual_stack requires a wildcard bind_address (0.0.0.0 or ::), got {}",
+                self.bind_address
+            );
+        }
+
+        if let Some(min_healthy) = self.runtime.startup_min_healthy {
+            let enabled = self.enabled_backends().count();
+            if min_healthy > enabled {
+                bail!(
+                    "startup_min_healthy ({}) exceeds the number of enabled backends ({})",
+                    min_healthy,
+                    enabled
+                );
+            }
+        }
+
+        if self.runtime.max_connect_attempts == Some(0) {
+            bail!("max_connect_attempts must be greater than 0");
+        }
+
+        if self.runtime.connect_budget_ms == Some(0) {
+            bail!("connect_budget_ms must be greater than 0");
+        }
+
         if self.runtime.health_check_interval_ms == 0 {
             bail!("health_check_interval_ms must be greater than 0");
         }
@@ -483,6 +1446,41 @@ impl Config {
             bail!("protection_window_ms must be greater than 0");
         }
 
+        for (name, value) in [
+            (
+                "protection_timeout_threshold",
+                self.runtime.protection_timeout_threshold.map(u64::from),
+            ),
+            (
+                "protection_timeout_window_ms",
+                self.runtime.protection_timeout_window_ms,
+            ),
+            (
+                "protection_refused_threshold",
+                self.runtime.protection_refused_threshold.map(u64::from),
+            ),
+            (
+                "protection_refused_window_ms",
+                self.runtime.protection_refused_window_ms,
+            ),
+        ] {
+            if value == Some(0) {
+                bail!("{} must be greater than 0 when set", name);
+            }
+        }
+
+        if self.runtime.protection_timeout_threshold.is_none()
+            && self.runtime.protection_timeout_window_ms.is_some()
+        {
+            bail!("protection_timeout_window_ms requires protection_timeout_threshold");
+        }
+
+        if self.runtime.protection_refused_threshold.is_none()
+            && self.runtime.protection_refused_window_ms.is_some()
+        {
+            bail!("protection_refused_window_ms requires protection_refused_threshold");
+        }
+
         if self.runtime.protection_stable_success_threshold == 0 {
             bail!("protection_stable_success_threshold must be greater than 0");
         }
@@ -498,6 +1496,12 @@ impl Config {
         Ok(())
     }
 
+    /// JSON Schema of the config file, for editor validation and autocomplete
+    pub fn json_schema() -> serde_json::Value {
+        serde_json::to_value(schemars::schema_for!(Config))
+            .expect("generated schema should serialize")
+    }
+
     /// Generate default configuration file template
     pub fn default_template() -> String {
         r#"# minimal config (recommended)
@@ -536,73 +1540,36 @@ backends:
     }
 }
 
-impl Default for Config {
-    fn default() -> Self {
-        Self::new()
+/// Parse a `host:port` backend entry (IPv6 hosts may be bracketed: `[::1]:9000`).
+fn parse_backend_entry(entry: &str) -> Result<BackendConfig> {
+    let (host, port) = entry
+        .rsplit_once(':')
+        .with_context(|| format!("expected host:port, got '{}'", entry))?;
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    if host.is_empty() {
+        bail!("missing host in '{}'", entry);
     }
+    let port = port
+        .parse()
+        .with_context(|| format!("invalid port in '{}'", entry))?;
+
+    Ok(BackendConfig {
+        host: host.to_string(),
+        port,
+        weight: 1,
+        enabled: true,
+        labels: Default::default(),
+        connect_timeout_ms: None,
+        health_check_interval_ms: None,
+        tier: 0,
+        path: None,
+    })
 }
 
-/// Validate configuration file (for check command)
-pub async fn validate_config_file(config_path: Option<std::path::PathBuf>) -> Result<()> {
-    let path = if let Some(path) = config_path {
-        path
-    } else {
-        Config::resolve_config_path(None)?
-    };
-
-    if !path.exists() {
-        bail!("Configuration file not found: {}", path.display());
-    }
-
-    println!("Validating configuration file: {}", path.display());
-
-    // Load and parse
-    let config = Config::load_from_file(&path).await?;
-
-    println!("  - Listen: {}:{}", config.bind_address, config.port);
-    println!("  - Load balancing: {:?}", config.method);
-    println!("  - Log level: {}", config.log_level);
-    println!(
-        "  - Runtime: health_interval={}ms health_timeout={}ms fail_threshold={} success_threshold={} backend_connect_timeout={}ms backoff_initial={}ms backoff_max={}ms cooldown={}ms protection_trigger={} protection_window={}ms protection_recover={} max_conns={} idle_timeout={}ms overload_policy={}",
-        config.runtime.health_check_interval_ms,
-        config.runtime.health_check_timeout_ms,
-        config.runtime.health_check_fail_threshold,
-        config.runtime.health_check_success_threshold,
-        config.runtime.backend_connect_timeout_ms,
-        config.runtime.failover_backoff_initial_ms,
-        config.runtime.failover_backoff_max_ms,
-        config.runtime.backend_cooldown_ms,
-        config.runtime.protection_trigger_threshold,
-        config.runtime.protection_window_ms,
-        config.runtime.protection_stable_success_threshold,
-        config.runtime.max_concurrent_connections,
-        config.runtime.connection_idle_timeout_ms,
-        match config.runtime.overload_policy { OverloadPolicy::Reject => "reject" },
-    );
-    println!("  - Number of backends: {}", config.backends.len());
-
-    // Validate backend connectivity
-    println!("Checking backend connectivity...");
-    for backend in &config.backends {
-        let addr = format!("{}:{}", backend.host, backend.port);
-        match tokio::time::timeout(Duration::from_secs(1), TcpStream::connect(&addr)).await {
-            Ok(Ok(_)) => println!(
-                "  [OK] {}:{} - Connection successful",
-                backend.host, backend.port
-            ),
-            Ok(Err(e)) => println!("  [WARN] {}:{} - {}", backend.host, backend.port, e),
-            Err(_) => println!(
-                "  [WARN] {}:{} - Connection timeout",
-                backend.host, backend.port
-            ),
-        }
+impl Default for Config {
+    fn default() -> Self {
+        Self::new()
     }
-
-    println!(
-        "Validation complete: {} healthy, 0 unhealthy",
-        config.backends.len()
-    );
-    Ok(())
 }
 
 #[cfg(test)]
@@ -613,7 +1580,7 @@ mod tests {
     async fn resolves_hostname_backend_address() {
         let backend = BackendConfig {
             host: "localhost".to_string(),
-            port: 80,
+            ..BackendConfig::local(80)
         };
 
         let resolved = backend
@@ -623,6 +1590,24 @@ mod tests {
         assert_eq!(resolved.port(), 80);
     }
 
+    #[test]
+    fn empty_resolution_is_reported_as_dns_no_addresses() {
+        let err = collect_resolved("empty.invalid:9000", std::iter::empty())
+            .expect_err("an empty answer should fail");
+        assert!(is_dns_no_addresses(&err));
+        assert_eq!(
+            err.to_string(),
+            "dns_no_addresses: empty.invalid:9000 resolved to no addresses"
+        );
+
+        let addr: SocketAddr = "127.0.0.1:9000".parse().unwrap();
+        assert_eq!(
+            collect_resolved("localhost:9000", std::iter::once(addr)).unwrap(),
+            vec![addr]
+        );
+        assert!(!is_dns_no_addresses(&anyhow::anyhow!("lookup failed")));
+    }
+
     #[test]
     fn parse_config_applies_defaults_and_auto_tuned_runtime_when_runtime_omitted() {
         let yaml = r#"
@@ -647,6 +1632,21 @@ backends:
         assert_eq!(config.runtime.connection_idle_timeout_ms, 120000);
     }
 
+    #[test]
+    fn parse_config_accepts_least_connections_method() {
+        let yaml = r#"
+method: least_connections
+backends:
+  - host: "127.0.0.1"
+    port: 9000
+"#;
+
+        let config: Config = serde_yaml::from_str(yaml).expect("config should parse");
+        assert_eq!(config.method, BalanceMethod::LeastConnections);
+        assert!(config.validate().is_ok());
+        assert_eq!(config.method.to_string(), "least_connections");
+    }
+
     #[test]
     fn parse_config_accepts_legacy_mode_field_but_ignores_it() {
         let yaml = r#"
@@ -726,4 +1726,479 @@ backends:
         assert!(!template.contains("log_level:"));
         assert!(!template.contains("runtime:"));
     }
+
+    #[test]
+    fn validate_rejects_out_of_range_relay_buffer_size() {
+        let mut config: Config =
+            serde_yaml::from_str("backends:\n  - host: \"127.0.0.1\"\n    port: 9000\n")
+                .expect("config should parse");
+        assert_eq!(config.runtime.relay_buffer_size, PROXY_BUFFER_SIZE);
+
+        config.runtime.relay_buffer_size = 512;
+        assert!(config.validate().is_err());
+        config.runtime.relay_buffer_size = 64 * 1024;
+        assert!(config.validate().is_ok());
+        config.runtime.relay_buffer_size = 4 * 1024 * 1024;
+        assert!(config.validate().is_err());
+
+        // The in-flight cap can't be below the userspace buffer it includes
+        config.runtime.relay_buffer_size = 64 * 1024;
+        config.runtime.relay_high_water_bytes = Some(16 * 1024);
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("relay_high_water_bytes"));
+        config.runtime.relay_high_water_bytes = Some(256 * 1024);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn validate_rejects_unparseable_backend_source_address() {
+        let mut config: Config =
+            serde_yaml::from_str("backends:\n  - host: \"127.0.0.1\"\n    port: 9000\n")
+                .expect("config should parse");
+
+        config.runtime.backend_source_address = Some("eth0".to_string());
+        assert!(config.validate().is_err());
+
+        config.runtime.backend_source_address = Some("10.0.0.5".to_string());
+        assert!(config.validate().is_ok());
+        assert_eq!(
+            config.runtime.backend_source_ip(),
+            Some("10.0.0.5".parse().unwrap())
+        );
+    }
+
+    #[test]
+    fn validate_checks_per_kind_protection_thresholds() {
+        let mut config: Config =
+            serde_yaml::from_str("backends:\n  - host: \"127.0.0.1\"\n    port: 9000\n")
+                .expect("config should parse");
+
+        config.runtime.protection_timeout_threshold = Some(0);
+        assert!(config.validate().is_err());
+
+        config.runtime.protection_timeout_threshold = None;
+        config.runtime.protection_refused_window_ms = Some(5_000);
+        let err = config
+            .validate()
+            .expect_err("a window without its threshold should be rejected");
+        assert!(err.to_string().contains("protection_refused_threshold"));
+
+        config.runtime.protection_refused_threshold = Some(4);
+        config.runtime.protection_timeout_threshold = Some(6);
+        assert!(config.validate().is_ok());
+        assert_eq!(
+            config.runtime.protection_refused_limits(),
+            Some(StormLimits {
+                threshold: 4,
+                window_ms: 5_000
+            })
+        );
+        assert_eq!(
+            config
+                .runtime
+                .protection_timeout_limits()
+                .map(|l| l.window_ms),
+            Some(config.runtime.protection_window_ms)
+        );
+    }
+
+    #[test]
+    fn validate_rejects_zero_tcp_backlog() {
+        let yaml = r#"
+runtime:
+  tcp_backlog: 0
+backends:
+  - host: "127.0.0.1"
+    port: 9000
+"#;
+        let config: Config = serde_yaml::from_str(yaml).expect("config should parse");
+        let err = config
+            .validate()
+            .expect_err("zero backlog should be rejected");
+        assert!(err.to_string().contains("tcp_backlog"));
+    }
+
+    #[test]
+    fn unix_socket_backends_take_a_path_instead_of_host_and_port() {
+        let yaml = r#"
+backends:
+  - path: "/var/run/app.sock"
+  - host: "127.0.0.1"
+    port: 9000
+"#;
+        let config: Config = serde_yaml::from_str(yaml).expect("config should parse");
+        config.validate().expect("path backend should be valid");
+        assert_eq!(config.backends[0].address(), "unix:/var/run/app.sock");
+
+        let both = r#"
+backends:
+  - path: "/var/run/app.sock"
+    host: "127.0.0.1"
+    port: 9000
+"#;
+        let config: Config = serde_yaml::from_str(both).unwrap();
+        assert!(config.validate().is_err());
+
+        let neither = "backends:\n  - weight: 1\n";
+        let config: Config = serde_yaml::from_str(neither).unwrap();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn validate_rejects_zero_backend_health_check_interval() {
+        let yaml = r#"
+backends:
+  - host: "127.0.0.1"
+    port: 9000
+    health_check_interval_ms: 0
+"#;
+        let config: Config = serde_yaml::from_str(yaml).expect("config should parse");
+        let err = config
+            .validate()
+            .expect_err("zero interval override should be rejected");
+        assert!(err
+            .to_string()
+            .contains("127.0.0.1:9000 health_check_interval_ms"));
+    }
+
+    #[test]
+    fn probe_payloads_decode_hex_and_reject_malformed_hex() {
+        assert_eq!(decode_probe_payload("PING\r\n").unwrap(), b"PING\r\n");
+        assert_eq!(
+            decode_probe_payload("hex:00ff 7f").unwrap(),
+            vec![0x00, 0xff, 0x7f]
+        );
+
+        let yaml = r#"
+runtime:
+  health_check_send: "hex:abc"
+backends:
+  - host: "127.0.0.1"
+    port: 9000
+"#;
+        let config: Config = serde_yaml::from_str(yaml).expect("config should parse");
+        let err = config
+            .validate()
+            .expect_err("odd-length hex should be rejected");
+        assert!(err.to_string().contains("health_check_send"));
+    }
+
+    #[test]
+    fn validate_rejects_dual_stack_with_specific_bind_address() {
+        let mut config: Config = serde_yaml::from_str(
+            r#"
+bind_address: "127.0.0.1"
+runtime:
+  dual_stack: true
+backends:
+  - host: "127.0.0.1"
+    port: 9000
+"#,
+        )
+        .expect("config should parse");
+
+        assert!(config.validate().is_err());
+
+        config.bind_address = "::".to_string();
+        assert!(config.validate().is_ok());
+    }
+
+    #[tokio::test]
+    async fn load_from_file_parses_yaml_toml_and_json_identically() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let files = [
+            (
+                "bal.yaml",
+                r#"
+port: 8080
+method: "round_robin"
+runtime:
+  health_check_interval_ms: 750
+  max_concurrent_connections: 321
+backends:
+  - host: "127.0.0.1"
+    port: 9000
+  - host: "127.0.0.1"
+    port: 9001
+"#,
+            ),
+            (
+                "bal.toml",
+                r#"
+port = 8080
+method = "round_robin"
+
+[runtime]
+health_check_interval_ms = 750
+max_concurrent_connections = 321
+
+[[backends]]
+host = "127.0.0.1"
+port = 9000
+
+[[backends]]
+host = "127.0.0.1"
+port = 9001
+"#,
+            ),
+            (
+                "bal.json",
+                r#"{
+  "port": 8080,
+  "method": "round_robin",
+  "runtime": {"health_check_interval_ms": 750, "max_concurrent_connections": 321},
+  "backends": [
+    {"host": "127.0.0.1", "port": 9000},
+    {"host": "127.0.0.1", "port": 9001}
+  ]
+}"#,
+            ),
+        ];
+
+        let mut loaded = Vec::new();
+        for (name, content) in files {
+            let path = dir.path().join(name);
+            std::fs::write(&path, content).expect("write config");
+            loaded.push(Config::load_from_file(&path).await.expect(name));
+        }
+
+        assert_eq!(loaded[0].port, 8080);
+        assert_eq!(loaded[0].runtime.health_check_interval_ms, 750);
+        assert_eq!(loaded[0].backends.len(), 2);
+        assert_eq!(loaded[0], loaded[1]);
+        assert_eq!(loaded[0], loaded[2]);
+    }
+
+    #[tokio::test]
+    async fn load_from_file_accepts_bom_and_crlf_and_names_the_bad_line() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let yaml = "port: 8080\nbackends:\n  - host: \"127.0.0.1\"\n    port: 9000\n";
+
+        let bom = dir.path().join("bom.yaml");
+        std::fs::write(&bom, format!("\u{feff}{}", yaml)).unwrap();
+        let crlf = dir.path().join("crlf.yaml");
+        std::fs::write(&crlf, format!("\u{feff}{}", yaml.replace('\n', "\r\n"))).unwrap();
+        let crlf_toml = dir.path().join("crlf.toml");
+        std::fs::write(
+            &crlf_toml,
+            "port = 8080\r\n[[backends]]\r\nhost = \"127.0.0.1\"\r\nport = 9000\r\n",
+        )
+        .unwrap();
+
+        let expected = Config::parse(yaml, ConfigFormat::Yaml).unwrap();
+        for path in [&bom, &crlf, &crlf_toml] {
+            let loaded = Config::load_from_file(path)
+                .await
+                .unwrap_or_else(|e| panic!("{} should load: {:#}", path.display(), e));
+            assert_eq!(loaded, expected);
+        }
+
+        let broken = dir.path().join("broken.yaml");
+        std::fs::write(
+            &broken,
+            "port: 8080\r\nbackends:\r\n  - host: [unclosed\r\n    port: 9000\r\n",
+        )
+        .unwrap();
+        let err = Config::load_from_file(&broken).await.unwrap_err();
+        let message = format!("{:#}", err);
+        assert!(message.contains("(line "), "{}", message);
+        assert!(!message.contains('\r'));
+    }
+
+    #[test]
+    fn config_format_defaults_to_yaml_for_unknown_extensions() {
+        assert_eq!(
+            ConfigFormat::from_path(Path::new("bal.TOML")),
+            ConfigFormat::Toml
+        );
+        assert_eq!(
+            ConfigFormat::from_path(Path::new("bal.json")),
+            ConfigFormat::Json
+        );
+        assert_eq!(
+            ConfigFormat::from_path(Path::new("bal.yml")),
+            ConfigFormat::Yaml
+        );
+        assert_eq!(
+            ConfigFormat::from_path(Path::new("bal.conf")),
+            ConfigFormat::Yaml
+        );
+    }
+
+    fn env_lookup(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: std::collections::HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        move |key| vars.get(key).cloned()
+    }
+
+    #[test]
+    fn env_overrides_replace_file_values() {
+        let mut config: Config = serde_yaml::from_str(
+            r#"
+port: 9295
+backends:
+  - host: "127.0.0.1"
+    port: 9000
+"#,
+        )
+        .expect("config should parse");
+
+        config
+            .apply_env_overrides(env_lookup(&[
+                ("BAL_PORT", "8080"),
+                ("BAL_BIND_ADDRESS", "127.0.0.1"),
+                ("BAL_LOG_LEVEL", "debug"),
+                ("BAL_MAX_CONCURRENT_CONNECTIONS", "500"),
+                (
+                    "BAL_BACKENDS",
+                    "10.0.0.1:9000, backend.local:9001,[::1]:9002",
+                ),
+            ]))
+            .expect("overrides should apply");
+
+        assert_eq!(config.port, 8080);
+        assert_eq!(config.bind_address, "127.0.0.1");
+        assert_eq!(config.log_level, "debug");
+        assert_eq!(config.runtime.max_concurrent_connections, 500);
+        let backends: Vec<String> = config
+            .backends
+            .iter()
+            .map(|b| format!("{}:{}", b.host, b.port))
+            .collect();
+        assert_eq!(
+            backends,
+            vec!["10.0.0.1:9000", "backend.local:9001", "::1:9002"]
+        );
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn env_overrides_reject_invalid_values() {
+        let base: Config = serde_yaml::from_str(
+            r#"
+backends:
+  - host: "127.0.0.1"
+    port: 9000
+"#,
+        )
+        .expect("config should parse");
+
+        let mut config = base.clone();
+        assert!(config
+            .apply_env_overrides(env_lookup(&[("BAL_PORT", "not-a-port")]))
+            .is_err());
+
+        let mut config = base.clone();
+        assert!(config
+            .apply_env_overrides(env_lookup(&[("BAL_BACKENDS", "127.0.0.1")]))
+            .is_err());
+
+        // Parses, but validate() must still catch out-of-range values
+        let mut config = base;
+        config
+            .apply_env_overrides(env_lookup(&[("BAL_MAX_CONCURRENT_CONNECTIONS", "0")]))
+            .expect("numeric value should parse");
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn validate_requires_an_enabled_backend() {
+        let config: Config = serde_yaml::from_str(
+            r#"
+backends:
+  - host: "127.0.0.1"
+    port: 9000
+    enabled: false
+"#,
+        )
+        .expect("config should parse");
+
+        assert!(!config.backends[0].enabled);
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn allow_empty_backends_permits_an_empty_list() {
+        let mut config: Config =
+            serde_yaml::from_str("port: 9295\nbackends: []\n").expect("config should parse");
+        let err = config
+            .validate()
+            .expect_err("empty list is rejected by default");
+        assert!(err.to_string().contains("allow_empty_backends"));
+
+        config.runtime.allow_empty_backends = true;
+        config
+            .validate()
+            .expect("empty list is allowed when opted in");
+    }
+
+    #[test]
+    fn backend_labels_parse_and_match_selectors() {
+        let config: Config = serde_yaml::from_str(
+            r#"
+backends:
+  - host: "127.0.0.1"
+    port: 9000
+    labels:
+      zone: us-east
+      role: primary
+  - host: "127.0.0.1"
+    port: 9001
+"#,
+        )
+        .expect("config should parse");
+
+        let selector: LabelSelector = "zone=us-east".parse().unwrap();
+        assert!(selector.matches(&config.backends[0].labels));
+        assert!(!selector.matches(&config.backends[1].labels));
+        assert!(config.backends[1].labels.is_empty());
+
+        assert!("zone".parse::<LabelSelector>().is_err());
+        assert!("=us-east".parse::<LabelSelector>().is_err());
+    }
+
+    #[test]
+    fn prefer_label_parses_from_runtime_section() {
+        let config: Config = serde_yaml::from_str(
+            r#"
+runtime:
+  prefer_label: "zone=us-east"
+backends:
+  - host: "127.0.0.1"
+    port: 9000
+"#,
+        )
+        .expect("config should parse");
+        assert_eq!(
+            config.runtime.prefer_label,
+            Some("zone=us-east".parse().unwrap())
+        );
+
+        let invalid = serde_yaml::from_str::<Config>(
+            "runtime:\n  prefer_label: \"us-east\"\nbackends:\n  - host: \"127.0.0.1\"\n    port: 9000\n",
+        );
+        assert!(invalid.is_err());
+    }
+
+    #[test]
+    fn json_schema_accepts_default_template_and_rejects_bad_values() {
+        let schema =
+            jsonschema::JSONSchema::compile(&Config::json_schema()).expect("schema should compile");
+
+        let template: serde_json::Value =
+            serde_yaml::from_str(&Config::default_template()).unwrap();
+        assert!(schema.is_valid(&template));
+
+        let advanced: serde_json::Value =
+            serde_yaml::from_str(include_str!("../sample/config.advanced.yaml")).unwrap();
+        assert!(schema.is_valid(&advanced));
+
+        let zero_weight = serde_json::json!({
+            "backends": [{ "host": "127.0.0.1", "port": 9000, "weight": 0 }]
+        });
+        assert!(!schema.is_valid(&zero_weight));
+        assert!(!schema.is_valid(&serde_json::json!({ "backends": [] })));
+    }
 }
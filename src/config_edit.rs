@@ -0,0 +1,503 @@
+//! In-place config edits
+//!
+//! Backs `bal set`, `bal add-backend` and `bal remove-backend`. The YAML
+//! file is edited line by line rather than re-serialized, so comments, key
+//! order and blank lines survive. `bal set` walks block-style mappings and
+//! the backend commands add or drop entries of a block-style `backends`
+//! list; flow-style (`{ ... }` / `[ ... ]`) documents are refused and have
+//! to be edited by hand.
+
+use anyhow::{bail, Context, Result};
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::config::{Config, ConfigFormat};
+use crate::process::ProcessManager;
+
+/// Set `key` to `value` in the config file, validate, write, and optionally reload
+pub fn run_set(cli_config: Option<PathBuf>, key: &str, value: &str, reload: bool) -> Result<()> {
+    let path = edit_file(cli_config, |content| apply_set(content, key, value))?;
+    println!("Set {} = {} in {}", key, value, path.display());
+    finish(&path, reload)
+}
+
+/// Append a backend entry to the config file
+pub fn run_add_backend(
+    cli_config: Option<PathBuf>,
+    address: &str,
+    weight: Option<u32>,
+    reload: bool,
+) -> Result<()> {
+    let (host, port) = parse_backend_address(address)?;
+    let path = edit_file(cli_config, |content| {
+        add_backend(content, &host, port, weight)
+    })?;
+    println!("Added backend {} to {}", address, path.display());
+    finish(&path, reload)
+}
+
+/// Drop a backend entry from the config file
+pub fn run_remove_backend(cli_config: Option<PathBuf>, address: &str, reload: bool) -> Result<()> {
+    let (host, port) = parse_backend_address(address)?;
+    let path = edit_file(cli_config, |content| remove_backend(content, &host, port))?;
+    println!("Removed backend {} from {}", address, path.display());
+    finish(&path, reload)
+}
+
+/// Read the YAML config, apply `edit`, and write the result back
+fn edit_file(
+    cli_config: Option<PathBuf>,
+    edit: impl FnOnce(&str) -> Result<String>,
+) -> Result<PathBuf> {
+    let path = Config::resolve_config_path(cli_config.as_deref())?;
+    if ConfigFormat::from_path(&path) != ConfigFormat::Yaml {
+        bail!(
+            "Only YAML config files can be edited from the CLI: {} (edit it directly instead)",
+            path.display()
+        );
+    }
+
+    let content = fs::read_to_string(&path)
+        .with_context(|| format!("Cannot read configuration file: {}", path.display()))?;
+    let updated = edit(&content)?;
+    write_in_place(&path, &updated)?;
+    Ok(path)
+}
+
+fn finish(path: &Path, reload: bool) -> Result<()> {
+    if reload {
+        ProcessManager::send_reload_signal(Some(path))?;
+        println!("Requested graceful reload");
+    }
+    Ok(())
+}
+
+/// Edit the document and make sure the result is still a valid config
+pub fn apply_set(content: &str, key: &str, value: &str) -> Result<String> {
+    ensure_known_key(key)?;
+    let updated = set_yaml_value(content, key, value)?;
+    ensure_valid(&updated)
+        .with_context(|| format!("Setting {} to '{}' was rejected", key, value))?;
+    Ok(updated)
+}
+
+/// Append `host:port` to the `backends` list
+pub fn add_backend(content: &str, host: &str, port: u16, weight: Option<u32>) -> Result<String> {
+    let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+    let (start, end) = backends_block(&lines)?;
+    let entry_indent = (start..end)
+        .find(|&i| is_content(&lines[i]))
+        .map_or(2, |i| indent_of(&lines[i]));
+    let insert_at = (start..end)
+        .rev()
+        .find(|&i| is_content(&lines[i]))
+        .map_or(start, |i| i + 1);
+
+    let pad = " ".repeat(entry_indent);
+    let mut entry = vec![
+        format!("{}- host: \"{}\"", pad, host),
+        format!("{}  port: {}", pad, port),
+    ];
+    if let Some(weight) = weight {
+        entry.push(format!("{}  weight: {}", pad, weight));
+    }
+    lines.splice(insert_at..insert_at, entry);
+
+    let updated = join_lines(&lines, content);
+    ensure_valid(&updated).with_context(|| format!("Cannot add backend {}:{}", host, port))?;
+    Ok(updated)
+}
+
+/// Remove the `host:port` entry (and only that entry) from the `backends` list
+pub fn remove_backend(content: &str, host: &str, port: u16) -> Result<String> {
+    let config = Config::parse(content, ConfigFormat::Yaml)?;
+    let Some(position) = config
+        .backends
+        .iter()
+        .position(|b| b.host == host && b.port == port)
+    else {
+        bail!("No backend {}:{} in the config", host, port);
+    };
+
+    let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+    let (start, end) = backends_block(&lines)?;
+    let entry_indent = (start..end)
+        .find(|&i| is_content(&lines[i]))
+        .map(|i| indent_of(&lines[i]))
+        .context("backends list is empty")?;
+    let items: Vec<usize> = (start..end)
+        .filter(|&i| indent_of(&lines[i]) == entry_indent && lines[i].trim_start().starts_with('-'))
+        .collect();
+    if items.len() != config.backends.len() {
+        bail!("backends is not a plain block list; edit it in the file directly");
+    }
+
+    let item_start = items[position];
+    let item_end = items.get(position + 1).copied().unwrap_or_else(|| {
+        (item_start..end)
+            .rev()
+            .find(|&i| is_content(&lines[i]))
+            .map_or(end, |i| i + 1)
+    });
+    lines.drain(item_start..item_end);
+
+    let updated = join_lines(&lines, content);
+    ensure_valid(&updated).with_context(|| format!("Cannot remove backend {}:{}", host, port))?;
+    Ok(updated)
+}
+
+fn ensure_valid(content: &str) -> Result<()> {
+    Config::parse(content, ConfigFormat::Yaml)?.validate()
+}
+
+/// Parse `host:port` (or `[v6]:port`) as given on the command line
+fn parse_backend_address(address: &str) -> Result<(String, u16)> {
+    let (host, port) = address
+        .rsplit_once(':')
+        .with_context(|| format!("Backend must be HOST:PORT, got '{}'", address))?;
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    let port = port
+        .parse::<u16>()
+        .with_context(|| format!("Invalid backend port in '{}'", address))?;
+    if host.is_empty() {
+        bail!("Backend must be HOST:PORT, got '{}'", address);
+    }
+    // The host is written into a quoted YAML scalar, so only accept the
+    // characters hostnames and IP literals are made of
+    if let Some(bad) = host
+        .chars()
+        .find(|c| !(c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_' | ':' | '%')))
+    {
+        bail!("Invalid character '{}' in backend host '{}'", bad, host);
+    }
+    Ok((host.to_string(), port))
+}
+
+/// Line range holding the entries of the top-level `backends:` list
+fn backends_block(lines: &[String]) -> Result<(usize, usize)> {
+    let header = lines
+        .iter()
+        .position(|l| indent_of(l) == 0 && key_of(l).map(|(k, _)| k) == Some("backends"))
+        .context("No top-level 'backends' list in the config")?;
+    if !key_of(&lines[header]).map_or(true, |(_, v)| v.is_empty()) {
+        bail!("backends is not a block list; edit it in the file directly");
+    }
+    let start = header + 1;
+    let end = (start..lines.len())
+        .find(|&i| is_content(&lines[i]) && indent_of(&lines[i]) == 0)
+        .unwrap_or(lines.len());
+    Ok((start, end))
+}
+
+/// Reject dotted keys that do not exist in the config schema (typo guard)
+fn ensure_known_key(key: &str) -> Result<()> {
+    let schema = Config::json_schema();
+    let mut node = &schema;
+    for segment in key.split('.') {
+        let resolved = resolve_object(&schema, node);
+        match resolved
+            .and_then(|n| n.get("properties"))
+            .and_then(|p| p.get(segment))
+        {
+            Some(next) => node = next,
+            None => bail!(
+                "Unknown config key '{}' (see `bal schema` for available keys)",
+                key
+            ),
+        }
+    }
+    Ok(())
+}
+
+/// Follow `$ref`/`allOf`/`anyOf` until a schema with `properties` is found
+fn resolve_object<'a>(root: &'a Value, node: &'a Value) -> Option<&'a Value> {
+    if node.get("properties").is_some() {
+        return Some(node);
+    }
+    if let Some(reference) = node.get("$ref").and_then(Value::as_str) {
+        let name = reference.strip_prefix("#/definitions/")?;
+        return resolve_object(root, root.get("definitions")?.get(name)?);
+    }
+    ["allOf", "anyOf"]
+        .iter()
+        .filter_map(|k| node.get(*k).and_then(Value::as_array))
+        .flatten()
+        .find_map(|n| resolve_object(root, n))
+}
+
+/// Set a dotted key in block-style YAML, touching only the affected line(s)
+///
+/// An existing scalar is replaced in place (keeping any trailing comment);
+/// a missing key is appended to the end of its parent mapping, creating the
+/// parent mappings as needed.
+pub fn set_yaml_value(content: &str, key: &str, value: &str) -> Result<String> {
+    let segments: Vec<&str> = key.split('.').collect();
+    if segments.iter().any(|s| s.is_empty()) {
+        bail!("Invalid config key '{}'", key);
+    }
+
+    let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+    let mut start = 0;
+    let mut end = lines.len();
+    let mut parent_indent: Option<usize> = None;
+
+    for (depth, segment) in segments.iter().enumerate() {
+        let first_child = (start..end).find(|&i| is_content(&lines[i]));
+        if let Some(i) = first_child {
+            if lines[i].trim_start().starts_with("- ") && depth > 0 {
+                bail!(
+                    "'{}' is a list; edit its entries in the file directly",
+                    segments[..depth].join(".")
+                );
+            }
+        }
+        let child_indent = first_child
+            .map(|i| indent_of(&lines[i]))
+            .unwrap_or_else(|| parent_indent.map_or(0, |p| p + 2));
+
+        let found = (start..end).find(|&i| {
+            is_content(&lines[i])
+                && indent_of(&lines[i]) == child_indent
+                && key_of(&lines[i]).map(|(k, _)| k) == Some(*segment)
+        });
+
+        let Some(index) = found else {
+            let insert_at = (start..end)
+                .rev()
+                .find(|&i| is_content(&lines[i]))
+                .map_or(start, |i| i + 1);
+            let remaining = &segments[depth..];
+            let new_lines = remaining.iter().enumerate().map(|(offset, segment)| {
+                let pad = " ".repeat(child_indent + offset * 2);
+                if offset + 1 == remaining.len() {
+                    format!("{}{}: {}", pad, segment, value)
+                } else {
+                    format!("{}{}:", pad, segment)
+                }
+            });
+            lines.splice(insert_at..insert_at, new_lines);
+            return Ok(join_lines(&lines, content));
+        };
+
+        let (_, inline_value) = key_of(&lines[index]).expect("matched line has a key");
+        if depth + 1 == segments.len() {
+            if inline_value.is_empty() {
+                bail!(
+                    "'{}' is a mapping or list; set one of its nested keys instead",
+                    key
+                );
+            }
+            lines[index] = replace_scalar(&lines[index], value);
+            return Ok(join_lines(&lines, content));
+        }
+
+        if !inline_value.is_empty() && inline_value != "null" && inline_value != "~" {
+            bail!(
+                "'{}' is not a block mapping; edit it in the file directly",
+                segments[..=depth].join(".")
+            );
+        }
+        if !inline_value.is_empty() {
+            // `runtime: null` becomes an empty mapping we can insert into
+            let colon = lines[index].find(':').expect("matched line has a colon");
+            let (head, rest) = lines[index].split_at(colon + 1);
+            lines[index] = format!("{}{}", head, split_comment(rest).1);
+        }
+        start = index + 1;
+        end = (start..end)
+            .find(|&i| is_content(&lines[i]) && indent_of(&lines[i]) <= child_indent)
+            .unwrap_or(end);
+        parent_indent = Some(child_indent);
+    }
+
+    unreachable!("the last segment always returns")
+}
+
+fn join_lines(lines: &[String], original: &str) -> String {
+    let mut joined = lines.join("\n");
+    if original.ends_with('\n') || original.is_empty() {
+        joined.push('\n');
+    }
+    joined
+}
+
+fn indent_of(line: &str) -> usize {
+    line.len() - line.trim_start_matches(' ').len()
+}
+
+fn is_content(line: &str) -> bool {
+    let trimmed = line.trim();
+    !trimmed.is_empty() && !trimmed.starts_with('#')
+}
+
+/// Split `key: value  # comment` into the unquoted key and the bare value
+fn key_of(line: &str) -> Option<(&str, &str)> {
+    let trimmed = line.trim_start();
+    if trimmed.starts_with("- ") {
+        return None;
+    }
+    let colon = trimmed.find(':')?;
+    let after = &trimmed[colon + 1..];
+    if !after.is_empty() && !after.starts_with(char::is_whitespace) {
+        return None;
+    }
+    let key = trimmed[..colon]
+        .trim()
+        .trim_matches(|c| c == '"' || c == '\'');
+    Some((key, split_comment(after).0.trim()))
+}
+
+/// Split a value into `(value, comment)`, where the comment keeps its leading spaces
+fn split_comment(rest: &str) -> (&str, &str) {
+    let mut quote: Option<char> = None;
+    let mut previous = ' ';
+    for (i, c) in rest.char_indices() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if c == '"' || c == '\'' => quote = Some(c),
+            None if c == '#' && previous.is_whitespace() => {
+                let value = &rest[..i];
+                let value_end = value.trim_end().len();
+                return (&rest[..value_end], &rest[value_end..]);
+            }
+            None => {}
+        }
+        previous = c;
+    }
+    (rest, "")
+}
+
+fn replace_scalar(line: &str, value: &str) -> String {
+    let indent = indent_of(line);
+    let colon = indent + line[indent..].find(':').expect("key line has a colon");
+    let (head, rest) = line.split_at(colon + 1);
+    format!("{} {}{}", head, value, split_comment(rest).1)
+}
+
+/// Replace the file contents via a sibling temp file so readers never see a partial write
+fn write_in_place(path: &Path, content: &str) -> Result<()> {
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "config".to_string());
+    let temp = path.with_file_name(format!(".{}.bal-set.tmp", file_name));
+    fs::write(&temp, content).with_context(|| format!("Failed to write {}", temp.display()))?;
+    if let Ok(metadata) = fs::metadata(path) {
+        let _ = fs::set_permissions(&temp, metadata.permissions());
+    }
+    fs::rename(&temp, path)
+        .with_context(|| format!("Failed to replace configuration file: {}", path.display()))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "\
+# bal config
+port: 9295 # listener port
+
+method: round_robin
+
+runtime:
+  # concurrency cap
+  max_concurrent_connections: 20000   # tuned for prod
+  backend_connect_timeout_ms: 3000
+
+backends:
+  - host: 127.0.0.1
+    port: 9000
+";
+
+    #[test]
+    fn setting_a_value_updates_only_that_key_and_keeps_comments() {
+        let updated = apply_set(SAMPLE, "port", "8080").unwrap();
+        assert_eq!(
+            updated,
+            SAMPLE.replace("port: 9295 # listener port", "port: 8080 # listener port")
+        );
+
+        let updated = apply_set(SAMPLE, "runtime.max_concurrent_connections", "5000").unwrap();
+        assert_eq!(
+            updated,
+            SAMPLE.replace(
+                "max_concurrent_connections: 20000   # tuned",
+                "max_concurrent_connections: 5000   # tuned"
+            )
+        );
+    }
+
+    #[test]
+    fn missing_keys_are_appended_to_their_parent_mapping() {
+        let updated = apply_set(SAMPLE, "runtime.health_check_interval_ms", "2000").unwrap();
+        assert!(updated.contains(
+            "  backend_connect_timeout_ms: 3000\n  health_check_interval_ms: 2000\n\nbackends:"
+        ));
+
+        let without_runtime = "port: 9295\nbackends:\n  - host: 127.0.0.1\n    port: 9000\n";
+        let updated = apply_set(without_runtime, "runtime.watch_config", "true").unwrap();
+        assert!(updated.ends_with("    port: 9000\nruntime:\n  watch_config: true\n"));
+        let config = Config::parse(&updated, ConfigFormat::Yaml).unwrap();
+        assert!(config.runtime.watch_config);
+    }
+
+    #[test]
+    fn adding_and_removing_backends_edits_only_that_entry() {
+        let added = add_backend(SAMPLE, "10.0.0.2", 9001, Some(3)).unwrap();
+        assert_eq!(
+            added,
+            format!(
+                "{}  - host: \"10.0.0.2\"\n    port: 9001\n    weight: 3\n",
+                SAMPLE
+            )
+        );
+
+        let removed = remove_backend(&added, "127.0.0.1", 9000).unwrap();
+        assert!(removed.starts_with("# bal config\nport: 9295 # listener port\n"));
+        assert!(
+            removed.ends_with("backends:\n  - host: \"10.0.0.2\"\n    port: 9001\n    weight: 3\n")
+        );
+
+        // The last backend cannot be removed, and unknown ones are reported
+        assert!(remove_backend(SAMPLE, "127.0.0.1", 9000).is_err());
+        assert!(remove_backend(&added, "10.0.0.9", 9001).is_err());
+    }
+
+    #[test]
+    fn adding_a_duplicate_backend_is_rejected() {
+        let err = add_backend(SAMPLE, "127.0.0.1", 9000, None).unwrap_err();
+        assert!(format!("{:#}", err).contains("Duplicate backend configuration: 127.0.0.1:9000"));
+        assert_eq!(
+            parse_backend_address("[::1]:9000").unwrap(),
+            ("::1".to_string(), 9000)
+        );
+    }
+
+    #[test]
+    fn backend_hosts_that_would_break_the_yaml_are_rejected() {
+        for address in [
+            "10.0.0.2\":9000",
+            "a\\b:9000",
+            "bad host:9000",
+            "x\"\n  - host: evil:9000",
+        ] {
+            let err = parse_backend_address(address).unwrap_err();
+            assert!(err.to_string().contains("Invalid character"), "{}", address);
+        }
+        assert_eq!(
+            parse_backend_address("backend-1.internal:9000").unwrap(),
+            ("backend-1.internal".to_string(), 9000)
+        );
+    }
+
+    #[test]
+    fn invalid_or_unknown_settings_are_rejected() {
+        assert!(apply_set(SAMPLE, "port", "not-a-port").is_err());
+        assert!(apply_set(SAMPLE, "runtime.max_concurent_connections", "1").is_err());
+        assert!(apply_set(SAMPLE, "runtime", "1").is_err());
+        assert!(apply_set(SAMPLE, "backends.port", "1").is_err());
+    }
+}
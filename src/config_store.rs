@@ -6,11 +6,26 @@
 
 use anyhow::{bail, Context, Result};
 use log::{debug, info, warn};
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
-use crate::config::Config;
+use crate::config::{is_dns_no_addresses, Config};
+use crate::constants::{get_reload_request_path, STARTUP_CONFIG_RETRY_MAX_MS};
+use crate::runtime_stats::{self, RuntimeSnapshot};
 use crate::state::{AppState, RuntimeConfig};
 
+/// How long startup keeps waiting for a config file that is missing or unreadable
+///
+/// During a deploy the file may show up a moment after bal is started, so
+/// systemd/container ordering does not have to be exact. `delay` is the first
+/// wait and doubles per retry. Zero retries (the default) fails on the first
+/// attempt.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StartupRetry {
+    pub retries: u32,
+    pub delay: Duration,
+}
+
 /// Configuration store
 ///
 /// Handles configuration file loading, validation, and hot-swapping.
@@ -21,7 +36,16 @@ impl ConfigStore {
     ///
     /// Validates new configuration file and converts to RuntimeConfig if valid.
     /// Also pre-checks backend connectivity.
+    #[cfg(test)]
     pub async fn validate_and_load(path: &Path) -> Result<RuntimeConfig> {
+        let (runtime_config, _reachable) = Self::load_and_probe(path).await?;
+        Ok(runtime_config)
+    }
+
+    /// Load configuration and count reachable backends
+    ///
+    /// Fails if no backend is reachable at all.
+    async fn load_and_probe(path: &Path) -> Result<(RuntimeConfig, usize)> {
         debug!("Loading configuration file: {}", path.display());
 
         // Load configuration file
@@ -33,22 +57,31 @@ impl ConfigStore {
         info!("Pre-validating backend connectivity...");
         let mut failed_count = 0;
 
-        for backend in &config.backends {
+        let enabled_count = config.enabled_backends().count();
+        for backend in config.enabled_backends() {
             match backend.check_connectivity().await {
                 Ok(()) => {
-                    debug!(
-                        "  [OK] {}:{} - Connection successful",
-                        backend.host, backend.port
+                    debug!("  [OK] {} - Connection successful", backend.address());
+                }
+                Err(e) if is_dns_no_addresses(&e) => {
+                    warn!(
+                        "  [FAIL] {} - host resolves to no addresses; it will stay unhealthy until DNS returns records ({})",
+                        backend.address(),
+                        e
                     );
+                    failed_count += 1;
                 }
                 Err(e) => {
-                    warn!("  [FAIL] {}:{} - {}", backend.host, backend.port, e);
+                    warn!("  [FAIL] {} - {}", backend.address(), e);
                     failed_count += 1;
                 }
             }
         }
 
-        if failed_count == config.backends.len() {
+        if enabled_count == 0 {
+            // Only reachable with allow_empty_backends (validate rejects it otherwise)
+            warn!("No enabled backends configured; every client connection will be closed");
+        } else if failed_count == enabled_count {
             bail!("Cannot connect to any backend. Please check your configuration.");
         }
 
@@ -61,14 +94,53 @@ impl ConfigStore {
 
         info!("Configuration file validation passed");
 
-        Ok(RuntimeConfig::from_config(config, path.to_path_buf()))
+        let reachable = enabled_count - failed_count;
+        Ok((
+            RuntimeConfig::from_config(config, path.to_path_buf()),
+            reachable,
+        ))
+    }
+
+    /// Refuse to start when fewer than `startup_min_healthy` backends are reachable
+    fn enforce_startup_min_healthy(
+        min_healthy: Option<usize>,
+        reachable: usize,
+        total: usize,
+    ) -> Result<()> {
+        match min_healthy {
+            Some(min) if reachable < min => bail!(
+                "Only {}/{} backends reachable at startup; startup_min_healthy requires {}",
+                reachable,
+                total,
+                min
+            ),
+            _ => Ok(()),
+        }
+    }
+
+    /// Reject a reload with any unreachable backend under `reload_require_all_healthy`
+    fn enforce_reload_all_healthy(required: bool, reachable: usize, total: usize) -> Result<()> {
+        if required && reachable < total {
+            bail!(
+                "Only {}/{} backends reachable; reload_require_all_healthy requires all of them",
+                reachable,
+                total
+            );
+        }
+        Ok(())
     }
 
     /// Validate a candidate config for reload without applying it.
     pub async fn validate_reload_candidate(path: &Path) -> Result<RuntimeConfig> {
-        Self::validate_and_load(path)
-            .await
-            .with_context(|| format!("Pre-reload validation failed: {}", path.display()))
+        let context = || format!("Pre-reload validation failed: {}", path.display());
+        let (runtime_config, reachable) = Self::load_and_probe(path).await.with_context(context)?;
+        Self::enforce_reload_all_healthy(
+            runtime_config.runtime_tuning.reload_require_all_healthy,
+            reachable,
+            runtime_config.backend_pool.total_count(),
+        )
+        .with_context(context)?;
+        Ok(runtime_config)
     }
 
     /// Perform configuration hot-swap
@@ -111,26 +183,39 @@ impl ConfigStore {
 
         // Replace configuration (atomic via arc-swap)
         state.swap_config(new_runtime_config);
+        runtime_stats::write_snapshot(&RuntimeSnapshot::capture(state).await);
 
         info!("Configuration successfully reloaded");
         Ok(())
     }
 
+    /// Take the pending config switch request left by `bal reload --config`
+    ///
+    /// The request file is consumed so a later plain SIGHUP reloads the
+    /// currently active file rather than repeating the switch.
+    pub fn take_reload_request() -> Option<PathBuf> {
+        let request_path = get_reload_request_path();
+        let content = std::fs::read_to_string(&request_path).ok()?;
+        let _ = std::fs::remove_file(&request_path);
+
+        let target = content.trim();
+        if target.is_empty() {
+            return None;
+        }
+        Some(PathBuf::from(target))
+    }
+
     /// Load initial configuration
     ///
     /// Loads configuration file at application startup, or creates default
-    /// template if file doesn't exist.
+    /// template if file doesn't exist. A file given via CLI that is missing or
+    /// unreadable is waited for according to `retry`.
     pub async fn load_initial_config(
         cli_path: Option<&Path>,
+        retry: StartupRetry,
     ) -> Result<(RuntimeConfig, std::path::PathBuf)> {
         let path = if let Some(p) = cli_path {
             // Use path specified via CLI
-            if !p.exists() {
-                bail!(
-                    "Specified configuration file does not exist: {}",
-                    p.display()
-                );
-            }
             p.to_path_buf()
         } else {
             // Search default paths or create
@@ -146,9 +231,265 @@ impl ConfigStore {
             }
         };
 
+        Self::wait_for_config_file(&path, retry).await?;
         info!("Loading configuration file: {}", path.display());
-        let runtime_config = Self::validate_and_load(&path).await?;
+        let (runtime_config, reachable) = Self::load_and_probe(&path).await?;
+
+        Self::enforce_startup_min_healthy(
+            runtime_config.runtime_tuning.startup_min_healthy,
+            reachable,
+            runtime_config.backend_pool.total_count(),
+        )?;
 
         Ok((runtime_config, path))
     }
+
+    /// Wait until the config file can be opened
+    ///
+    /// Only a missing or unreadable file is retried, with the delay doubling
+    /// after each attempt (capped at `STARTUP_CONFIG_RETRY_MAX_MS`). A file
+    /// that opens but fails to parse, validate or probe fails right away.
+    async fn wait_for_config_file(path: &Path, retry: StartupRetry) -> Result<()> {
+        let max_delay = Duration::from_millis(STARTUP_CONFIG_RETRY_MAX_MS);
+        let mut delay = retry.delay;
+        let mut attempt = 0;
+        loop {
+            let err = match tokio::fs::File::open(path).await {
+                Ok(_) => return Ok(()),
+                Err(err) => err,
+            };
+            if attempt >= retry.retries {
+                if err.kind() == std::io::ErrorKind::NotFound {
+                    bail!(
+                        "Specified configuration file does not exist: {}",
+                        path.display()
+                    );
+                }
+                return Err(err).with_context(|| {
+                    format!("Cannot read configuration file: {}", path.display())
+                });
+            }
+
+            attempt += 1;
+            warn!(
+                "Configuration file {} not readable yet ({}); retry {}/{} in {}ms",
+                path.display(),
+                err,
+                attempt,
+                retry.retries,
+                delay.as_millis()
+            );
+            tokio::time::sleep(delay).await;
+            delay = (delay * 2).min(max_delay);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+    use std::sync::Arc;
+
+    fn write_config(dir: &Path, name: &str, backend_port: u16) -> PathBuf {
+        let path = dir.join(name);
+        std::fs::write(
+            &path,
+            format!(
+                "port: 9295\nbackends:\n  - host: \"127.0.0.1\"\n    port: {}\n",
+                backend_port
+            ),
+        )
+        .expect("config should be written");
+        path
+    }
+
+    async fn state_from(path: &Path) -> Arc<AppState> {
+        let runtime_config = ConfigStore::validate_and_load(path)
+            .await
+            .expect("initial config should load");
+        AppState::for_test(runtime_config)
+    }
+
+    #[tokio::test]
+    async fn reload_from_alternate_file_swaps_backends_and_config_path() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let first = TcpListener::bind("127.0.0.1:0").expect("bind first backend");
+        let second = TcpListener::bind("127.0.0.1:0").expect("bind second backend");
+        let first_port = first.local_addr().unwrap().port();
+        let second_port = second.local_addr().unwrap().port();
+
+        let original = write_config(dir.path(), "original.yaml", first_port);
+        let alternate = write_config(dir.path(), "alternate.yaml", second_port);
+        let state = state_from(&original).await;
+
+        ConfigStore::reload_config(&state, Some(&alternate))
+            .await
+            .expect("alternate config should be accepted");
+
+        let config = state.config();
+        assert_eq!(config.config_path, alternate);
+        let ports: Vec<u16> = config
+            .backend_pool
+            .all_backends()
+            .iter()
+            .map(|b| b.config.port)
+            .collect();
+        assert_eq!(ports, vec![second_port]);
+    }
+
+    #[tokio::test]
+    async fn reload_from_invalid_alternate_file_keeps_previous_config() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let backend = TcpListener::bind("127.0.0.1:0").expect("bind backend");
+        let backend_port = backend.local_addr().unwrap().port();
+
+        let original = write_config(dir.path(), "original.yaml", backend_port);
+        let invalid = dir.path().join("invalid.yaml");
+        std::fs::write(&invalid, "port: 9295\nbackends: []\n").expect("write invalid config");
+        let state = state_from(&original).await;
+
+        let result = ConfigStore::reload_config(&state, Some(&invalid)).await;
+        assert!(result.is_err());
+
+        let config = state.config();
+        assert_eq!(config.config_path, original);
+        assert_eq!(
+            config.backend_pool.all_backends()[0].config.port,
+            backend_port
+        );
+    }
+
+    #[test]
+    fn startup_min_healthy_threshold() {
+        assert!(ConfigStore::enforce_startup_min_healthy(None, 1, 3).is_ok());
+        assert!(ConfigStore::enforce_startup_min_healthy(Some(2), 2, 3).is_ok());
+        assert!(ConfigStore::enforce_startup_min_healthy(Some(3), 2, 3).is_err());
+    }
+
+    #[tokio::test]
+    async fn startup_refuses_when_too_few_backends_are_reachable() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let up = TcpListener::bind("127.0.0.1:0").expect("bind backend");
+        let up_port = up.local_addr().unwrap().port();
+        let down_port = {
+            let listener = TcpListener::bind("127.0.0.1:0").expect("bind backend");
+            listener.local_addr().unwrap().port()
+        };
+
+        let path = dir.path().join("config.yaml");
+        let write = |min_healthy: usize| {
+            std::fs::write(
+                &path,
+                format!(
+                    "port: 9295\nruntime:\n  startup_min_healthy: {}\nbackends:\n  - host: \"127.0.0.1\"\n    port: {}\n  - host: \"127.0.0.1\"\n    port: {}\n",
+                    min_healthy, up_port, down_port
+                ),
+            )
+            .expect("config should be written");
+        };
+
+        write(1);
+        assert!(
+            ConfigStore::load_initial_config(Some(&path), StartupRetry::default())
+                .await
+                .is_ok()
+        );
+
+        write(2);
+        let err = ConfigStore::load_initial_config(Some(&path), StartupRetry::default())
+            .await
+            .expect_err("one reachable backend should not satisfy startup_min_healthy: 2");
+        assert!(err.to_string().contains("startup_min_healthy"));
+    }
+
+    #[tokio::test]
+    async fn strict_reload_requires_every_backend_to_be_reachable() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let up = TcpListener::bind("127.0.0.1:0").expect("bind backend");
+        let also_up = TcpListener::bind("127.0.0.1:0").expect("bind backend");
+        let up_port = up.local_addr().unwrap().port();
+        let also_up_port = also_up.local_addr().unwrap().port();
+        let down_port = {
+            let listener = TcpListener::bind("127.0.0.1:0").expect("bind backend");
+            listener.local_addr().unwrap().port()
+        };
+
+        let original = write_config(dir.path(), "original.yaml", up_port);
+        let state = state_from(&original).await;
+        let candidate = dir.path().join("candidate.yaml");
+        let write = |second_port: u16| {
+            std::fs::write(
+                &candidate,
+                format!(
+                    "port: 9295\nruntime:\n  reload_require_all_healthy: true\nbackends:\n  - host: \"127.0.0.1\"\n    port: {}\n  - host: \"127.0.0.1\"\n    port: {}\n",
+                    up_port, second_port
+                ),
+            )
+            .expect("config should be written");
+        };
+
+        write(down_port);
+        let err = ConfigStore::reload_config(&state, Some(&candidate))
+            .await
+            .expect_err("a partially reachable candidate should be rejected");
+        assert!(format!("{:#}", err).contains("reload_require_all_healthy"));
+        assert_eq!(state.config().backend_pool.total_count(), 1);
+
+        write(also_up_port);
+        ConfigStore::reload_config(&state, Some(&candidate))
+            .await
+            .expect("an all-reachable candidate should be accepted");
+        assert_eq!(state.config().backend_pool.total_count(), 2);
+
+        assert!(ConfigStore::enforce_reload_all_healthy(false, 1, 2).is_ok());
+    }
+
+    #[tokio::test]
+    async fn startup_waits_for_a_config_that_appears_later() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let backend = TcpListener::bind("127.0.0.1:0").expect("bind backend");
+        let backend_port = backend.local_addr().unwrap().port();
+        let path = dir.path().join("late.yaml");
+
+        let err = ConfigStore::load_initial_config(Some(&path), StartupRetry::default())
+            .await
+            .expect_err("missing config should fail without retries");
+        assert!(err.to_string().contains("does not exist"));
+
+        let writer_dir = dir.path().to_path_buf();
+        let writer = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(150)).await;
+            write_config(&writer_dir, "late.yaml", backend_port);
+        });
+
+        let retry = StartupRetry {
+            retries: 20,
+            delay: Duration::from_millis(50),
+        };
+        let (runtime_config, loaded_path) = ConfigStore::load_initial_config(Some(&path), retry)
+            .await
+            .expect("config written during the retry window should load");
+        assert_eq!(loaded_path, path);
+        assert_eq!(runtime_config.backend_pool.total_count(), 1);
+        writer.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn startup_does_not_retry_a_config_that_fails_to_parse() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("broken.yaml");
+        std::fs::write(&path, "port: [not a port\n").unwrap();
+
+        let retry = StartupRetry {
+            retries: 5,
+            delay: Duration::from_secs(1),
+        };
+        let started = std::time::Instant::now();
+        let err = ConfigStore::load_initial_config(Some(&path), retry)
+            .await
+            .expect_err("a config that fails to parse should not load");
+        assert!(format!("{:#}", err).contains("parsing failed"));
+        assert!(started.elapsed() < Duration::from_secs(1));
+    }
 }
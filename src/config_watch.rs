@@ -0,0 +1,187 @@
+//! Config file watcher
+//!
+//! With `watch_config`, the daemon watches its config file and requests a
+//! reload through the same channel as `bal graceful`, so a bad edit is
+//! rejected by the normal reload validation and the running config is kept.
+
+use anyhow::{Context, Result};
+use log::{debug, info, warn};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{broadcast, mpsc};
+use tokio::time::timeout;
+
+use crate::constants::{CONFIG_WATCH_DEBOUNCE_MS, CONFIG_WATCH_PATH_POLL_MS};
+use crate::state::AppState;
+
+/// Watch the active config file until shutdown, triggering debounced reloads
+///
+/// The parent directory is watched rather than the file itself because
+/// editors commonly save by writing a temp file and renaming it over the
+/// original, which would orphan a watch on the old inode. When a reload
+/// (`bal graceful --config`) switches to a file in another directory, the
+/// watch moves to that directory.
+pub async fn run(state: Arc<AppState>, mut shutdown: broadcast::Receiver<()>) -> Result<()> {
+    let config_path = state.config().config_path.clone();
+    let mut watch_dir = watch_dir_for(&config_path);
+
+    let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+    let mut watcher = RecommendedWatcher::new(
+        move |event: notify::Result<notify::Event>| {
+            let _ = event_tx.send(event);
+        },
+        notify::Config::default(),
+    )
+    .context("Failed to create config file watcher")?;
+    watcher
+        .watch(&watch_dir, RecursiveMode::NonRecursive)
+        .with_context(|| format!("Failed to watch {}", watch_dir.display()))?;
+
+    info!("Watching {} for changes", config_path.display());
+
+    let mut path_poll = tokio::time::interval(Duration::from_millis(CONFIG_WATCH_PATH_POLL_MS));
+    loop {
+        tokio::select! {
+            _ = path_poll.tick() => {
+                let config_path = state.config().config_path.clone();
+                let current_dir = watch_dir_for(&config_path);
+                if current_dir == watch_dir {
+                    continue;
+                }
+
+                let _ = watcher.unwatch(&watch_dir);
+                match watcher.watch(&current_dir, RecursiveMode::NonRecursive) {
+                    Ok(()) => info!("Watching {} for changes", config_path.display()),
+                    Err(e) => warn!(
+                        "Failed to watch {} - config changes won't trigger reloads: {}",
+                        current_dir.display(),
+                        e
+                    ),
+                }
+                watch_dir = current_dir;
+            }
+            event = event_rx.recv() => {
+                let Some(event) = event else { break };
+                if !touches_config(&event, &state.config().config_path) {
+                    continue;
+                }
+
+                // Wait for the writer to go quiet before reloading
+                while let Ok(Some(_)) =
+                    timeout(Duration::from_millis(CONFIG_WATCH_DEBOUNCE_MS), event_rx.recv()).await
+                {}
+
+                info!("Config file changed - requesting reload");
+                if let Err(e) = state.trigger_reload().await {
+                    warn!("Failed to request reload after config change: {}", e);
+                }
+            }
+            _ = shutdown.recv() => {
+                debug!("Config watcher received shutdown signal");
+                break;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Directory to watch for `config_path`
+fn watch_dir_for(config_path: &Path) -> PathBuf {
+    config_path
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or_else(|| Path::new("."))
+        .to_path_buf()
+}
+
+/// Whether a watcher event modified the given config file
+fn touches_config(event: &notify::Result<notify::Event>, config_path: &Path) -> bool {
+    let Ok(event) = event else {
+        return false;
+    };
+    if event.kind.is_access() {
+        return false;
+    }
+
+    let file_name = config_path.file_name();
+    event
+        .paths
+        .iter()
+        .any(|path| path == config_path || (file_name.is_some() && path.file_name() == file_name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::RuntimeTuning;
+    use crate::state::RuntimeConfig;
+
+    fn runtime_config(config_path: &Path) -> RuntimeConfig {
+        RuntimeConfig {
+            config_path: config_path.to_path_buf(),
+            ..RuntimeConfig::for_test(Vec::new(), RuntimeTuning::default())
+        }
+    }
+
+    #[tokio::test]
+    async fn modifying_watched_config_requests_reload() {
+        let dir = tempfile::tempdir().unwrap();
+        let config_path = dir.path().join("config.yaml");
+        std::fs::write(&config_path, "port: 9295\n").unwrap();
+
+        let runtime_config = runtime_config(&config_path);
+        let (shutdown_tx, _) = broadcast::channel(4);
+        let (reload_tx, mut reload_rx) = mpsc::channel(4);
+        let state = Arc::new(AppState::new(runtime_config, shutdown_tx, reload_tx));
+
+        let watcher = tokio::spawn(run(Arc::clone(&state), state.subscribe_shutdown()));
+        // Give the watcher time to register before editing
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        std::fs::write(dir.path().join("unrelated.yaml"), "x: 1\n").unwrap();
+        std::fs::write(&config_path, "port: 9296\n").unwrap();
+
+        timeout(Duration::from_secs(5), reload_rx.recv())
+            .await
+            .expect("config change should request a reload")
+            .expect("reload channel should stay open");
+
+        state.trigger_shutdown();
+        watcher.await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn switching_to_a_config_in_another_directory_moves_the_watch() {
+        let (old_dir, new_dir) = (tempfile::tempdir().unwrap(), tempfile::tempdir().unwrap());
+        let old_path = old_dir.path().join("config.yaml");
+        let new_path = new_dir.path().join("bal.yaml");
+        std::fs::write(&old_path, "port: 9295\n").unwrap();
+        std::fs::write(&new_path, "port: 9295\n").unwrap();
+
+        let (shutdown_tx, _) = broadcast::channel(4);
+        let (reload_tx, mut reload_rx) = mpsc::channel(4);
+        let state = Arc::new(AppState::new(
+            runtime_config(&old_path),
+            shutdown_tx,
+            reload_tx,
+        ));
+        let watcher = tokio::spawn(run(Arc::clone(&state), state.subscribe_shutdown()));
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        // What `bal graceful --config <new_dir>/bal.yaml` leaves behind
+        state.swap_config(runtime_config(&new_path));
+        tokio::time::sleep(Duration::from_millis(CONFIG_WATCH_PATH_POLL_MS + 300)).await;
+
+        std::fs::write(&new_path, "port: 9296\n").unwrap();
+        timeout(Duration::from_secs(5), reload_rx.recv())
+            .await
+            .expect("editing the new config should request a reload")
+            .expect("reload channel should stay open");
+
+        state.trigger_shutdown();
+        watcher.await.unwrap().unwrap();
+    }
+}
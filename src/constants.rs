@@ -3,10 +3,13 @@
 //! Centralizes constants used throughout the application.
 //! This improves maintainability by requiring changes in only one place.
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 
 /// Application basic settings
+#[allow(dead_code)]
 pub const APP_NAME: &str = "bal";
+#[allow(dead_code)]
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 
 /// Default port and network settings
@@ -15,6 +18,12 @@ pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 /// to avoid conflicts with other common ports.
 pub const DEFAULT_PORT: u16 = 9295;
 
+/// Listen backlog used when `tcp_backlog` is not configured (matches tokio's default)
+pub const DEFAULT_TCP_BACKLOG: u32 = 1024;
+
+/// First descriptor systemd passes with socket activation (`SD_LISTEN_FDS_START`)
+pub const SD_LISTEN_FDS_START: i32 = 3;
+
 /// Health check settings
 ///
 /// Ultra-fast failover: 200ms interval for sub-second detection and recovery.
@@ -22,13 +31,28 @@ pub const HEALTH_CHECK_INTERVAL_MS: u64 = 200;
 pub const HEALTH_CHECK_TIMEOUT_MS: u64 = 500;
 pub const HEALTH_CHECK_MAX_RETRIES: u32 = 1;
 pub const HEALTH_CHECK_MIN_SUCCESS: u32 = 1;
+/// Probes in flight at once when `health_check_concurrency` is not configured
+pub const DEFAULT_HEALTH_CHECK_CONCURRENCY: usize = 64;
 
 /// Connection settings
 ///
 /// Backend connection attempt timeout - too short causes unnecessary failure
 /// detection during temporary network delays, too long causes failover delays.
+#[allow(dead_code)]
 pub const BACKEND_CONNECT_TIMEOUT_SECS: u64 = 5;
+/// First sleep after a failed accept(); doubles per consecutive failure
+pub const ACCEPT_ERROR_BACKOFF_INITIAL_MS: u64 = 5;
+/// Longest sleep between accept() retries when `accept_error_backoff_max_ms` is not configured
+pub const DEFAULT_ACCEPT_ERROR_BACKOFF_MAX_MS: u64 = 1000;
+/// Spare connections per backend with `backend_keepalive`
+pub const DEFAULT_BACKEND_KEEPALIVE_POOL_SIZE: usize = 4;
+/// Spares older than this are closed rather than handed to a client
+pub const DEFAULT_BACKEND_KEEPALIVE_IDLE_MS: u64 = 30_000;
+/// Per-direction relay buffer used when `relay_buffer_size` is not configured
 pub const PROXY_BUFFER_SIZE: usize = 8192;
+/// Accepted `relay_buffer_size` range
+pub const MIN_RELAY_BUFFER_SIZE: usize = 1024;
+pub const MAX_RELAY_BUFFER_SIZE: usize = 1024 * 1024;
 
 /// Graceful shutdown settings
 ///
@@ -36,10 +60,43 @@ pub const PROXY_BUFFER_SIZE: usize = 8192;
 /// Forces shutdown after this time to prevent infinite waits.
 pub const GRACEFUL_SHUTDOWN_TIMEOUT_SECS: u64 = 30;
 
+/// Config watch settings
+///
+/// Editors and GitOps tools often write a file in several steps; changes are
+/// coalesced until the file has been quiet for this long before reloading.
+pub const CONFIG_WATCH_DEBOUNCE_MS: u64 = 300;
+/// How often the watcher checks whether a reload switched to a config file
+/// in another directory, which it then starts watching instead
+pub const CONFIG_WATCH_PATH_POLL_MS: u64 = 500;
+
+/// Startup config retry settings
+///
+/// `--startup-config-retry-ms` is the first wait; it doubles per retry up to
+/// this cap.
+pub const STARTUP_CONFIG_RETRY_MAX_MS: u64 = 10_000;
+
+/// How often the daemon checks whether `bal maintenance` toggled the flag file
+pub const MAINTENANCE_POLL_INTERVAL_MS: u64 = 500;
+
+/// Shortest pause between heartbeats, however small the watchdog timeout
+pub const MIN_HEARTBEAT_INTERVAL_MS: u64 = 1;
+
+/// State snapshots older than this are treated as leftovers of a crashed run
+/// when no daemon is running
+pub const STALE_SNAPSHOT_AFTER_MS: u64 = 300_000;
+
 /// File and directory settings
 pub const PID_FILENAME: &str = "bal.pid";
 pub const LOG_FILENAME: &str = "bal.log";
 pub const CONFIG_FILENAME: &str = "config.yaml";
+pub const RELOAD_REQUEST_FILENAME: &str = "reload_request";
+pub const MAINTENANCE_FILENAME: &str = "maintenance";
+pub const RESET_STATS_REQUEST_FILENAME: &str = "reset_stats_request";
+pub const LOG_LEVEL_REQUEST_FILENAME: &str = "log_level_request";
+pub const PROTECTION_STATE_FILENAME: &str = "protection_state.json";
+pub const RUNTIME_STATS_FILENAME: &str = "runtime_stats.json";
+/// Overrides the runtime directory (PID, log and state files)
+pub const RUNTIME_DIR_ENV: &str = "BAL_RUNTIME_DIR";
 
 /// Configuration file priority (higher = more priority)
 /// 1. Path specified via CLI argument
@@ -57,21 +114,204 @@ pub fn get_system_config_path() -> PathBuf {
 
 /// PID file path ($HOME/.bal/bal.pid)
 pub fn get_pid_file_path() -> PathBuf {
-    dirs::home_dir()
-        .map(|home| home.join(".bal").join(PID_FILENAME))
-        .unwrap_or_else(|| PathBuf::from(PID_FILENAME))
+    get_runtime_file_path(PID_FILENAME)
 }
 
 /// Log file path ($HOME/.bal/bal.log)
 pub fn get_log_file_path() -> PathBuf {
-    dirs::home_dir()
-        .map(|home| home.join(".bal").join(LOG_FILENAME))
-        .unwrap_or_else(|| PathBuf::from(LOG_FILENAME))
+    get_runtime_file_path(LOG_FILENAME)
+}
+
+/// Pending reload target path ($HOME/.bal/reload_request)
+///
+/// Written by `bal reload --config <FILE>` right before SIGHUP so the daemon
+/// knows which file to switch to.
+pub fn get_reload_request_path() -> PathBuf {
+    get_runtime_file_path(RELOAD_REQUEST_FILENAME)
+}
+
+/// Maintenance flag path ($HOME/.bal/maintenance)
+///
+/// Present while `bal maintenance on` is in effect; the daemon refuses new
+/// clients for as long as it exists.
+pub fn get_maintenance_path() -> PathBuf {
+    get_runtime_file_path(MAINTENANCE_FILENAME)
+}
+
+/// Pending stats reset request path ($HOME/.bal/reset_stats_request)
+///
+/// Written by `bal reset-stats`; the daemon zeroes its counters and removes
+/// the file on its next maintenance poll.
+pub fn get_reset_stats_request_path() -> PathBuf {
+    get_runtime_file_path(RESET_STATS_REQUEST_FILENAME)
+}
+
+/// Pending log level request path ($HOME/.bal/log_level_request)
+///
+/// Written by `bal log-level <LEVEL>` right before SIGUSR2; without it the
+/// signal toggles debug logging.
+pub fn get_log_level_request_path() -> PathBuf {
+    get_runtime_file_path(LOG_LEVEL_REQUEST_FILENAME)
+}
+
+/// Runtime file `file_name` for the current `--instance`
+///
+/// Instances share the runtime directory; their files are told apart by a
+/// `-<instance>` suffix on the file stem (`bal-web.pid`, `maintenance-web`).
+pub fn get_runtime_file_path(file_name: &str) -> PathBuf {
+    get_runtime_dir().join(instance_file_name(file_name, instance_name()))
 }
 
-/// Runtime directory path ($HOME/.bal/)
+/// `file_name` namespaced for `instance`; unchanged without one
+pub fn instance_file_name(file_name: &str, instance: Option<&str>) -> String {
+    match (instance, file_name.split_once('.')) {
+        (None, _) => file_name.to_string(),
+        (Some(instance), Some((stem, ext))) => format!("{}-{}.{}", stem, instance, ext),
+        (Some(instance), None) => format!("{}-{}", file_name, instance),
+    }
+}
+
+/// `--instance`, set once at startup before any path is resolved
+static INSTANCE_NAME: OnceLock<String> = OnceLock::new();
+
+/// Namespace every runtime file for the named instance (`--instance`)
+///
+/// Only the first call takes effect; later ones are ignored.
+pub fn set_instance_name(name: String) {
+    let _ = INSTANCE_NAME.set(name);
+}
+
+/// Name of the instance this process runs or controls, if any
+pub fn instance_name() -> Option<&'static str> {
+    INSTANCE_NAME.get().map(String::as_str)
+}
+
+/// `--runtime-dir`, or the directory resolved at startup, set once before
+/// `fork_daemon` changes into /tmp
+static RUNTIME_DIR_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
+
+/// Relocate every runtime path to `dir` (`--runtime-dir`)
+///
+/// A relative `dir` is resolved against the current directory. Only the
+/// first call takes effect; later ones are ignored. main also pins the
+/// resolved directory this way before daemonizing.
+pub fn set_runtime_dir_override(dir: PathBuf) {
+    let _ = RUNTIME_DIR_OVERRIDE.set(absolute_dir(dir));
+}
+
+/// Runtime directory path (--runtime-dir, else $BAL_RUNTIME_DIR, else $HOME/.bal/)
 pub fn get_runtime_dir() -> PathBuf {
-    dirs::home_dir()
-        .map(|home| home.join(".bal"))
-        .unwrap_or_else(|| PathBuf::from("."))
+    runtime_dir_from(RUNTIME_DIR_OVERRIDE.get().map(PathBuf::as_path), |name| {
+        std::env::var(name).ok()
+    })
+}
+
+/// Runtime directory for an optional `--runtime-dir`, looking
+/// `BAL_RUNTIME_DIR` up via `lookup`
+pub fn runtime_dir_from<F>(cli_dir: Option<&Path>, lookup: F) -> PathBuf
+where
+    F: Fn(&str) -> Option<String>,
+{
+    cli_dir
+        .map(Path::to_path_buf)
+        .or_else(|| {
+            lookup(RUNTIME_DIR_ENV)
+                .filter(|dir| !dir.is_empty())
+                .map(|dir| absolute_dir(PathBuf::from(dir)))
+        })
+        .or_else(|| dirs::home_dir().map(|home| home.join(".bal")))
+        .unwrap_or_else(|| absolute_dir(PathBuf::from(".")))
+}
+
+/// `dir` resolved against the current directory (the one bal was launched
+/// from, as long as this runs before `fork_daemon`)
+fn absolute_dir(dir: PathBuf) -> PathBuf {
+    if dir.is_absolute() {
+        return dir;
+    }
+    std::env::current_dir()
+        .map(|cwd| cwd.join(&dir))
+        .unwrap_or(dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bal_runtime_dir_overrides_the_home_directory() {
+        let env = |name: &str| (name == RUNTIME_DIR_ENV).then(|| "/run/bal".to_string());
+        assert_eq!(runtime_dir_from(None, env), PathBuf::from("/run/bal"));
+        // --runtime-dir wins over the environment
+        assert_eq!(
+            runtime_dir_from(Some(Path::new("/srv/bal-b")), env),
+            PathBuf::from("/srv/bal-b")
+        );
+        // A relative value means the launch directory, not the daemon's /tmp
+        let relative = |_: &str| Some("state/bal".to_string());
+        assert_eq!(
+            runtime_dir_from(None, relative),
+            std::env::current_dir().unwrap().join("state/bal")
+        );
+
+        // Unset or empty falls back to $HOME/.bal
+        let home_default = dirs::home_dir()
+            .map(|home| home.join(".bal"))
+            .unwrap_or_else(|| PathBuf::from("."));
+        assert_eq!(runtime_dir_from(None, |_| None), home_default);
+        assert_eq!(
+            runtime_dir_from(None, |_| Some(String::new())),
+            home_default
+        );
+    }
+
+    #[test]
+    fn instance_names_suffix_the_file_stem() {
+        assert_eq!(instance_file_name(PID_FILENAME, None), "bal.pid");
+        assert_eq!(instance_file_name(PID_FILENAME, Some("web")), "bal-web.pid");
+        assert_eq!(
+            instance_file_name(PROTECTION_STATE_FILENAME, Some("web")),
+            "protection_state-web.json"
+        );
+        assert_eq!(
+            instance_file_name(MAINTENANCE_FILENAME, Some("db")),
+            "maintenance-db"
+        );
+    }
+
+    #[test]
+    fn runtime_dir_override_relocates_every_state_file() {
+        // The only test setting the process-wide override. Other tests may
+        // write snapshots under it afterwards, so it's a fixed scratch dir
+        // rather than a TempDir removed mid-run
+        let runtime_dir = std::env::temp_dir().join("bal-runtime-test");
+        set_runtime_dir_override(runtime_dir.clone());
+        assert_eq!(get_runtime_dir(), runtime_dir);
+
+        for path in [
+            get_pid_file_path(),
+            get_log_file_path(),
+            get_reload_request_path(),
+            get_maintenance_path(),
+            get_reset_stats_request_path(),
+            get_log_level_request_path(),
+            crate::protection::protection_state_path(),
+            crate::runtime_stats::runtime_stats_path(),
+        ] {
+            assert_eq!(
+                path.parent(),
+                Some(runtime_dir.as_path()),
+                "{}",
+                path.display()
+            );
+        }
+
+        // A relative --runtime-dir is stored resolved, so the daemon (which
+        // changes into /tmp) and the CLI agree on where it is
+        assert_eq!(
+            absolute_dir(PathBuf::from("bal-a")),
+            std::env::current_dir().unwrap().join("bal-a")
+        );
+        assert_eq!(absolute_dir(runtime_dir.clone()), runtime_dir);
+    }
 }
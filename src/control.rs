@@ -0,0 +1,268 @@
+//! Control endpoint
+//!
+//! Optional HTTP listener on `control_address` for orchestrators.
+//! `GET /livez` answers 200 while the process is up; `GET /readyz` answers
+//! 200 only when the proxy listener is bound and at least one backend is
+//! healthy and the instance is not in maintenance mode, and 503 otherwise,
+//! so traffic can be steered away from an instance whose whole backend fleet
+//! is down or that an operator is quiescing. With `control_reset_stats`,
+//! `POST /reset-stats` zeroes the backend error/byte counters and the
+//! protection storm window.
+
+use anyhow::{Context, Result};
+use log::{debug, info};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::broadcast;
+use tokio::time::timeout;
+
+use crate::state::AppState;
+
+/// Upper bound on the request head we read before answering
+const MAX_REQUEST_BYTES: usize = 1024;
+/// How long a client gets to send its request line
+const REQUEST_READ_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Readiness verdict with the reason shown in the response body
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Readiness {
+    pub ready: bool,
+    pub reason: &'static str,
+}
+
+/// Decide whether this instance should receive traffic
+pub fn readiness(state: &AppState) -> Readiness {
+    if !state.is_listener_bound() {
+        return Readiness {
+            ready: false,
+            reason: "listener not bound",
+        };
+    }
+
+    if state.is_maintenance() {
+        return Readiness {
+            ready: false,
+            reason: "maintenance mode",
+        };
+    }
+
+    if state.backend_pool().healthy_count() == 0 {
+        return Readiness {
+            ready: false,
+            reason: "no healthy backends",
+        };
+    }
+
+    Readiness {
+        ready: true,
+        reason: "ready",
+    }
+}
+
+/// Serve the control endpoint until shutdown
+pub async fn run(
+    state: Arc<AppState>,
+    address: String,
+    mut shutdown: broadcast::Receiver<()>,
+) -> Result<()> {
+    let listener = TcpListener::bind(&address)
+        .await
+        .with_context(|| format!("Failed to bind control endpoint {}", address))?;
+    info!(
+        "Control endpoint listening on {} (/livez, /readyz)",
+        address
+    );
+    serve(listener, state, &mut shutdown).await;
+    Ok(())
+}
+
+async fn serve(
+    listener: TcpListener,
+    state: Arc<AppState>,
+    shutdown: &mut broadcast::Receiver<()>,
+) {
+    loop {
+        tokio::select! {
+            result = listener.accept() => {
+                if let Ok((stream, _)) = result {
+                    let state = Arc::clone(&state);
+                    tokio::spawn(async move {
+                        if let Err(e) = handle_request(stream, &state).await {
+                            debug!("Control request failed: {}", e);
+                        }
+                    });
+                }
+            }
+            _ = shutdown.recv() => {
+                debug!("Control endpoint received shutdown signal");
+                break;
+            }
+        }
+    }
+}
+
+async fn handle_request(mut stream: TcpStream, state: &AppState) -> Result<()> {
+    let mut head = Vec::with_capacity(256);
+    let mut buf = [0u8; 256];
+    timeout(REQUEST_READ_TIMEOUT, async {
+        while !head.windows(2).any(|w| w == b"\r\n") && head.len() < MAX_REQUEST_BYTES {
+            let n = stream.read(&mut buf).await?;
+            if n == 0 {
+                break;
+            }
+            head.extend_from_slice(&buf[..n]);
+        }
+        Ok::<_, std::io::Error>(())
+    })
+    .await
+    .context("Control request timed out")??;
+
+    let request_line = String::from_utf8_lossy(&head);
+    let mut parts = request_line.lines().next().unwrap_or("").split_whitespace();
+    let method = parts.next().unwrap_or("");
+    let path = parts.next().unwrap_or("");
+
+    let reset_stats_enabled = state.config().runtime_tuning.control_reset_stats;
+    let (status, body) = match path {
+        "/livez" => ("200 OK", "alive"),
+        "/reset-stats" if reset_stats_enabled && method == "POST" => {
+            state.reset_stats();
+            info!("Stats reset via control endpoint");
+            ("200 OK", "stats reset")
+        }
+        "/reset-stats" if reset_stats_enabled => ("405 Method Not Allowed", "use POST"),
+        "/readyz" => {
+            let verdict = readiness(state);
+            if verdict.ready {
+                ("200 OK", verdict.reason)
+            } else {
+                ("503 Service Unavailable", verdict.reason)
+            }
+        }
+        _ => ("404 Not Found", "not found"),
+    };
+
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}\n",
+        status,
+        body.len() + 1,
+        body
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{BackendConfig, RuntimeTuning};
+    use crate::state::RuntimeConfig;
+
+    fn state_with_backends(count: u16) -> Arc<AppState> {
+        state_with_tuning(count, RuntimeTuning::default())
+    }
+
+    fn state_with_tuning(count: u16, runtime_tuning: RuntimeTuning) -> Arc<AppState> {
+        let backends = (0..count).map(|i| BackendConfig::local(9000 + i)).collect();
+        AppState::for_test(RuntimeConfig::for_test(backends, runtime_tuning))
+    }
+
+    #[test]
+    fn ready_only_with_bound_listener_and_a_healthy_backend() {
+        let state = state_with_backends(2);
+        assert_eq!(readiness(&state).reason, "listener not bound");
+
+        state.mark_listener_bound();
+        assert!(readiness(&state).ready);
+
+        for backend in state.backend_pool().all_backends() {
+            backend.set_healthy(false);
+        }
+        let verdict = readiness(&state);
+        assert!(!verdict.ready);
+        assert_eq!(verdict.reason, "no healthy backends");
+
+        state.backend_pool().all_backends()[1].set_healthy(true);
+        assert!(readiness(&state).ready);
+
+        state.set_maintenance(true);
+        assert_eq!(readiness(&state).reason, "maintenance mode");
+    }
+
+    async fn get(addr: std::net::SocketAddr, path: &str) -> String {
+        request(addr, "GET", path).await
+    }
+
+    async fn request(addr: std::net::SocketAddr, method: &str, path: &str) -> String {
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(format!("{} {} HTTP/1.1\r\nHost: bal\r\n\r\n", method, path).as_bytes())
+            .await
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        response
+    }
+
+    #[tokio::test]
+    async fn http_endpoints_report_liveness_and_readiness() {
+        let state = state_with_backends(1);
+        state.mark_listener_bound();
+        state.backend_pool().all_backends()[0].set_healthy(false);
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let mut shutdown = state.subscribe_shutdown();
+        let server_state = Arc::clone(&state);
+        let server =
+            tokio::spawn(async move { serve(listener, server_state, &mut shutdown).await });
+
+        assert!(get(addr, "/livez").await.starts_with("HTTP/1.1 200"));
+        let not_ready = get(addr, "/readyz").await;
+        assert!(not_ready.starts_with("HTTP/1.1 503"));
+        assert!(not_ready.ends_with("no healthy backends\n"));
+
+        state.backend_pool().all_backends()[0].set_healthy(true);
+        assert!(get(addr, "/readyz").await.starts_with("HTTP/1.1 200"));
+        assert!(get(addr, "/metrics").await.starts_with("HTTP/1.1 404"));
+
+        // Resetting over the unauthenticated endpoint needs an explicit opt-in
+        state.backend_pool().all_backends()[0].record_bytes(512);
+        let refused = request(addr, "POST", "/reset-stats").await;
+        assert!(refused.starts_with("HTTP/1.1 404"));
+        assert_eq!(state.backend_pool().all_backends()[0].bytes_total(), 512);
+
+        state.trigger_shutdown();
+        server.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn reset_stats_route_works_once_enabled() {
+        let state = state_with_tuning(
+            1,
+            RuntimeTuning {
+                control_reset_stats: true,
+                ..RuntimeTuning::default()
+            },
+        );
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let mut shutdown = state.subscribe_shutdown();
+        let server_state = Arc::clone(&state);
+        let server =
+            tokio::spawn(async move { serve(listener, server_state, &mut shutdown).await });
+
+        state.backend_pool().all_backends()[0].record_bytes(512);
+        assert!(get(addr, "/reset-stats").await.starts_with("HTTP/1.1 405"));
+        assert_eq!(state.backend_pool().all_backends()[0].bytes_total(), 512);
+        let reset = request(addr, "POST", "/reset-stats").await;
+        assert!(reset.starts_with("HTTP/1.1 200"));
+        assert_eq!(state.backend_pool().all_backends()[0].bytes_total(), 0);
+
+        state.trigger_shutdown();
+        server.await.unwrap();
+    }
+}
@@ -0,0 +1,114 @@
+//! Resolver cache for the proxy connect path
+//!
+//! With `dns_cache_ttl_ms`, backend (and upstream proxy) hostnames are
+//! resolved once per TTL instead of on every client connection. Failed
+//! lookups are never cached, so a resolver hiccup is retried right away.
+
+use anyhow::Result;
+use std::collections::HashMap;
+use std::future::Future;
+use std::net::SocketAddr;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Resolved addresses keyed by `host:port`
+#[derive(Debug, Default)]
+pub struct DnsCache {
+    entries: Mutex<HashMap<String, CachedAddrs>>,
+}
+
+#[derive(Debug)]
+struct CachedAddrs {
+    addrs: Vec<SocketAddr>,
+    expires_at: Instant,
+}
+
+impl DnsCache {
+    /// Resolver using this cache for `ttl_ms`; None when caching is off (0)
+    pub fn with_ttl(&self, ttl_ms: u64) -> Option<CachedResolver<'_>> {
+        (ttl_ms > 0).then_some(CachedResolver {
+            cache: self,
+            ttl: Duration::from_millis(ttl_ms),
+        })
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, CachedAddrs>> {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// A `DnsCache` plus the TTL to apply, passed down the connect path
+#[derive(Debug, Clone, Copy)]
+pub struct CachedResolver<'a> {
+    cache: &'a DnsCache,
+    ttl: Duration,
+}
+
+impl CachedResolver<'_> {
+    /// Cached addresses for `key`, or the result of `lookup` once they expired
+    pub async fn resolve<F, Fut>(&self, key: &str, lookup: F) -> Result<Vec<SocketAddr>>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<Vec<SocketAddr>>>,
+    {
+        let now = Instant::now();
+        if let Some(entry) = self.cache.lock().get(key) {
+            if entry.expires_at > now {
+                return Ok(entry.addrs.clone());
+            }
+        }
+
+        let addrs = lookup().await?;
+        self.cache.lock().insert(
+            key.to_string(),
+            CachedAddrs {
+                addrs: addrs.clone(),
+                expires_at: Instant::now() + self.ttl,
+            },
+        );
+        Ok(addrs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[tokio::test]
+    async fn lookups_within_the_ttl_hit_the_cache_and_expired_ones_requery() {
+        let cache = DnsCache::default();
+        assert!(cache.with_ttl(0).is_none());
+        let resolver = cache.with_ttl(100).unwrap();
+
+        let queries = AtomicUsize::new(0);
+        let lookup = || async {
+            let n = queries.fetch_add(1, Ordering::Relaxed) as u8;
+            Ok(vec![SocketAddr::from(([10, 0, 0, n + 1], 9000))])
+        };
+
+        let first = resolver.resolve("app.internal:9000", lookup).await.unwrap();
+        let second = resolver.resolve("app.internal:9000", lookup).await.unwrap();
+        assert_eq!(queries.load(Ordering::Relaxed), 1);
+        assert_eq!(first, second);
+
+        // A different host:port is its own entry
+        resolver.resolve("db.internal:5432", lookup).await.unwrap();
+        assert_eq!(queries.load(Ordering::Relaxed), 2);
+
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        let refreshed = resolver.resolve("app.internal:9000", lookup).await.unwrap();
+        assert_eq!(queries.load(Ordering::Relaxed), 3);
+        assert_ne!(refreshed, first);
+
+        // Failures aren't cached: the next call queries again
+        let failing = || async { Err(anyhow::anyhow!("resolver down")) };
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        assert!(resolver
+            .resolve("app.internal:9000", failing)
+            .await
+            .is_err());
+        resolver.resolve("app.internal:9000", lookup).await.unwrap();
+        assert_eq!(queries.load(Ordering::Relaxed), 4);
+    }
+}
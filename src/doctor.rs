@@ -1,15 +1,21 @@
-use anyhow::{bail, Result};
-use serde::Serialize;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
 use std::net::{SocketAddr, TcpListener, ToSocketAddrs};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use crate::config::Config;
+use crate::check::{check_backend_connectivity, BackendCheck, Connectivity, ExitStatus};
+use crate::config::{unix_listen_path, Config};
 use crate::constants::get_pid_file_path;
 use crate::operator_message::render_operator_message;
+use crate::output::OutputFormat;
 use crate::process::{ProcessManager, ProtectionModeSummary};
 use crate::protection;
+use crate::proxy::unix_listen_problem;
 
-#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+/// Summary prefix of the `config` check when the file is missing
+const CONFIG_NOT_FOUND: &str = "config file not found";
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum CheckLevel {
     Ok,
@@ -17,7 +23,7 @@ pub enum CheckLevel {
     Critical,
 }
 
-#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct DoctorCheck {
     pub name: String,
     pub level: CheckLevel,
@@ -25,13 +31,28 @@ pub struct DoctorCheck {
     pub hint: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DoctorReport {
     pub checks: Vec<DoctorCheck>,
     pub protection_mode: ProtectionModeSummary,
 }
 
 impl DoctorReport {
+    /// Exit status for `bal doctor`: missing config (3), critical findings (1), else 0
+    pub fn exit_status(&self) -> ExitStatus {
+        if self
+            .checks
+            .iter()
+            .any(|check| check.name == "config" && check.summary.starts_with(CONFIG_NOT_FOUND))
+        {
+            ExitStatus::ConfigNotFound
+        } else if self.has_critical_failure() {
+            ExitStatus::Errors
+        } else {
+            ExitStatus::Ok
+        }
+    }
+
     pub fn has_critical_failure(&self) -> bool {
         self.checks
             .iter()
@@ -123,8 +144,17 @@ impl CheckLevel {
 }
 
 pub async fn run_doctor(config_path: Option<PathBuf>) -> DoctorReport {
+    run_doctor_with(config_path, None).await
+}
+
+/// Same as `run_doctor`, but reuses backend `probes` a caller already ran
+/// against this config instead of connecting to every backend again
+pub async fn run_doctor_with(
+    config_path: Option<PathBuf>,
+    probes: Option<Vec<BackendCheck>>,
+) -> DoctorReport {
     let mut checks = Vec::new();
-    let protection_mode = current_protection_mode();
+    let protection_mode = current_protection_mode(ProcessManager::is_daemon_running());
 
     checks.push(check_pid_consistency());
 
@@ -148,7 +178,7 @@ pub async fn run_doctor(config_path: Option<PathBuf>) -> DoctorReport {
         checks.push(DoctorCheck {
             name: "config".to_string(),
             level: CheckLevel::Critical,
-            summary: format!("config file not found: {}", resolved_config.display()),
+            summary: format!("{}: {}", CONFIG_NOT_FOUND, resolved_config.display()),
             hint: Some(format!(
                 "Create a config file at {} or pass '--config <FILE>'",
                 resolved_config.display()
@@ -185,7 +215,13 @@ pub async fn run_doctor(config_path: Option<PathBuf>) -> DoctorReport {
     };
 
     checks.push(check_bindability(&config));
-    checks.push(check_backends(&config).await);
+    let probes = match probes {
+        Some(probes) => probes,
+        // Same bounded, concurrent probe as `bal check`, so a few timing-out
+        // backends don't add up
+        None => check_backend_connectivity(&config).await,
+    };
+    checks.push(check_backends(&config, probes));
 
     DoctorReport {
         checks,
@@ -193,20 +229,25 @@ pub async fn run_doctor(config_path: Option<PathBuf>) -> DoctorReport {
     }
 }
 
-pub async fn run_and_print(config_path: Option<PathBuf>, json: bool, verbose: bool) -> Result<()> {
+/// Print the doctor report and return the exit status `main` should use
+pub async fn run_and_print(
+    config_path: Option<PathBuf>,
+    format: OutputFormat,
+    verbose: bool,
+) -> Result<ExitStatus> {
     let report = run_doctor(config_path).await;
 
-    if json {
-        println!("{}", serde_json::to_string_pretty(&report)?);
-    } else {
-        println!("{}", report.to_plain_text(verbose));
+    match format.serialize(&report)? {
+        Some(rendered) => println!("{}", rendered),
+        None => println!("{}", report.to_plain_text(verbose)),
     }
 
-    if report.has_critical_failure() {
-        bail!("doctor found critical issues")
+    let status = report.exit_status();
+    if status != ExitStatus::Ok {
+        eprintln!("doctor found critical issues");
     }
 
-    Ok(())
+    Ok(status)
 }
 
 fn check_pid_consistency() -> DoctorCheck {
@@ -252,6 +293,29 @@ fn check_pid_consistency() -> DoctorCheck {
 }
 
 fn check_bindability(config: &Config) -> DoctorCheck {
+    if let Some(path) = unix_listen_path(&config.bind_address) {
+        return match unix_listen_problem(Path::new(path)) {
+            None => DoctorCheck {
+                name: "bind".to_string(),
+                level: CheckLevel::Ok,
+                summary: format!("unix:{} is bindable", path),
+                hint: None,
+            },
+            Some(problem) if ProcessManager::is_daemon_running() => DoctorCheck {
+                name: "bind".to_string(),
+                level: CheckLevel::Warn,
+                summary: problem,
+                hint: Some("Run 'bal status' to confirm it is the expected daemon".to_string()),
+            },
+            Some(problem) => DoctorCheck {
+                name: "bind".to_string(),
+                level: CheckLevel::Critical,
+                summary: problem,
+                hint: Some("Fix the socket path in bind_address or stop its owner".to_string()),
+            },
+        };
+    }
+
     let bind_target = format!("{}:{}", config.bind_address, config.port);
 
     let socket_addr = match resolve_bind_target(&bind_target) {
@@ -284,6 +348,19 @@ fn check_bindability(config: &Config) -> DoctorCheck {
                     summary: format!("{} is already in use by an active process", bind_target),
                     hint: Some("Run 'bal status' to confirm it is the expected daemon".to_string()),
                 }
+            } else if let Some(owner) = find_port_owner(config.port) {
+                DoctorCheck {
+                    name: "bind".to_string(),
+                    level: CheckLevel::Critical,
+                    summary: format!(
+                        "{} is already in use by PID {} ({})",
+                        bind_target, owner.pid, owner.command
+                    ),
+                    hint: Some(format!(
+                        "Stop '{}' (PID {}) or update bind_address/port in config",
+                        owner.command, owner.pid
+                    )),
+                }
             } else {
                 DoctorCheck {
                     name: "bind".to_string(),
@@ -305,29 +382,28 @@ fn check_bindability(config: &Config) -> DoctorCheck {
     }
 }
 
-async fn check_backends(config: &Config) -> DoctorCheck {
+fn check_backends(config: &Config, probes: Vec<BackendCheck>) -> DoctorCheck {
     let mut resolved_count = 0usize;
     let mut reachable_count = 0usize;
     let mut unresolved = Vec::new();
     let mut unreachable = Vec::new();
 
-    for backend in &config.backends {
-        let backend_addr = format!("{}:{}", backend.host, backend.port);
-
-        match backend.resolve_socket_addr().await {
-            Ok(_) => {
+    for probe in probes {
+        match probe.connectivity {
+            Connectivity::Reachable => {
                 resolved_count += 1;
-                if backend.check_connectivity().await.is_ok() {
-                    reachable_count += 1;
-                } else {
-                    unreachable.push(backend_addr);
-                }
+                reachable_count += 1;
             }
-            Err(_) => unresolved.push(backend_addr),
+            Connectivity::Unreachable => {
+                resolved_count += 1;
+                unreachable.push(probe.address);
+            }
+            Connectivity::Unresolved => unresolved.push(probe.address),
+            Connectivity::Disabled => {}
         }
     }
 
-    let total = config.backends.len();
+    let total = config.enabled_backends().count();
 
     if total == 0 {
         return DoctorCheck {
@@ -382,8 +458,91 @@ async fn check_backends(config: &Config) -> DoctorCheck {
     }
 }
 
-fn current_protection_mode() -> ProtectionModeSummary {
-    if let Some(snapshot) = protection::read_snapshot() {
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PortOwner {
+    pid: i32,
+    command: String,
+}
+
+/// Find the process listening on `port` via /proc (Linux only).
+///
+/// Returns None when the owner is not visible, e.g. it belongs to another
+/// user whose /proc/<pid>/fd we cannot read.
+#[cfg(target_os = "linux")]
+fn find_port_owner(port: u16) -> Option<PortOwner> {
+    let mut inodes = Vec::new();
+    for table in ["/proc/net/tcp", "/proc/net/tcp6"] {
+        if let Ok(content) = std::fs::read_to_string(table) {
+            inodes.extend(parse_listen_inodes(&content, port));
+        }
+    }
+    if inodes.is_empty() {
+        return None;
+    }
+
+    for entry in std::fs::read_dir("/proc").ok()?.flatten() {
+        let pid: i32 = match entry.file_name().to_str().and_then(|n| n.parse().ok()) {
+            Some(pid) => pid,
+            None => continue,
+        };
+        let fds = match std::fs::read_dir(entry.path().join("fd")) {
+            Ok(fds) => fds,
+            Err(_) => continue,
+        };
+
+        for fd in fds.flatten() {
+            let link = match std::fs::read_link(fd.path()) {
+                Ok(link) => link,
+                Err(_) => continue,
+            };
+            if parse_socket_inode(&link.to_string_lossy()).is_some_and(|i| inodes.contains(&i)) {
+                let command = std::fs::read_to_string(entry.path().join("comm"))
+                    .map(|c| c.trim().to_string())
+                    .unwrap_or_else(|_| "unknown".to_string());
+                return Some(PortOwner { pid, command });
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(not(target_os = "linux"))]
+fn find_port_owner(_port: u16) -> Option<PortOwner> {
+    None
+}
+
+/// Extract socket inodes of LISTEN entries bound to `port` from /proc/net/tcp{,6} content.
+fn parse_listen_inodes(content: &str, port: u16) -> Vec<u64> {
+    const TCP_LISTEN: &str = "0A";
+
+    content
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 10 || fields[3] != TCP_LISTEN {
+                return None;
+            }
+            let local_port = fields[1].rsplit(':').next()?;
+            if u16::from_str_radix(local_port, 16).ok()? != port {
+                return None;
+            }
+            fields[9].parse().ok()
+        })
+        .collect()
+}
+
+/// Parse the inode from an fd link target like `socket:[12345]`.
+fn parse_socket_inode(link: &str) -> Option<u64> {
+    link.strip_prefix("socket:[")?
+        .strip_suffix(']')?
+        .parse()
+        .ok()
+}
+
+fn current_protection_mode(daemon_running: bool) -> ProtectionModeSummary {
+    if let Some(snapshot) = protection::read_current_snapshot(daemon_running) {
         return ProtectionModeSummary {
             enabled: snapshot.enabled,
             reason: snapshot.reason,
@@ -396,7 +555,7 @@ fn current_protection_mode() -> ProtectionModeSummary {
     }
 }
 
-fn resolve_bind_target(bind_target: &str) -> std::result::Result<SocketAddr, String> {
+pub(crate) fn resolve_bind_target(bind_target: &str) -> std::result::Result<SocketAddr, String> {
     bind_target
         .to_socket_addrs()
         .map_err(|err| format!("cannot resolve {}: {}", bind_target, err))?
@@ -407,6 +566,7 @@ fn resolve_bind_target(bind_target: &str) -> std::result::Result<SocketAddr, Str
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::BackendConfig;
 
     #[test]
     fn doctor_report_marks_critical_failure_when_any_critical_exists() {
@@ -432,6 +592,48 @@ mod tests {
         };
 
         assert!(report.has_critical_failure());
+        assert_eq!(report.exit_status(), ExitStatus::Errors);
+    }
+
+    #[tokio::test]
+    async fn doctor_exit_status_reports_missing_config() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let report = run_doctor(Some(dir.path().join("missing.yaml"))).await;
+        assert_eq!(report.exit_status(), ExitStatus::ConfigNotFound);
+    }
+
+    #[tokio::test]
+    async fn supplied_probes_are_used_instead_of_connecting_again() {
+        // Nothing listens here, so a fresh probe would report it unreachable
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("bal.yaml");
+        std::fs::write(
+            &path,
+            format!(
+                "port: 9295\nbackends:\n  - host: \"127.0.0.1\"\n    port: {}\n",
+                port
+            ),
+        )
+        .unwrap();
+        let probes = vec![BackendCheck {
+            address: format!("127.0.0.1:{}", port),
+            connectivity: Connectivity::Reachable,
+            error: None,
+        }];
+
+        let report = run_doctor_with(Some(path), Some(probes)).await;
+        let backend = report
+            .checks
+            .iter()
+            .find(|check| check.name == "backend")
+            .expect("backend check should run");
+        assert_eq!(backend.level, CheckLevel::Ok);
+        assert_eq!(backend.summary, "resolvable 1/1 | reachable 1/1");
     }
 
     #[test]
@@ -504,4 +706,67 @@ mod tests {
         let result = resolve_bind_target("invalid host name:9295");
         assert!(result.is_err());
     }
+
+    #[test]
+    fn parse_listen_inodes_matches_port_and_listen_state() {
+        let content = "\
+  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 0100007F:244F 00000000:0000 0A 00000000:00000000 00:00000000 00000000  1000        0 43121 1 0000000000000000 100 0 0 10 0
+   1: 0100007F:244F 0100007F:D2C4 01 00000000:00000000 00:00000000 00000000  1000        0 43999 1 0000000000000000 20 4 30 10 -1
+   2: 00000000:0016 00000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 18822 1 0000000000000000 100 0 0 10 0
+";
+
+        assert_eq!(parse_listen_inodes(content, 9295), vec![43121]);
+        assert_eq!(parse_listen_inodes(content, 22), vec![18822]);
+        assert!(parse_listen_inodes(content, 8080).is_empty());
+    }
+
+    #[test]
+    fn parse_socket_inode_reads_fd_link_target() {
+        assert_eq!(parse_socket_inode("socket:[43121]"), Some(43121));
+        assert_eq!(parse_socket_inode("pipe:[43121]"), None);
+        assert_eq!(parse_socket_inode("/dev/null"), None);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn find_port_owner_identifies_current_process() {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind listener");
+        let port = listener.local_addr().unwrap().port();
+
+        let owner = find_port_owner(port).expect("own listener should be visible");
+        assert_eq!(owner.pid, std::process::id() as i32);
+    }
+
+    #[tokio::test]
+    async fn backend_probes_run_concurrently() {
+        // A listener with a full accept queue: further connects hang until
+        // check_connectivity's 1s timeout, so serial probing would take ~6s
+        let socket = tokio::net::TcpSocket::new_v4().expect("socket");
+        socket
+            .bind("127.0.0.1:0".parse().unwrap())
+            .expect("bind listener");
+        let listener = socket.listen(0).expect("listen");
+        let addr = listener.local_addr().unwrap();
+        let _queued: Vec<_> = (0..3)
+            .filter_map(|_| {
+                std::net::TcpStream::connect_timeout(&addr, std::time::Duration::from_millis(200))
+                    .ok()
+            })
+            .collect();
+
+        let mut config = Config::new();
+        config.backends = (0..6).map(|_| BackendConfig::local(addr.port())).collect();
+
+        let started = std::time::Instant::now();
+        let check = check_backends(&config, check_backend_connectivity(&config).await);
+        let elapsed = started.elapsed();
+
+        assert_eq!(check.summary, "resolvable 6/6 | reachable 0/6");
+        assert!(
+            elapsed < std::time::Duration::from_secs(3),
+            "probes took {:?}",
+            elapsed
+        );
+    }
 }
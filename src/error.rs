@@ -10,6 +10,7 @@ use std::io;
 /// Each error clearly expresses the context where it occurred (config, network,
 /// process, etc.) to reduce problem resolution time.
 #[derive(Debug)]
+#[allow(dead_code)]
 pub enum BalError {
     /// Configuration file related errors
     Config(String),
@@ -21,6 +22,10 @@ pub enum BalError {
     Backend(String),
     /// Health check related errors
     HealthCheck(String),
+    /// The configuration file does not exist
+    ConfigNotFound(String),
+    /// A backend host resolved successfully but to zero addresses
+    DnsNoAddresses(String),
 }
 
 impl std::fmt::Display for BalError {
@@ -31,6 +36,16 @@ impl std::fmt::Display for BalError {
             BalError::Process(msg) => write!(f, "Process control error: {}", msg),
             BalError::Backend(msg) => write!(f, "Backend connection failed: {}", msg),
             BalError::HealthCheck(msg) => write!(f, "Health check failed: {}", msg),
+            BalError::ConfigNotFound(path) => {
+                write!(f, "Configuration file not found: {}", path)
+            }
+            BalError::DnsNoAddresses(host_port) => {
+                write!(
+                    f,
+                    "dns_no_addresses: {} resolved to no addresses",
+                    host_port
+                )
+            }
         }
     }
 }
@@ -40,12 +55,15 @@ impl std::error::Error for BalError {}
 /// Helper trait for adding context to anyhow::Error
 pub trait ResultExt<T> {
     /// Add configuration error context
+    #[allow(dead_code)]
     fn context_config(self, msg: &str) -> anyhow::Result<T>;
     /// Add network error context
+    #[allow(dead_code)]
     fn context_network(self, msg: &str) -> anyhow::Result<T>;
     /// Add process error context
     fn context_process(self, msg: &str) -> anyhow::Result<T>;
     /// Add backend error context
+    #[allow(dead_code)]
     fn context_backend(self, msg: &str) -> anyhow::Result<T>;
 }
 
@@ -88,6 +106,7 @@ impl<T> ResultExt<T> for io::Result<T> {
 /// Generate user-friendly error message
 ///
 /// Converts internal errors into messages users can understand and act upon.
+#[allow(dead_code)]
 pub fn format_user_error(error: &anyhow::Error) -> String {
     let error_str = error.to_string();
 
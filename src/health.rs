@@ -3,36 +3,64 @@
 //! Periodically monitors backend server status.
 //! Determines backend status based on TCP connectivity, transitioning
 //! state based on consecutive failures/successes.
+//!
+//! Backends may override `health_check_interval_ms`. The checker ticks at the
+//! shortest interval in use and each tick probes only the backends that are
+//! due, so an override that is not a multiple of the tick is rounded up to one.
 
 use anyhow::Result;
 use log::{debug, error, info};
+use std::collections::HashMap;
+use std::future::Future;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio::net::TcpStream;
-use tokio::time::{interval, timeout};
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+use tokio::time::{interval, interval_at, timeout};
+
+use crate::backend_pool::BackendState;
+use crate::config::{is_dns_no_addresses, RuntimeTuning};
+use crate::proxy::{BackendTarget, ConnectOptions, ProxyStream};
+use crate::runtime_stats::{self, RuntimeSnapshot};
+use crate::state::{AppState, RuntimeConfig};
 
-use crate::state::AppState;
+/// Upper bound on response bytes read while looking for `health_check_expect`
+const MAX_PROBE_RESPONSE_BYTES: usize = 4096;
 
 /// Health check manager
 ///
 /// Periodically checks all backend statuses and updates state.
 pub struct HealthChecker {
     state: Arc<AppState>,
+    /// When the checker was created; `startup_warmup_ms` counts from here
+    started: Instant,
 }
 
 impl HealthChecker {
     /// Create new health checker
     pub fn new(state: Arc<AppState>) -> Self {
-        Self { state }
+        Self {
+            state,
+            started: Instant::now(),
+        }
+    }
+
+    /// Whether probe failures are still being forgiven after startup
+    fn in_startup_warmup(&self, warmup_ms: u64) -> bool {
+        self.started.elapsed() < Duration::from_millis(warmup_ms)
     }
 
     /// Run health check loop
     ///
-    /// Periodically checks all backends, logging state changes.
+    /// Periodically checks the backends that are due, logging state changes.
     /// Exits loop on shutdown signal.
     pub async fn run(&self, mut shutdown: tokio::sync::broadcast::Receiver<()>) -> Result<()> {
         let runtime = self.state.config().runtime_tuning.clone();
-        let mut ticker = interval(Duration::from_millis(runtime.health_check_interval_ms));
+        let mut tick = scheduler_tick(&self.state.config());
+        let mut ticker = interval(tick);
+        let mut next_due = HashMap::new();
 
         info!(
             "Health check started: {}ms interval, {}ms timeout",
@@ -44,10 +72,19 @@ impl HealthChecker {
 
         loop {
             tokio::select! {
-                _ = ticker.tick() => {
-                    if let Err(e) = self.check_all_backends().await {
+                now = ticker.tick() => {
+                    if let Err(e) = self.check_due_backends(now.into_std(), &mut next_due).await {
                         error!("Health check error: {}", e);
                     }
+                    self.state.enforce_drain_deadline();
+                    runtime_stats::write_snapshot(&RuntimeSnapshot::capture(&self.state).await);
+
+                    // A reload may have added, removed or changed interval overrides
+                    let wanted = scheduler_tick(&self.state.config());
+                    if wanted != tick {
+                        tick = wanted;
+                        ticker = interval_at(now + tick, tick);
+                    }
                 }
                 _ = shutdown.recv() => {
                     info!("Health check received shutdown signal");
@@ -60,75 +97,143 @@ impl HealthChecker {
         Ok(())
     }
 
-    /// Check all backends
-    async fn check_all_backends(&self) -> Result<()> {
+    /// Check the backends whose interval has elapsed at `now`
+    ///
+    /// `next_due` maps backend address to its next probe time; backends not in
+    /// it yet are due immediately, and entries for removed backends are dropped.
+    async fn check_due_backends(
+        &self,
+        now: Instant,
+        next_due: &mut HashMap<String, Instant>,
+    ) -> Result<()> {
         let config = self.state.config();
+        let default_ms = config.runtime_tuning.health_check_interval_ms;
+        let backends = config.backend_pool.all_backends();
+
+        next_due.retain(|address, _| backends.iter().any(|b| b.address() == *address));
+        let due: Vec<_> = backends
+            .iter()
+            .filter(|backend| {
+                let address = backend.address();
+                if next_due.get(&address).is_some_and(|due| now < *due) {
+                    return false;
+                }
+                next_due.insert(
+                    address,
+                    now + backend.config.health_check_interval(default_ms),
+                );
+                true
+            })
+            .map(Arc::clone)
+            .collect();
+
+        self.check_backends(&config, due).await
+    }
+
+    /// Probe `backends`, then refresh pool-wide saturation and status logging
+    async fn check_backends(
+        &self,
+        config: &RuntimeConfig,
+        backends: Vec<Arc<BackendState>>,
+    ) -> Result<()> {
         let pool = &config.backend_pool;
         let runtime = config.runtime_tuning.clone();
+        let warmup = self.in_startup_warmup(runtime.startup_warmup_ms);
 
-        // Check each backend in parallel
-        let mut handles = vec![];
-
-        for backend in pool.all_backends() {
-            let backend = Arc::clone(backend);
-            let runtime = runtime.clone();
-            let handle = tokio::spawn(async move {
-                let addr = match backend.config.to_health_check_addr().await {
-                    Ok(a) => a,
+        // Check backends in parallel, at most health_check_concurrency at a time
+        let probe_runtime = runtime.clone();
+        let exchange = Arc::new(ProbeExchange::from_runtime(&runtime));
+        for_each_bounded(backends, runtime.health_check_concurrency, move |backend| {
+            let runtime = probe_runtime.clone();
+            let upstream = runtime.upstream_proxy();
+            let exchange = Arc::clone(&exchange);
+            async move {
+                let target = match BackendTarget::resolve(&backend.config, upstream.as_ref(), None)
+                    .await
+                {
+                    Ok(target) => target,
                     Err(e) => {
-                        error!("Backend address conversion failed: {}", e);
+                        record_resolve_failure(&backend, &e, runtime.health_check_fail_threshold);
                         return;
                     }
                 };
 
-                debug!(
-                    "Health check: {}:{}",
-                    backend.config.host, backend.config.port
-                );
+                debug!("Health check: {}", backend.address());
 
-                // TCP connection test
-                let result = timeout(
-                    Duration::from_millis(runtime.health_check_timeout_ms),
-                    TcpStream::connect(&addr),
+                let result = probe(
+                    &target,
+                    backend
+                        .config
+                        .connect_timeout(runtime.health_check_timeout_ms),
+                    &exchange,
                 )
                 .await;
 
                 backend.mark_checked_now();
 
                 match result {
-                    Ok(Ok(_)) => {
-                        // Connection success
+                    Ok(latency) => {
+                        backend.record_latency(latency);
                         backend.mark_success(runtime.health_check_success_threshold);
                     }
-                    Ok(Err(e)) => {
-                        // Connection failure
-                        debug!(
-                            "Backend {}:{} connection failed: {}",
-                            backend.config.host, backend.config.port, e
-                        );
-                        backend.mark_failure(runtime.health_check_fail_threshold);
-                    }
-                    Err(_) => {
-                        // Timeout
-                        debug!(
-                            "Backend {}:{} timeout",
-                            backend.config.host, backend.config.port
-                        );
-                        backend.mark_failure(runtime.health_check_fail_threshold);
+                    Err(reason) => {
+                        debug!("Backend {} {}", backend.address(), reason);
+                        backend.record_error(format!("Health check {}", reason));
+                        if warmup {
+                            debug!(
+                                "Backend {} failure not counted (startup_warmup_ms)",
+                                backend.address()
+                            );
+                        } else {
+                            backend.mark_failure(runtime.health_check_fail_threshold);
+                        }
                     }
                 }
-            });
+            }
+        })
+        .await;
 
-            handles.push(handle);
+        if let Some(max_connections) = runtime.backend_max_connections {
+            let now_ms = BackendState::now_unix_ms();
+            for backend in pool.all_backends() {
+                let was_degraded = backend.is_degraded();
+                backend.update_saturation(max_connections, runtime.degrade_after_ms, now_ms);
+                if backend.is_degraded() != was_degraded {
+                    info!(
+                        "Backend {} {} (active={}, backend_max_connections={})",
+                        backend.address(),
+                        if was_degraded {
+                            "no longer degraded"
+                        } else {
+                            "degraded by sustained saturation"
+                        },
+                        backend.active_connections(),
+                        max_connections
+                    );
+                }
+            }
         }
 
-        // Wait for all checks to complete
-        for handle in handles {
-            if let Err(e) = handle.await {
-                error!("Health check task error: {}", e);
+        // Close stale keepalive spares (all of them once the option is off)
+        let keepalive_idle = if runtime.backend_keepalive {
+            Duration::from_millis(runtime.backend_keepalive_idle_ms)
+        } else {
+            Duration::ZERO
+        };
+        for backend in pool.all_backends() {
+            let evicted = backend.keepalive().evict_idle(keepalive_idle).await;
+            if evicted > 0 {
+                debug!(
+                    "Closed {} idle keepalive connection(s) to {}",
+                    evicted,
+                    backend.address()
+                );
             }
         }
 
+        // Recoveries are noticed here even when no client is connecting
+        pool.record_availability(pool.healthy_count() > 0);
+
         // Log overall status periodically
         pool.log_status();
 
@@ -136,6 +241,7 @@ impl HealthChecker {
     }
 
     /// Single backend health check (for external use)
+    #[allow(dead_code)]
     pub async fn check_single_backend(host: &str, port: u16) -> Result<bool> {
         let addr = format!("{}:{}", host, port);
 
@@ -145,3 +251,463 @@ impl HealthChecker {
         }
     }
 }
+
+/// Handle a backend whose address could not be resolved for a probe
+///
+/// A host that resolves to zero addresses would otherwise never be probed
+/// nor flagged, so it counts as a failed check with a `dns_no_addresses` error.
+fn record_resolve_failure(backend: &BackendState, err: &anyhow::Error, fail_threshold: u32) {
+    if !is_dns_no_addresses(err) {
+        error!("Backend address conversion failed: {}", err);
+        return;
+    }
+    backend.mark_checked_now();
+    debug!("Backend {} {}", backend.address(), err);
+    backend.record_error(err.to_string());
+    backend.mark_failure(fail_threshold);
+}
+
+/// Tick period for the checker: the shortest interval any backend uses
+fn scheduler_tick(config: &RuntimeConfig) -> Duration {
+    let default_ms = config.runtime_tuning.health_check_interval_ms;
+    config
+        .backend_pool
+        .all_backends()
+        .iter()
+        .map(|backend| backend.config.health_check_interval(default_ms))
+        .min()
+        .unwrap_or(Duration::from_millis(default_ms))
+        .min(Duration::from_millis(default_ms))
+}
+
+/// Optional request/response check run once the TCP connect succeeds
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProbeExchange {
+    /// Written to the backend right after connecting
+    pub send: Option<Vec<u8>>,
+    /// Must appear in what the backend sends back (a greeting when nothing is sent)
+    pub expect: Option<Vec<u8>>,
+}
+
+impl ProbeExchange {
+    pub fn from_runtime(runtime: &RuntimeTuning) -> Self {
+        Self {
+            send: runtime.health_check_send_bytes(),
+            expect: runtime.health_check_expect_bytes(),
+        }
+    }
+}
+
+/// Probe one backend, returning the connect latency or why it failed
+///
+/// The connect and the optional send/expect exchange share `probe_timeout`,
+/// so a port that accepts connections but is not the expected service (or
+/// never answers) is marked as a failure.
+async fn probe(
+    target: &BackendTarget,
+    probe_timeout: Duration,
+    exchange: &ProbeExchange,
+) -> std::result::Result<Duration, String> {
+    let started = Instant::now();
+    // Never Fast Open: a deferred handshake would "connect" to a dead backend
+    let mut stream = match timeout(probe_timeout, target.connect(ConnectOptions::default())).await {
+        Ok(Ok((stream, _))) => stream,
+        Ok(Err(e)) => return Err(format!("connection failed: {}", e)),
+        Err(_) => return Err("timeout".to_string()),
+    };
+    let latency = started.elapsed();
+
+    if exchange.send.is_none() && exchange.expect.is_none() {
+        return Ok(latency);
+    }
+
+    let remaining = probe_timeout.saturating_sub(latency);
+    let outcome = timeout(remaining, async {
+        if let Some(payload) = &exchange.send {
+            stream
+                .write_all(payload)
+                .await
+                .map_err(|e| format!("probe send failed: {}", e))?;
+        }
+        if let Some(expected) = &exchange.expect {
+            match read_until_contains(&mut stream, expected).await {
+                Ok(true) => {}
+                Ok(false) => {
+                    return Err(format!(
+                        "response did not contain {:?}",
+                        String::from_utf8_lossy(expected)
+                    ))
+                }
+                Err(e) => return Err(format!("probe read failed: {}", e)),
+            }
+        }
+        Ok(())
+    })
+    .await;
+
+    match outcome {
+        Ok(Ok(())) => Ok(latency),
+        Ok(Err(reason)) => Err(reason),
+        Err(_) => Err("timed out waiting for probe response".to_string()),
+    }
+}
+
+/// Read until `needle` shows up, the peer closes, or MAX_PROBE_RESPONSE_BYTES were read
+async fn read_until_contains(stream: &mut ProxyStream, needle: &[u8]) -> std::io::Result<bool> {
+    let mut received = Vec::new();
+    let mut buf = [0u8; 512];
+    while received.len() < MAX_PROBE_RESPONSE_BYTES {
+        let n = stream.read(&mut buf).await?;
+        if n == 0 {
+            break;
+        }
+        received.extend_from_slice(&buf[..n]);
+        if received.windows(needle.len().max(1)).any(|w| w == needle) {
+            return Ok(true);
+        }
+    }
+    Ok(needle.is_empty())
+}
+
+/// Run `probe` for every item with at most `limit` probes in flight
+///
+/// A permit is taken before each task is spawned, so a large pool is probed
+/// in waves instead of spawning every task (and opening every socket) at once.
+/// Results come back in input order; a probe task that panicked is logged and
+/// left out.
+pub(crate) async fn for_each_bounded<T, R, F, Fut>(items: Vec<T>, limit: usize, probe: F) -> Vec<R>
+where
+    F: Fn(T) -> Fut,
+    Fut: Future<Output = R> + Send + 'static,
+    R: Send + 'static,
+{
+    let limiter = Arc::new(Semaphore::new(limit.max(1)));
+    let mut tasks = JoinSet::new();
+
+    for (index, item) in items.into_iter().enumerate() {
+        let permit = Arc::clone(&limiter)
+            .acquire_owned()
+            .await
+            .expect("health check limiter is never closed");
+        let probe = probe(item);
+        tasks.spawn(async move {
+            let result = probe.await;
+            drop(permit);
+            (index, result)
+        });
+    }
+
+    // Wait for all checks to complete
+    let mut results = Vec::with_capacity(tasks.len());
+    while let Some(joined) = tasks.join_next().await {
+        match joined {
+            Ok(result) => results.push(result),
+            Err(e) => error!("Health check task error: {}", e),
+        }
+    }
+    results.sort_by_key(|(index, _)| *index);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{BackendConfig, RuntimeTuning};
+    use crate::state::RuntimeConfig;
+    use std::net::SocketAddr;
+
+    #[test]
+    fn backend_resolving_to_no_addresses_is_marked_unhealthy() {
+        let backend = BackendState::new(BackendConfig {
+            host: "empty.invalid".to_string(),
+            ..BackendConfig::local(9000)
+        });
+        let err = anyhow::Error::from(crate::error::BalError::DnsNoAddresses(
+            "empty.invalid:9000".to_string(),
+        ));
+
+        record_resolve_failure(&backend, &err, 2);
+        assert!(backend.is_healthy());
+        record_resolve_failure(&backend, &err, 2);
+        assert!(!backend.is_healthy());
+        let last_error = backend.last_error().expect("reason should be recorded");
+        assert!(last_error.message.starts_with("dns_no_addresses"));
+
+        // Plain resolver errors keep the old log-only handling
+        let other = BackendState::new(backend.config.clone());
+        record_resolve_failure(&other, &anyhow::anyhow!("lookup failed"), 1);
+        assert!(other.is_healthy());
+    }
+
+    #[tokio::test]
+    async fn failures_during_startup_warmup_are_not_counted() {
+        // Bound then dropped, so the port refuses connections
+        let refusing = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = refusing.local_addr().unwrap().port();
+        drop(refusing);
+
+        let state = AppState::for_test(RuntimeConfig::for_test(
+            vec![BackendConfig::local(port)],
+            RuntimeTuning {
+                health_check_fail_threshold: 1,
+                startup_warmup_ms: 60_000,
+                ..RuntimeTuning::default()
+            },
+        ));
+        let backend = Arc::clone(&state.backend_pool().all_backends()[0]);
+
+        let mut checker = HealthChecker::new(Arc::clone(&state));
+        checker
+            .check_backends(&state.config(), vec![Arc::clone(&backend)])
+            .await
+            .unwrap();
+        assert!(backend.is_healthy(), "failure during warmup must not count");
+        assert!(backend.last_error().is_some());
+
+        // Once the warmup has elapsed, the same failure marks it unhealthy
+        checker.started = Instant::now() - Duration::from_secs(61);
+        checker
+            .check_backends(&state.config(), vec![Arc::clone(&backend)])
+            .await
+            .unwrap();
+        assert!(!backend.is_healthy());
+    }
+
+    #[tokio::test]
+    async fn disabled_backend_is_not_probed() {
+        let enabled = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let disabled = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        disabled.set_nonblocking(true).unwrap();
+
+        let backends = [(&enabled, true), (&disabled, false)]
+            .into_iter()
+            .map(|(listener, enabled)| BackendConfig {
+                enabled,
+                ..BackendConfig::local(listener.local_addr().unwrap().port())
+            })
+            .collect();
+        let state = AppState::for_test(RuntimeConfig::for_test(backends, RuntimeTuning::default()));
+
+        HealthChecker::new(Arc::clone(&state))
+            .check_due_backends(Instant::now(), &mut HashMap::new())
+            .await
+            .unwrap();
+
+        let probed = state.backend_pool().all_backends()[0].last_check_ms();
+        assert!(probed > 0);
+        assert_eq!(state.backend_pool().total_count(), 1);
+        assert_eq!(
+            disabled.accept().unwrap_err().kind(),
+            std::io::ErrorKind::WouldBlock
+        );
+    }
+
+    #[tokio::test]
+    async fn health_checks_keep_running_in_maintenance_mode() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let backend = BackendConfig::local(listener.local_addr().unwrap().port());
+        let state = AppState::for_test(RuntimeConfig::for_test(
+            vec![backend],
+            RuntimeTuning::default(),
+        ));
+        state.set_maintenance(true);
+        let tracked = Arc::clone(&state.backend_pool().all_backends()[0]);
+        tracked.set_healthy(false);
+
+        HealthChecker::new(Arc::clone(&state))
+            .check_due_backends(Instant::now(), &mut HashMap::new())
+            .await
+            .unwrap();
+        assert!(tracked.is_healthy());
+
+        drop(listener);
+        let checker = HealthChecker::new(Arc::clone(&state));
+        for _ in 0..state.config().runtime_tuning.health_check_fail_threshold {
+            checker
+                .check_due_backends(Instant::now(), &mut HashMap::new())
+                .await
+                .unwrap();
+        }
+        assert!(!tracked.is_healthy());
+        assert!(state.is_maintenance());
+    }
+
+    /// Listener that counts the probes (accepted connections) it receives
+    async fn counting_backend(
+        health_check_interval_ms: Option<u64>,
+    ) -> (BackendConfig, Arc<std::sync::atomic::AtomicUsize>) {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let config = BackendConfig {
+            health_check_interval_ms,
+            ..BackendConfig::local(listener.local_addr().unwrap().port())
+        };
+        let probes = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counter = Arc::clone(&probes);
+        tokio::spawn(async move {
+            while listener.accept().await.is_ok() {
+                counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            }
+        });
+        (config, probes)
+    }
+
+    #[tokio::test]
+    async fn backend_interval_override_is_probed_more_often() {
+        use std::sync::atomic::Ordering;
+
+        let (critical, critical_probes) = counting_backend(Some(40)).await;
+        let (bulk, bulk_probes) = counting_backend(None).await;
+        let state = AppState::for_test(RuntimeConfig::for_test(
+            vec![critical, bulk],
+            RuntimeTuning {
+                health_check_interval_ms: 200,
+                ..RuntimeTuning::default()
+            },
+        ));
+        assert_eq!(scheduler_tick(&state.config()), Duration::from_millis(40));
+
+        let shutdown = state.subscribe_shutdown();
+        let checker = HealthChecker::new(Arc::clone(&state));
+        let running = tokio::spawn(async move { checker.run(shutdown).await });
+        tokio::time::sleep(Duration::from_millis(650)).await;
+        state.trigger_shutdown();
+        running.await.unwrap().unwrap();
+
+        let critical = critical_probes.load(Ordering::SeqCst);
+        let bulk = bulk_probes.load(Ordering::SeqCst);
+        assert!(bulk >= 1, "bulk backend was never probed");
+        assert!(critical >= bulk * 3, "critical={} bulk={}", critical, bulk);
+    }
+
+    #[tokio::test]
+    async fn probes_in_flight_never_exceed_the_concurrency_limit() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let completed = Arc::new(AtomicUsize::new(0));
+
+        let counters = (
+            Arc::clone(&in_flight),
+            Arc::clone(&peak),
+            Arc::clone(&completed),
+        );
+        for_each_bounded((0..200).collect(), 8, move |_: usize| {
+            let (in_flight, peak, completed) = (
+                Arc::clone(&counters.0),
+                Arc::clone(&counters.1),
+                Arc::clone(&counters.2),
+            );
+            async move {
+                let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(2)).await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                completed.fetch_add(1, Ordering::SeqCst);
+            }
+        })
+        .await;
+
+        assert_eq!(completed.load(Ordering::SeqCst), 200);
+        assert_eq!(peak.load(Ordering::SeqCst), 8);
+    }
+
+    async fn banner_server(banner: &'static [u8]) -> SocketAddr {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let _ = stream.write_all(banner).await;
+            }
+        });
+        addr
+    }
+
+    fn expect_only(expected: &str) -> ProbeExchange {
+        ProbeExchange {
+            send: None,
+            expect: Some(expected.as_bytes().to_vec()),
+        }
+    }
+
+    #[tokio::test]
+    async fn expect_banner_requires_a_matching_greeting() {
+        let smtp = banner_server(b"220 mail.example.com ESMTP ready\r\n").await;
+        let timeout = Duration::from_millis(500);
+
+        assert!(probe(
+            &BackendTarget::Tcp(vec![smtp]),
+            timeout,
+            &ProbeExchange::default()
+        )
+        .await
+        .is_ok());
+        assert!(probe(
+            &BackendTarget::Tcp(vec![smtp]),
+            timeout,
+            &expect_only("220 ")
+        )
+        .await
+        .is_ok());
+
+        let reason = probe(
+            &BackendTarget::Tcp(vec![smtp]),
+            timeout,
+            &expect_only("+OK"),
+        )
+        .await
+        .unwrap_err();
+        assert!(reason.contains("did not contain"), "{}", reason);
+    }
+
+    /// Replies PONG to PING and echoes anything else
+    async fn ping_server() -> SocketAddr {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                tokio::spawn(async move {
+                    let mut buf = [0u8; 64];
+                    let Ok(n) = stream.read(&mut buf).await else {
+                        return;
+                    };
+                    let reply: &[u8] = if buf[..n].starts_with(b"PING") {
+                        b"+PONG\r\n"
+                    } else {
+                        &buf[..n]
+                    };
+                    let _ = stream.write_all(reply).await;
+                });
+            }
+        });
+        addr
+    }
+
+    #[tokio::test]
+    async fn send_then_expect_checks_the_reply() {
+        let server = ping_server().await;
+        let timeout = Duration::from_millis(500);
+
+        let runtime = RuntimeTuning {
+            health_check_send: Some("PING\r\n".to_string()),
+            health_check_expect: Some("PONG".to_string()),
+            ..RuntimeTuning::default()
+        };
+        let ping = ProbeExchange::from_runtime(&runtime);
+        assert!(probe(&BackendTarget::Tcp(vec![server]), timeout, &ping)
+            .await
+            .is_ok());
+
+        // Hex payloads: "HELO" is echoed back, so expecting PONG must fail
+        let runtime = RuntimeTuning {
+            health_check_send: Some("hex:48 45 4c 4f".to_string()),
+            health_check_expect: Some("hex:504f4e47".to_string()),
+            ..RuntimeTuning::default()
+        };
+        let helo = ProbeExchange::from_runtime(&runtime);
+        assert_eq!(helo.send.as_deref(), Some(&b"HELO"[..]));
+        assert!(probe(&BackendTarget::Tcp(vec![server]), timeout, &helo)
+            .await
+            .is_err());
+    }
+}
@@ -0,0 +1,163 @@
+//! Backend keepalive pool
+//!
+//! With `backend_keepalive`, each backend keeps a few spare connections
+//! opened ahead of time so a new client session can skip the backend
+//! handshake. A spare is handed out once and never returned: after a
+//! session it carries that session's protocol state. This is only safe for
+//! backends whose protocol has no per-connection setup bal would skip.
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tokio::time::timeout;
+
+use crate::proxy::ProxyStream;
+
+/// Pre-opened backend connection waiting for a client
+#[derive(Debug)]
+struct IdleConnection {
+    stream: ProxyStream,
+    /// Peer it reached, for connection logs
+    peer: String,
+    since: Instant,
+}
+
+/// Spare connections for one backend, oldest first
+#[derive(Debug, Default)]
+pub struct KeepalivePool {
+    idle: Mutex<VecDeque<IdleConnection>>,
+    /// Set while a task is topping the pool up, so only one runs at a time
+    refilling: AtomicBool,
+}
+
+impl KeepalivePool {
+    /// Number of spare connections currently held
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Park a freshly opened connection for a later client
+    pub fn put(&self, stream: ProxyStream, peer: String) {
+        self.lock().push_back(IdleConnection {
+            stream,
+            peer,
+            since: Instant::now(),
+        });
+    }
+
+    /// Hand out the newest spare that is younger than `idle` and still open
+    pub async fn take(&self, idle: Duration) -> Option<(ProxyStream, String)> {
+        loop {
+            let candidate = self.lock().pop_back()?;
+            if candidate.since.elapsed() < idle && is_open(&candidate.stream).await {
+                return Some((candidate.stream, candidate.peer));
+            }
+        }
+    }
+
+    /// Close spares idle for `idle` or longer and any the backend closed
+    ///
+    /// Returns how many were dropped.
+    pub async fn evict_idle(&self, idle: Duration) -> usize {
+        let held: Vec<IdleConnection> = self.lock().drain(..).collect();
+        let before = held.len();
+        let mut kept = Vec::with_capacity(before);
+        for connection in held {
+            if connection.since.elapsed() < idle && is_open(&connection.stream).await {
+                kept.push(connection);
+            }
+        }
+        let evicted = before - kept.len();
+
+        // Spares put while we were checking stay behind the older ones
+        let mut idle_connections = self.lock();
+        for connection in kept.into_iter().rev() {
+            idle_connections.push_front(connection);
+        }
+        evicted
+    }
+
+    /// Claim the refill slot; `false` if another task is already refilling
+    pub fn begin_refill(&self) -> bool {
+        !self.refilling.swap(true, Ordering::Relaxed)
+    }
+
+    pub fn end_refill(&self) {
+        self.refilling.store(false, Ordering::Relaxed);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, VecDeque<IdleConnection>> {
+        self.idle.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// An idle connection is usable unless the backend closed or reset it
+///
+/// Bytes already waiting (a server greeting) are left for the client.
+async fn is_open(stream: &ProxyStream) -> bool {
+    let mut probe = [0u8; 1];
+    match timeout(Duration::ZERO, stream.peek(&mut probe)).await {
+        Err(_) => true,
+        Ok(Ok(n)) => n > 0,
+        Ok(Err(_)) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::{TcpListener, TcpStream};
+
+    async fn open_to(listener: &TcpListener) -> (ProxyStream, TcpStream) {
+        let client = TcpStream::connect(listener.local_addr().unwrap())
+            .await
+            .unwrap();
+        let (server, _) = listener.accept().await.unwrap();
+        (ProxyStream::Tcp(client), server)
+    }
+
+    #[tokio::test]
+    async fn idle_connections_are_reused_then_evicted() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let pool = KeepalivePool::default();
+
+        let (stream, _server) = open_to(&listener).await;
+        let ProxyStream::Tcp(tcp) = &stream else {
+            unreachable!()
+        };
+        let local = tcp.local_addr().unwrap();
+        pool.put(stream, "backend".to_string());
+
+        let (reused, peer) = pool
+            .take(Duration::from_secs(10))
+            .await
+            .expect("spare should be handed out");
+        let ProxyStream::Tcp(reused) = reused else {
+            unreachable!()
+        };
+        assert_eq!(reused.local_addr().unwrap(), local);
+        assert_eq!(peer, "backend");
+        assert_eq!(pool.len(), 0);
+
+        let (stream, _server2) = open_to(&listener).await;
+        pool.put(stream, "backend".to_string());
+        assert_eq!(pool.evict_idle(Duration::from_secs(10)).await, 0);
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        assert_eq!(pool.evict_idle(Duration::from_millis(20)).await, 1);
+        assert_eq!(pool.len(), 0);
+    }
+
+    #[tokio::test]
+    async fn connections_closed_by_the_backend_are_not_handed_out() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let pool = KeepalivePool::default();
+
+        let (stream, server) = open_to(&listener).await;
+        pool.put(stream, "backend".to_string());
+        drop(server);
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        assert!(pool.take(Duration::from_secs(10)).await.is_none());
+    }
+}
@@ -1,14 +1,14 @@
 //! Load balancer module
 //!
 //! Implements load balancing algorithms.
-//! Currently supports Round Robin algorithm, designed to allow adding
-//! Least Connections and others in the future.
+//! Supports Round Robin, Least Connections, Random, Weighted Random and
+//! Least Response Time.
 
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 
 use crate::backend_pool::{BackendPool, BackendState};
-use crate::config::BalanceMethod;
+use crate::config::{BalanceMethod, LabelSelector};
 
 /// Load balancer
 ///
@@ -21,6 +21,12 @@ pub struct LoadBalancer {
     pool: Arc<BackendPool>,
     /// Round robin index (atomic increment)
     rr_index: AtomicUsize,
+    /// Slow-start ramp window for recovered backends (0 = disabled)
+    slow_start_ms: u64,
+    /// How long a recovered backend must stay healthy before it is selectable (0 = disabled)
+    stabilization_window_ms: u64,
+    /// Prefer healthy backends carrying this label (e.g. the local zone)
+    prefer_label: Option<LabelSelector>,
 }
 
 impl LoadBalancer {
@@ -29,14 +35,32 @@ impl LoadBalancer {
     /// # Arguments
     /// * `method` - Load balancing algorithm to use
     /// * `pool` - Backend pool (shared via Arc)
-    pub fn new(method: BalanceMethod, pool: Arc<BackendPool>) -> Self {
+    /// * `slow_start_ms` - Ramp window for recovered backends (0 disables slow-start)
+    /// * `stabilization_window_ms` - Hold-off for recovered backends (0 disables it)
+    /// * `prefer_label` - Label of backends to use first, falling back to others
+    pub fn new(
+        method: BalanceMethod,
+        pool: Arc<BackendPool>,
+        slow_start_ms: u64,
+        stabilization_window_ms: u64,
+        prefer_label: Option<LabelSelector>,
+    ) -> Self {
         Self {
             method,
             pool,
             rr_index: AtomicUsize::new(0),
+            slow_start_ms,
+            stabilization_window_ms,
+            prefer_label,
         }
     }
 
+    /// Pick up round robin where `previous` left off instead of restarting at 0
+    pub fn continue_rotation_from(&self, previous: &LoadBalancer) {
+        self.rr_index
+            .store(previous.rr_index.load(Ordering::Relaxed), Ordering::Relaxed);
+    }
+
     /// Select backend
     ///
     /// Selects appropriate backend based on configured algorithm.
@@ -45,17 +69,139 @@ impl LoadBalancer {
     /// # Returns
     /// * `Some(Arc<BackendState>)` - Selected backend state
     /// * `None` - No available backends
+    #[cfg(test)]
     pub fn select_backend(&self) -> Option<Arc<BackendState>> {
+        self.select_backend_in(None)
+    }
+
+    /// Select a backend among those matching `route` (e.g. an SNI route)
+    ///
+    /// Like `select_backend`, but only healthy backends carrying the label
+    /// are candidates. Returns None when none of them are healthy.
+    pub fn select_backend_in(&self, route: Option<&LabelSelector>) -> Option<Arc<BackendState>> {
         let healthy_backends = self.pool.healthy_backends();
 
+        self.pool.record_availability(!healthy_backends.is_empty());
+        let healthy_backends: Vec<_> = match route {
+            Some(selector) => healthy_backends
+                .into_iter()
+                .filter(|b| b.config.matches_label(selector))
+                .collect(),
+            None => healthy_backends,
+        };
         if healthy_backends.is_empty() {
-            log::warn!("No healthy backends available");
+            log::debug!("No healthy backends available");
             return None;
         }
 
+        let candidates = self.apply_slow_start(self.apply_degraded(
+            self.apply_preference(self.apply_stabilization(self.apply_tiers(healthy_backends))),
+        ));
+
         match self.method {
-            BalanceMethod::RoundRobin => self.select_round_robin(&healthy_backends),
-            BalanceMethod::LeastConnections => self.select_least_connections(&healthy_backends),
+            BalanceMethod::RoundRobin => self.select_round_robin(&candidates),
+            BalanceMethod::LeastConnections => self.select_least_connections(&candidates),
+            BalanceMethod::Random => self.select_random(&candidates),
+            BalanceMethod::WeightedRandom => self.select_weighted_random(&candidates),
+            BalanceMethod::LeastResponseTime => self.select_least_response_time(&candidates),
+        }
+    }
+
+    /// Keep only the lowest failover tier that has a healthy backend
+    ///
+    /// Secondary tiers take traffic only while every primary is unhealthy,
+    /// and hand it back as soon as one recovers.
+    fn apply_tiers(&self, backends: Vec<Arc<BackendState>>) -> Vec<Arc<BackendState>> {
+        let Some(active) = backends.iter().map(|b| b.config.tier).min() else {
+            return backends;
+        };
+
+        backends
+            .into_iter()
+            .filter(|b| b.config.tier == active)
+            .collect()
+    }
+
+    /// Hold back backends that recovered less than the stabilization window ago
+    ///
+    /// A flapping backend keeps resetting its recovery time, so it stays out
+    /// of rotation until it has been healthy for the whole window. If every
+    /// healthy backend is still stabilizing they are used anyway.
+    fn apply_stabilization(&self, backends: Vec<Arc<BackendState>>) -> Vec<Arc<BackendState>> {
+        if self.stabilization_window_ms == 0 {
+            return backends;
+        }
+
+        let now_ms = BackendState::now_unix_ms();
+        let (stable, stabilizing): (Vec<_>, Vec<_>) = backends
+            .into_iter()
+            .partition(|b| b.is_stable(self.stabilization_window_ms, now_ms));
+
+        if stable.is_empty() {
+            stabilizing
+        } else {
+            stable
+        }
+    }
+
+    /// Narrow healthy backends to the preferred label set
+    ///
+    /// Falls back to every healthy backend when none of the preferred ones are healthy.
+    fn apply_preference(&self, backends: Vec<Arc<BackendState>>) -> Vec<Arc<BackendState>> {
+        let Some(selector) = &self.prefer_label else {
+            return backends;
+        };
+
+        let (preferred, fallback): (Vec<_>, Vec<_>) = backends
+            .into_iter()
+            .partition(|b| b.config.matches_label(selector));
+
+        if preferred.is_empty() {
+            log::debug!(
+                "No healthy backend matches prefer_label {}; using fallback set",
+                selector
+            );
+            fallback
+        } else {
+            preferred
+        }
+    }
+
+    /// Deprioritize backends degraded by sustained saturation
+    ///
+    /// Degraded backends are only used when every candidate is degraded, so
+    /// overload shifts traffic away without hard-failing the backend.
+    fn apply_degraded(&self, backends: Vec<Arc<BackendState>>) -> Vec<Arc<BackendState>> {
+        if backends.iter().all(|b| b.is_degraded()) {
+            return backends;
+        }
+
+        backends.into_iter().filter(|b| !b.is_degraded()).collect()
+    }
+
+    /// Thin out recently recovered backends according to their slow-start ramp
+    ///
+    /// Each warming backend stays a candidate with probability equal to its
+    /// ramp factor. If every backend is warming and all are dropped, the full
+    /// healthy list is used so traffic is never refused because of slow-start.
+    fn apply_slow_start(&self, backends: Vec<Arc<BackendState>>) -> Vec<Arc<BackendState>> {
+        if self.slow_start_ms == 0 {
+            return backends;
+        }
+
+        let admitted: Vec<Arc<BackendState>> = backends
+            .iter()
+            .filter(|b| {
+                let factor = b.slow_start_factor(self.slow_start_ms);
+                factor >= 1.0 || fastrand::f64() < factor
+            })
+            .cloned()
+            .collect();
+
+        if admitted.is_empty() {
+            backends
+        } else {
+            admitted
         }
     }
 
@@ -71,9 +217,8 @@ impl LoadBalancer {
         let selected = &backends[index % backends.len()];
 
         log::debug!(
-            "Round robin selection: {}:{} (index: {})",
-            selected.config.host,
-            selected.config.port,
+            "Round robin selection: {} (index: {})",
+            selected.address(),
             index % backends.len()
         );
 
@@ -82,33 +227,81 @@ impl LoadBalancer {
 
     /// Least connections backend selection
     ///
-    /// Selects backend with fewest active connections.
-    /// If tie, selects first backend.
+    /// Selects backend with fewest active plus in-flight connects, so a
+    /// burst of concurrent connects doesn't pile onto one backend.
+    /// Ties rotate round robin, so equal counts (e.g. all zero right after
+    /// startup) don't make the first backend a hotspot.
     fn select_least_connections(
         &self,
         backends: &[Arc<BackendState>],
+    ) -> Option<Arc<BackendState>> {
+        let load = |b: &Arc<BackendState>| b.active_connections() + b.pending_connects();
+        let fewest = backends.iter().map(load).min()?;
+        let tied: Vec<&Arc<BackendState>> = backends.iter().filter(|b| load(b) == fewest).collect();
+
+        let index = self.rr_index.fetch_add(1, Ordering::Relaxed);
+        Some(Arc::clone(tied[index % tied.len()]))
+    }
+
+    /// Uniform random backend selection
+    ///
+    /// Uses fastrand's thread-local RNG, so there is no shared state to contend on.
+    fn select_random(&self, backends: &[Arc<BackendState>]) -> Option<Arc<BackendState>> {
+        let selected = &backends[fastrand::usize(..backends.len())];
+        Some(Arc::clone(selected))
+    }
+
+    /// Weighted random backend selection
+    ///
+    /// Picks a backend with probability `weight / sum(weights)`.
+    fn select_weighted_random(&self, backends: &[Arc<BackendState>]) -> Option<Arc<BackendState>> {
+        let total: u64 = backends.iter().map(|b| u64::from(b.config.weight)).sum();
+        if total == 0 {
+            return self.select_random(backends);
+        }
+
+        let mut point = fastrand::u64(..total);
+        for backend in backends {
+            let weight = u64::from(backend.config.weight);
+            if point < weight {
+                return Some(Arc::clone(backend));
+            }
+            point -= weight;
+        }
+
+        backends.last().cloned()
+    }
+
+    /// Least response time backend selection
+    ///
+    /// Selects the backend with the lowest smoothed health probe latency.
+    /// Backends without a sample yet rank last; ties go to fewer active connections.
+    fn select_least_response_time(
+        &self,
+        backends: &[Arc<BackendState>],
     ) -> Option<Arc<BackendState>> {
         backends
             .iter()
-            .min_by_key(|b| b.active_connections())
+            .min_by_key(|b| {
+                (
+                    b.smoothed_latency().unwrap_or(std::time::Duration::MAX),
+                    b.active_connections(),
+                )
+            })
             .cloned()
     }
 
     /// Get load balancing method
+    #[allow(dead_code)]
     pub fn method(&self) -> BalanceMethod {
         self.method
     }
 
     /// Get backend pool reference
+    #[allow(dead_code)]
     pub fn pool(&self) -> &Arc<BackendPool> {
         &self.pool
     }
-
-    /// Get current round robin index (for testing)
-    #[cfg(test)]
-    pub fn current_index(&self) -> usize {
-        self.rr_index.load(Ordering::Relaxed)
-    }
 }
 
 #[cfg(test)]
@@ -119,18 +312,9 @@ mod tests {
 
     fn create_test_pool() -> Arc<BackendPool> {
         let configs = vec![
-            BackendConfig {
-                host: "127.0.0.1".to_string(),
-                port: 8080,
-            },
-            BackendConfig {
-                host: "127.0.0.1".to_string(),
-                port: 8081,
-            },
-            BackendConfig {
-                host: "127.0.0.1".to_string(),
-                port: 8082,
-            },
+            BackendConfig::local(8080),
+            BackendConfig::local(8081),
+            BackendConfig::local(8082),
         ];
 
         Arc::new(BackendPool::new(configs))
@@ -139,7 +323,7 @@ mod tests {
     #[test]
     fn test_round_robin_selection() {
         let pool = create_test_pool();
-        let lb = LoadBalancer::new(BalanceMethod::RoundRobin, Arc::clone(&pool));
+        let lb = LoadBalancer::new(BalanceMethod::RoundRobin, Arc::clone(&pool), 0, 0, None);
 
         // Sequential selections should cycle
         let backend1 = lb.select_backend().unwrap();
@@ -159,7 +343,13 @@ mod tests {
     #[test]
     fn test_least_connections_selection() {
         let pool = create_test_pool();
-        let lb = LoadBalancer::new(BalanceMethod::LeastConnections, Arc::clone(&pool));
+        let lb = LoadBalancer::new(
+            BalanceMethod::LeastConnections,
+            Arc::clone(&pool),
+            0,
+            0,
+            None,
+        );
 
         // Add connections to first backend
         let backends = pool.all_backends();
@@ -173,6 +363,83 @@ mod tests {
         assert_ne!(selected.config.port, 8080);
     }
 
+    #[test]
+    fn test_least_connections_from_parsed_config_rotates_through_ties() {
+        let yaml = r#"
+method: least_connections
+backends:
+  - host: "127.0.0.1"
+    port: 8080
+  - host: "127.0.0.1"
+    port: 8081
+  - host: "127.0.0.1"
+    port: 8082
+"#;
+        let config: crate::config::Config = serde_yaml::from_str(yaml).unwrap();
+        let pool = Arc::new(BackendPool::new(config.backends));
+        let lb = LoadBalancer::new(config.method, Arc::clone(&pool), 0, 0, None);
+
+        // All idle (e.g. right after startup): rotate instead of hammering 8080
+        let ports: Vec<u16> = (0..6)
+            .map(|_| lb.select_backend().unwrap().config.port)
+            .collect();
+        assert_eq!(ports, [8080, 8081, 8082, 8080, 8081, 8082]);
+
+        // Rotation only covers the backends tied for fewest connections
+        pool.all_backends()[0].increment_connections();
+        assert!((0..6).all(|_| lb.select_backend().unwrap().config.port != 8080));
+    }
+
+    #[test]
+    fn test_least_connections_counts_pending_connects() {
+        use crate::backend_pool::PendingConnectGuard;
+
+        let pool = create_test_pool();
+        let lb = LoadBalancer::new(
+            BalanceMethod::LeastConnections,
+            Arc::clone(&pool),
+            0,
+            0,
+            None,
+        );
+
+        // A burst of selections whose connects haven't resolved yet
+        let pending: Vec<_> = (0..6)
+            .map(|_| PendingConnectGuard::new(lb.select_backend().unwrap()))
+            .collect();
+
+        for backend in pool.all_backends() {
+            assert_eq!(backend.pending_connects(), 2);
+            assert_eq!(backend.active_connections(), 0);
+        }
+
+        drop(pending);
+        assert!(pool
+            .all_backends()
+            .iter()
+            .all(|b| b.pending_connects() == 0));
+    }
+
+    #[test]
+    fn test_degraded_backends_used_only_when_all_are_degraded() {
+        let pool = create_test_pool();
+        let lb = LoadBalancer::new(BalanceMethod::RoundRobin, Arc::clone(&pool), 0, 0, None);
+        let backends = pool.all_backends();
+        for backend in &backends[..2] {
+            backend.increment_connections();
+            backend.update_saturation(1, 0, 1_000);
+            assert!(backend.is_degraded());
+        }
+
+        for _ in 0..6 {
+            assert_eq!(lb.select_backend().unwrap().config.port, 8082);
+        }
+
+        backends[2].increment_connections();
+        backends[2].update_saturation(1, 0, 1_000);
+        assert!(lb.select_backend().is_some());
+    }
+
     #[test]
     fn test_no_healthy_backend() {
         // Set all backends as unhealthy
@@ -181,9 +448,247 @@ mod tests {
             backend.set_healthy(false);
         }
 
-        let lb = LoadBalancer::new(BalanceMethod::RoundRobin, pool);
+        let lb = LoadBalancer::new(BalanceMethod::RoundRobin, pool, 0, 0, None);
 
         // Should not be able to select any backend
         assert!(lb.select_backend().is_none());
     }
+
+    #[test]
+    fn test_all_down_transition_is_recorded_once() {
+        let pool = create_test_pool();
+        let lb = LoadBalancer::new(BalanceMethod::RoundRobin, Arc::clone(&pool), 0, 0, None);
+        for backend in pool.all_backends() {
+            backend.set_healthy(false);
+        }
+
+        assert!((0..50).all(|_| lb.select_backend().is_none()));
+        // Selection already recorded the outage; repeating it is not a new transition
+        assert!(!pool.record_availability(false));
+
+        pool.all_backends()[0].set_healthy(true);
+        assert!((0..50).all(|_| lb.select_backend().is_some()));
+        assert!(!pool.record_availability(true));
+        assert!(pool.record_availability(false));
+    }
+
+    #[test]
+    fn test_slow_start_reduces_share_until_ramp_completes() {
+        let pool = create_test_pool();
+        let recovered = Arc::clone(&pool.all_backends()[1]);
+        recovered.mark_failure(1);
+        recovered.mark_success(1);
+
+        let lb = LoadBalancer::new(BalanceMethod::RoundRobin, Arc::clone(&pool), 200, 0, None);
+
+        let during_ramp = (0..900)
+            .filter_map(|_| lb.select_backend())
+            .filter(|b| b.config.port == recovered.config.port)
+            .count();
+        // Full round-robin share would be 300
+        assert!(
+            during_ramp < 100,
+            "recovered backend got {during_ramp} selections during ramp"
+        );
+
+        std::thread::sleep(std::time::Duration::from_millis(250));
+
+        let after_ramp = (0..900)
+            .filter_map(|_| lb.select_backend())
+            .filter(|b| b.config.port == recovered.config.port)
+            .count();
+        assert_eq!(after_ramp, 300);
+    }
+
+    #[test]
+    fn test_flapping_backend_waits_out_the_stabilization_window() {
+        let pool = create_test_pool();
+        let flapping = Arc::clone(&pool.all_backends()[1]);
+        let lb = LoadBalancer::new(BalanceMethod::RoundRobin, Arc::clone(&pool), 0, 200, None);
+
+        // Recovers, drops again mid-window, recovers again: the window restarts
+        flapping.mark_failure(1);
+        flapping.mark_success(1);
+        std::thread::sleep(std::time::Duration::from_millis(120));
+        flapping.mark_failure(1);
+        flapping.mark_success(1);
+        std::thread::sleep(std::time::Duration::from_millis(120));
+
+        assert!(flapping.is_healthy());
+        assert!((0..30).all(|_| lb.select_backend().unwrap().config.port != flapping.config.port));
+
+        std::thread::sleep(std::time::Duration::from_millis(120));
+        let selected = (0..30)
+            .filter(|_| lb.select_backend().unwrap().config.port == flapping.config.port)
+            .count();
+        assert_eq!(selected, 10);
+    }
+
+    fn selection_counts(lb: &LoadBalancer, rounds: usize) -> std::collections::HashMap<u16, usize> {
+        let mut counts = std::collections::HashMap::new();
+        for _ in 0..rounds {
+            let backend = lb.select_backend().unwrap();
+            *counts.entry(backend.config.port).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    #[test]
+    fn test_random_selection_is_roughly_uniform() {
+        let pool = create_test_pool();
+        let lb = LoadBalancer::new(BalanceMethod::Random, pool, 0, 0, None);
+
+        let counts = selection_counts(&lb, 30_000);
+        for port in [8080, 8081, 8082] {
+            let share = counts[&port] as f64 / 30_000.0;
+            assert!(
+                (share - 1.0 / 3.0).abs() < 0.03,
+                "port {port} got share {share}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_weighted_random_follows_weights() {
+        let configs = [(8080, 1), (8081, 3), (8082, 6)]
+            .into_iter()
+            .map(|(port, weight)| BackendConfig {
+                weight,
+                ..BackendConfig::local(port)
+            })
+            .collect();
+        let lb = LoadBalancer::new(
+            BalanceMethod::WeightedRandom,
+            Arc::new(BackendPool::new(configs)),
+            0,
+            0,
+            None,
+        );
+
+        let counts = selection_counts(&lb, 50_000);
+        for (port, expected) in [(8080, 0.1), (8081, 0.3), (8082, 0.6)] {
+            let share = counts[&port] as f64 / 50_000.0;
+            assert!(
+                (share - expected).abs() < 0.03,
+                "port {port} got share {share}, expected {expected}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_least_response_time_prefers_fastest_backend() {
+        use std::time::Duration;
+
+        let pool = create_test_pool();
+        let backends = pool.all_backends();
+        for _ in 0..5 {
+            backends[0].record_latency(Duration::from_millis(40));
+            backends[1].record_latency(Duration::from_millis(3));
+            backends[2].record_latency(Duration::from_millis(15));
+        }
+        let lb = LoadBalancer::new(
+            BalanceMethod::LeastResponseTime,
+            Arc::clone(&pool),
+            0,
+            0,
+            None,
+        );
+
+        for _ in 0..10 {
+            assert_eq!(lb.select_backend().unwrap().config.port, 8081);
+        }
+
+        // A formerly fast backend that turns slow loses its spot after a few probes
+        for _ in 0..10 {
+            backends[1].record_latency(Duration::from_millis(80));
+        }
+        assert_eq!(lb.select_backend().unwrap().config.port, 8082);
+
+        let parsed: BalanceMethod = serde_yaml::from_str("least_response_time").unwrap();
+        assert_eq!(parsed, BalanceMethod::LeastResponseTime);
+    }
+
+    #[test]
+    fn test_random_methods_parse_from_yaml() {
+        let random: BalanceMethod = serde_yaml::from_str("random").unwrap();
+        let weighted: BalanceMethod = serde_yaml::from_str("weighted_random").unwrap();
+        assert_eq!(random, BalanceMethod::Random);
+        assert_eq!(weighted, BalanceMethod::WeightedRandom);
+    }
+
+    #[test]
+    fn test_disabled_backend_never_selected() {
+        let configs = [(8080, true), (8081, false)]
+            .into_iter()
+            .map(|(port, enabled)| BackendConfig {
+                enabled,
+                ..BackendConfig::local(port)
+            })
+            .collect();
+        let pool = Arc::new(BackendPool::new(configs));
+
+        for method in [
+            BalanceMethod::RoundRobin,
+            BalanceMethod::Random,
+            BalanceMethod::WeightedRandom,
+        ] {
+            let lb = LoadBalancer::new(method, Arc::clone(&pool), 0, 0, None);
+            assert!((0..100).all(|_| lb.select_backend().unwrap().config.port == 8080));
+        }
+        assert_eq!(pool.total_count(), 1);
+    }
+
+    #[test]
+    fn test_prefer_label_uses_fallback_only_when_preferred_are_down() {
+        let configs = [(8080, "us-east"), (8081, "us-east"), (8082, "us-west")]
+            .into_iter()
+            .map(|(port, zone)| BackendConfig {
+                labels: [("zone".to_string(), zone.to_string())].into(),
+                ..BackendConfig::local(port)
+            })
+            .collect();
+        let pool = Arc::new(BackendPool::new(configs));
+        let lb = LoadBalancer::new(
+            BalanceMethod::RoundRobin,
+            Arc::clone(&pool),
+            0,
+            0,
+            Some("zone=us-east".parse().unwrap()),
+        );
+
+        assert!((0..20).all(|_| lb.select_backend().unwrap().config.port != 8082));
+
+        pool.all_backends()[0].set_healthy(false);
+        pool.all_backends()[1].set_healthy(false);
+        assert!((0..20).all(|_| lb.select_backend().unwrap().config.port == 8082));
+
+        pool.all_backends()[1].set_healthy(true);
+        assert!((0..20).all(|_| lb.select_backend().unwrap().config.port == 8081));
+    }
+
+    #[test]
+    fn secondary_tier_serves_only_while_every_primary_is_down() {
+        let configs = [(8080, 0), (8081, 0), (8082, 1)]
+            .into_iter()
+            .map(|(port, tier)| BackendConfig {
+                tier,
+                ..BackendConfig::local(port)
+            })
+            .collect();
+        let pool = Arc::new(BackendPool::new(configs));
+        let lb = LoadBalancer::new(BalanceMethod::RoundRobin, Arc::clone(&pool), 0, 0, None);
+
+        assert!((0..20).all(|_| lb.select_backend().unwrap().config.tier == 0));
+
+        // One primary left: it takes everything, the standby stays idle
+        pool.all_backends()[0].set_healthy(false);
+        assert!((0..20).all(|_| lb.select_backend().unwrap().config.port == 8081));
+
+        pool.all_backends()[1].set_healthy(false);
+        assert!((0..20).all(|_| lb.select_backend().unwrap().config.port == 8082));
+
+        // Traffic returns to the primary tier as soon as one recovers
+        pool.all_backends()[0].set_healthy(true);
+        assert!((0..20).all(|_| lb.select_backend().unwrap().config.port == 8080));
+    }
 }
@@ -5,14 +5,18 @@
 
 use anyhow::Result;
 use log::LevelFilter;
-use serde_json::{json, Value};
+use serde_json::{json, Map, Value};
 use std::fs::OpenOptions;
 use std::io::Write;
+use std::sync::OnceLock;
 
 use crate::constants::get_log_file_path;
 
+/// Level the process started with; SIGUSR2 toggles back to it
+static CONFIGURED_LEVEL: OnceLock<LevelFilter> = OnceLock::new();
+
 /// Parse log level string to LevelFilter
-fn parse_log_level(level: &str) -> LevelFilter {
+pub fn parse_log_level(level: &str) -> LevelFilter {
     match level.to_lowercase().as_str() {
         "debug" => LevelFilter::Debug,
         "info" => LevelFilter::Info,
@@ -22,6 +26,23 @@ fn parse_log_level(level: &str) -> LevelFilter {
     }
 }
 
+/// Level this run logs at
+///
+/// `--verbose` forces debug; otherwise `--log-level` beats the config's
+/// `log_level`, which beats the `info` default.
+pub fn effective_log_level(
+    config_level: Option<String>,
+    cli_level: Option<String>,
+    verbose: bool,
+) -> String {
+    if verbose {
+        return "debug".to_string();
+    }
+    cli_level
+        .or(config_level)
+        .unwrap_or_else(|| "info".to_string())
+}
+
 /// Initialize logging system
 ///
 /// - foreground mode: logs to stdout
@@ -30,34 +51,65 @@ pub fn init_logging(log_level_str: &str, daemon_mode: bool) -> Result<()> {
     let log_level = parse_log_level(log_level_str);
 
     if daemon_mode {
-        init_file_logging(log_level)?;
+        init_file_logging()?;
     } else {
-        init_console_logging(log_level)?;
+        init_console_logging()?;
     }
 
+    // The logger itself passes everything; the global max level does the
+    // filtering so it can be changed at runtime
+    let _ = CONFIGURED_LEVEL.set(log_level);
+    log::set_max_level(log_level);
+
     Ok(())
 }
 
-fn init_console_logging(log_level: LevelFilter) -> Result<()> {
+/// Change the running process's log level; returns the previous one
+pub fn set_log_level(level: LevelFilter) -> LevelFilter {
+    let previous = log::max_level();
+    log::set_max_level(level);
+    previous
+}
+
+/// Level a SIGUSR2 switches to from `current`
+///
+/// An explicit `requested` level wins; otherwise debug is toggled on, or
+/// back off to the configured level (info if that was debug already).
+pub fn next_log_level(
+    current: LevelFilter,
+    configured: LevelFilter,
+    requested: Option<LevelFilter>,
+) -> LevelFilter {
+    if let Some(level) = requested {
+        return level;
+    }
+    if current < LevelFilter::Debug {
+        LevelFilter::Debug
+    } else if configured < LevelFilter::Debug {
+        configured
+    } else {
+        LevelFilter::Info
+    }
+}
+
+/// Apply a runtime log level change (SIGUSR2); returns the new level
+pub fn apply_log_level_change(requested: Option<LevelFilter>) -> LevelFilter {
+    let configured = CONFIGURED_LEVEL.get().copied().unwrap_or(LevelFilter::Info);
+    let level = next_log_level(log::max_level(), configured, requested);
+    set_log_level(level);
+    level
+}
+
+fn init_console_logging() -> Result<()> {
     env_logger::Builder::new()
-        .format(move |buf, record| {
-            let payload = build_json_payload(
-                &chrono::Utc::now().to_rfc3339(),
-                &record.level().to_string(),
-                &record.args().to_string(),
-                record.module_path().unwrap_or(record.target()),
-                "log",
-                json!({}),
-            );
-            writeln!(buf, "{}", payload)
-        })
-        .filter_level(log_level)
+        .format(move |buf, record| writeln!(buf, "{}", record_payload(record)))
+        .filter_level(LevelFilter::Trace)
         .init();
 
     Ok(())
 }
 
-fn init_file_logging(log_level: LevelFilter) -> Result<()> {
+fn init_file_logging() -> Result<()> {
     let log_path = get_log_file_path();
     if let Some(parent) = log_path.parent() {
         std::fs::create_dir_all(parent)?;
@@ -72,23 +124,64 @@ fn init_file_logging(log_level: LevelFilter) -> Result<()> {
 
     env_logger::Builder::new()
         .target(env_logger::Target::Pipe(target))
-        .format(move |buf, record| {
-            let payload = build_json_payload(
-                &chrono::Utc::now().to_rfc3339(),
-                &record.level().to_string(),
-                &record.args().to_string(),
-                record.module_path().unwrap_or(record.target()),
-                "log",
-                json!({}),
-            );
-            writeln!(buf, "{}", payload)
-        })
-        .filter_level(log_level)
+        .format(move |buf, record| writeln!(buf, "{}", record_payload(record)))
+        .filter_level(LevelFilter::Trace)
         .init();
 
     Ok(())
 }
 
+/// Render a log record as the one-line JSON payload
+fn record_payload(record: &log::Record) -> Value {
+    let (event, fields) = record_fields(record);
+    build_json_payload(
+        &chrono::Utc::now().to_rfc3339(),
+        &record.level().to_string(),
+        &record.args().to_string(),
+        record.module_path().unwrap_or(record.target()),
+        &event,
+        fields,
+    )
+}
+
+/// Split a record's structured key-values into its event name and `fields`
+///
+/// `info!(event = "connection_closed", conn_id = id; "...")` sets the payload's
+/// `event` key; every other pair lands in `fields`. Records without an
+/// `event` pair use the generic "log" event.
+pub(crate) fn record_fields(record: &log::Record) -> (String, Value) {
+    struct Collect(Map<String, Value>);
+
+    impl<'kvs> log::kv::VisitSource<'kvs> for Collect {
+        fn visit_pair(
+            &mut self,
+            key: log::kv::Key<'kvs>,
+            value: log::kv::Value<'kvs>,
+        ) -> Result<(), log::kv::Error> {
+            let value = if let Some(number) = value.to_u64() {
+                Value::from(number)
+            } else if let Some(number) = value.to_i64() {
+                Value::from(number)
+            } else if let Some(flag) = value.to_bool() {
+                Value::from(flag)
+            } else {
+                Value::from(value.to_string())
+            };
+            self.0.insert(key.to_string(), value);
+            Ok(())
+        }
+    }
+
+    let mut collect = Collect(Map::new());
+    let _ = record.key_values().visit(&mut collect);
+    let mut fields = collect.0;
+    let event = match fields.remove("event") {
+        Some(Value::String(event)) => event,
+        _ => "log".to_string(),
+    };
+    (event, Value::Object(fields))
+}
+
 fn build_json_payload(
     timestamp: &str,
     level: &str,
@@ -108,6 +201,7 @@ fn build_json_payload(
 }
 
 /// Append log message to file in one-line JSON format.
+#[allow(dead_code)]
 pub fn append_to_log_file(message: &str) -> Result<()> {
     let log_path = get_log_file_path();
 
@@ -137,6 +231,41 @@ pub fn append_to_log_file(message: &str) -> Result<()> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn cli_log_level_overrides_the_config_value() {
+        let from_config = Some("warn".to_string());
+        assert_eq!(
+            effective_log_level(from_config.clone(), None, false),
+            "warn"
+        );
+        assert_eq!(
+            effective_log_level(from_config.clone(), Some("error".to_string()), false),
+            "error"
+        );
+        assert_eq!(
+            effective_log_level(from_config, Some("error".to_string()), true),
+            "debug"
+        );
+        assert_eq!(effective_log_level(None, None, false), "info");
+    }
+
+    #[test]
+    fn runtime_level_changes_toggle_debug_and_move_the_max_level() {
+        use LevelFilter::{Debug, Error, Info, Trace, Warn};
+
+        assert_eq!(next_log_level(Info, Info, None), Debug);
+        assert_eq!(next_log_level(Debug, Warn, None), Warn);
+        assert_eq!(next_log_level(Debug, Debug, None), Info);
+        assert_eq!(next_log_level(Debug, Info, Some(Error)), Error);
+
+        // Other tests capture debug logs, so only move between debug and trace
+        set_log_level(Debug);
+        assert_eq!(set_log_level(Trace), Debug);
+        assert_eq!(log::max_level(), Trace);
+        set_log_level(Debug);
+        assert_eq!(log::max_level(), Debug);
+    }
+
     #[test]
     fn json_log_payload_uses_stable_keys() {
         let payload = build_json_payload(
@@ -155,4 +284,26 @@ mod tests {
         assert_eq!(payload["event"], "service_started");
         assert_eq!(payload["fields"]["daemon"], false);
     }
+
+    #[test]
+    fn record_key_values_become_event_and_fields() {
+        let kvs: &[(&str, log::kv::Value)] = &[
+            ("event", log::kv::Value::from("connection_closed")),
+            ("conn_id", log::kv::Value::from("00002a")),
+            ("bytes", log::kv::Value::from(42u64)),
+        ];
+        let record = log::Record::builder()
+            .args(format_args!("closed"))
+            .key_values(&kvs)
+            .build();
+
+        let payload = record_payload(&record);
+        assert_eq!(payload["event"], "connection_closed");
+        assert_eq!(payload["fields"]["conn_id"], "00002a");
+        assert_eq!(payload["fields"]["bytes"], 42);
+        assert!(payload["fields"].get("event").is_none());
+
+        let plain = log::Record::builder().args(format_args!("plain")).build();
+        assert_eq!(record_payload(&plain)["event"], "log");
+    }
 }
@@ -0,0 +1,142 @@
+//! `bal logs`
+//!
+//! Prints the tail of the daemon log (`bal.log` in the runtime directory)
+//! and optionally follows it, so operators don't need to know where it lives.
+
+use anyhow::{bail, Context, Result};
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+use std::time::Duration;
+
+use crate::constants::get_log_file_path;
+
+/// Bytes read per step while scanning backwards for line breaks
+const TAIL_CHUNK_BYTES: u64 = 8192;
+
+/// How often `--follow` checks the file for new output
+const FOLLOW_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Last `count` lines of `path`, plus the file length they were read up to
+///
+/// Reads backwards in chunks, so only the tail of a large log is touched.
+pub fn tail_lines(path: &Path, count: usize) -> std::io::Result<(Vec<String>, u64)> {
+    let mut file = File::open(path)?;
+    let len = file.metadata()?.len();
+
+    let mut start = len;
+    let mut tail = Vec::new();
+    // One extra line break so a partial first line can be dropped
+    while start > 0 && tail.iter().filter(|byte| **byte == b'\n').count() <= count {
+        let chunk = TAIL_CHUNK_BYTES.min(start);
+        start -= chunk;
+        file.seek(SeekFrom::Start(start))?;
+        let mut piece = vec![0u8; chunk as usize];
+        file.read_exact(&mut piece)?;
+        piece.extend_from_slice(&tail);
+        tail = piece;
+    }
+
+    let text = String::from_utf8_lossy(&tail);
+    let lines: Vec<&str> = text.lines().collect();
+    let skip = lines.len().saturating_sub(count);
+    Ok((
+        lines[skip..].iter().map(|line| line.to_string()).collect(),
+        len,
+    ))
+}
+
+/// Complete lines appended after `offset`, and the offset to continue from
+///
+/// A file shorter than `offset` was truncated or rotated, so reading restarts
+/// at its beginning. A trailing partial line is left for the next call.
+pub fn read_appended(path: &Path, offset: u64) -> std::io::Result<(Vec<String>, u64)> {
+    let mut file = File::open(path)?;
+    let len = file.metadata()?.len();
+    let offset = if len < offset { 0 } else { offset };
+
+    file.seek(SeekFrom::Start(offset))?;
+    let mut appended = Vec::new();
+    file.take(len - offset).read_to_end(&mut appended)?;
+
+    let complete = appended
+        .iter()
+        .rposition(|byte| *byte == b'\n')
+        .map_or(0, |last| last + 1);
+    let lines = String::from_utf8_lossy(&appended[..complete])
+        .lines()
+        .map(str::to_string)
+        .collect();
+    Ok((lines, offset + complete as u64))
+}
+
+/// Print the log tail, then keep printing new lines with `follow`
+pub async fn run(follow: bool, lines: usize) -> Result<()> {
+    let path = get_log_file_path();
+    if !path.exists() {
+        bail!(
+            "No log file at {} (bal writes it only when running as a daemon)",
+            path.display()
+        );
+    }
+
+    let (tail, mut offset) = tail_lines(&path, lines)
+        .with_context(|| format!("Failed to read log file: {}", path.display()))?;
+    for line in tail {
+        println!("{}", line);
+    }
+
+    if !follow {
+        return Ok(());
+    }
+    loop {
+        tokio::time::sleep(FOLLOW_POLL_INTERVAL).await;
+        let (appended, next) = read_appended(&path, offset)
+            .with_context(|| format!("Failed to read log file: {}", path.display()))?;
+        for line in appended {
+            println!("{}", line);
+        }
+        offset = next;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn tail_returns_the_last_lines_and_follow_picks_up_appends() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bal.log");
+        // Long enough that the tail spans several backward chunks
+        let mut file = File::create(&path).unwrap();
+        for i in 0..2_000 {
+            writeln!(file, "{{\"level\":\"INFO\",\"message\":\"line {}\"}}", i).unwrap();
+        }
+
+        let (tail, offset) = tail_lines(&path, 3).unwrap();
+        assert_eq!(
+            tail,
+            [
+                "{\"level\":\"INFO\",\"message\":\"line 1997\"}",
+                "{\"level\":\"INFO\",\"message\":\"line 1998\"}",
+                "{\"level\":\"INFO\",\"message\":\"line 1999\"}",
+            ]
+        );
+        assert_eq!(offset, std::fs::metadata(&path).unwrap().len());
+        assert_eq!(tail_lines(&path, 5_000).unwrap().0.len(), 2_000);
+        assert!(tail_lines(&path, 0).unwrap().0.is_empty());
+
+        // Only complete lines are followed; the partial one waits
+        write!(file, "appended\npartial").unwrap();
+        let (appended, next) = read_appended(&path, offset).unwrap();
+        assert_eq!(appended, ["appended"]);
+        writeln!(file, " line").unwrap();
+        assert_eq!(read_appended(&path, next).unwrap().0, ["partial line"]);
+
+        // Truncation (e.g. rotation) restarts from the top
+        std::fs::write(&path, "fresh\n").unwrap();
+        assert_eq!(read_appended(&path, next).unwrap().0, ["fresh"]);
+    }
+}
@@ -14,23 +14,38 @@ mod backend_pool;
 mod check;
 mod cli;
 mod config;
+mod config_edit;
 mod config_store;
+mod config_watch;
+mod control;
 mod constants;
+mod dns_cache;
 mod doctor;
 mod error;
 mod health;
+mod keepalive;
 mod load_balancer;
 mod logging;
+mod logs;
 mod operator_message;
+mod output;
+mod preflight;
 mod process;
 mod protection;
 mod proxy;
+mod reaper;
+mod runtime_stats;
+mod sni;
 mod state;
 mod supervisor;
+mod top;
+mod upstream;
+mod version;
 
-use cli::{Cli, Commands};
+use cli::{Cli, Commands, MaintenanceMode};
 use config::Config;
-use constants::get_pid_file_path;
+use config_store::StartupRetry;
+use output::OutputFormat;
 use process::ProcessManager;
 
 /// Fork and detach process to run as daemon
@@ -52,43 +67,68 @@ fn fork_daemon() -> Result<()> {
     }
 }
 
+/// Exit with the documented code for a non-OK check/doctor outcome
+fn exit_with(status: check::ExitStatus) {
+    if status != check::ExitStatus::Ok {
+        std::process::exit(status.code());
+    }
+}
+
 /// Run async logic with the pre-parsed command
-async fn run_with_command(command: Commands, daemon_mode: bool) -> Result<()> {
+///
+/// `log_level` (`--log-level`) and `verbose` (`--verbose`) override the
+/// config's log_level for this run.
+async fn run_with_command(
+    command: Commands,
+    daemon_mode: bool,
+    log_level: Option<String>,
+    verbose: bool,
+) -> Result<()> {
     // For Start command, load config first to get log_level
-    let log_level = match &command {
+    let config_level = match &command {
         Commands::Start {
             config: cli_config, ..
         } => {
             // Try to load config to get log_level
             match Config::resolve_config_path(cli_config.as_deref()) {
-                Ok(config_path) => {
-                    match Config::load(&config_path).await {
-                        Ok(config) => config.log_level,
-                        Err(_) => "info".to_string(), // Default if config fails to load
-                    }
-                }
-                Err(_) => "info".to_string(), // Default if no config found
+                Ok(config_path) => Config::load(&config_path)
+                    .await
+                    .ok()
+                    .map(|config| config.log_level),
+                Err(_) => None, // Default if no config found
             }
         }
-        _ => "info".to_string(), // Default for non-start commands
+        // Keep `status --quiet` silent unless --log-level asks otherwise
+        Commands::Status { quiet: true, .. } => Some("error".to_string()),
+        _ => None, // Default for non-start commands
     };
+    let log_level = logging::effective_log_level(config_level, log_level, verbose);
 
-    // Initialize logging system with config's log_level
+    // Initialize logging system with the effective log_level
     logging::init_logging(&log_level, daemon_mode)?;
 
     log::info!("bal v{} starting", env!("CARGO_PKG_VERSION"));
 
     // Dispatch subcommands
     match command {
-        Commands::Start { config, daemon } => {
+        Commands::Start {
+            config,
+            daemon,
+            startup_config_retries,
+            startup_config_retry_ms,
+        } => {
+            let retry = StartupRetry {
+                retries: startup_config_retries,
+                delay: std::time::Duration::from_millis(startup_config_retry_ms),
+            };
             if daemon {
                 // Already forked, run daemon logic
                 log::info!("Starting in daemon mode");
-                supervisor::run_daemon(config.as_deref()).await?;
+                supervisor::run_daemon(config.as_deref(), retry).await?;
             } else {
                 // Run in foreground
                 log::info!("Starting in foreground mode");
-                supervisor::run_foreground(config.as_deref()).await?;
+                supervisor::run_foreground(config.as_deref(), retry).await?;
             }
         }
         Commands::Stop => {
@@ -96,37 +136,117 @@ async fn run_with_command(command: Commands, daemon_mode: bool) -> Result<()> {
             log::info!("Stopping running bal process");
             ProcessManager::stop_daemon()?;
         }
-        Commands::Graceful => {
+        Commands::Graceful { config } => {
             // Zero-downtime config reload (send SIGHUP signal)
             log::info!("Reloading configuration gracefully");
-            ProcessManager::send_reload_signal()?;
+            ProcessManager::send_reload_signal(config.as_deref())?;
+        }
+        Commands::Maintenance { mode } => {
+            ProcessManager::set_maintenance(mode == MaintenanceMode::On)?;
+        }
+        Commands::LogLevel { level } => {
+            ProcessManager::send_log_level_signal(level.as_deref())?;
+        }
+        Commands::ResetStats => {
+            ProcessManager::request_reset_stats()?;
         }
         Commands::Check {
             config,
             strict,
             json,
+            format,
             verbose,
+            reload,
         } => {
             log::info!("Running static config check");
-            check::run_and_print(config, strict, json, verbose).await?;
+            let format = OutputFormat::resolve(format, json);
+            let status = check::run_and_print(config, strict, format, verbose, reload).await?;
+            exit_with(status);
         }
         Commands::Status {
             config,
             json,
+            format,
             brief,
             verbose,
+            label,
+            backend,
+            quiet,
         } => {
+            if quiet {
+                let status =
+                    ProcessManager::status_exit_code(config, label.as_ref(), backend.as_deref())
+                        .await;
+                std::process::exit(status.code());
+            }
             log::info!("Showing bal state status");
-            ProcessManager::print_status(config, json, verbose && !brief).await?;
+            // Backend filters only affect backend details, so show them
+            let verbose = (verbose || label.is_some() || backend.is_some()) && !brief;
+            ProcessManager::print_status(
+                config,
+                OutputFormat::resolve(format, json),
+                verbose,
+                label.as_ref(),
+                backend.as_deref(),
+            )
+            .await?;
         }
         Commands::Doctor {
             config,
             json,
+            format,
             brief,
             verbose,
         } => {
             log::info!("Running bal doctor diagnostics");
-            doctor::run_and_print(config, json, verbose && !brief).await?;
+            let format = OutputFormat::resolve(format, json);
+            let status = doctor::run_and_print(config, format, verbose && !brief).await?;
+            exit_with(status);
+        }
+        Commands::Preflight {
+            config,
+            json,
+            format,
+            verbose,
+        } => {
+            let format = OutputFormat::resolve(format, json);
+            let status = preflight::run_and_print(config, format, verbose).await?;
+            exit_with(status);
+        }
+        Commands::TopBackends { config, by, limit } => {
+            top::run(config, by, limit).await?;
+        }
+        Commands::Logs { follow, lines } => {
+            logs::run(follow, lines).await?;
+        }
+        Commands::Version { json } => {
+            version::print(json)?;
+        }
+        Commands::Schema => {
+            println!("{}", serde_json::to_string_pretty(&Config::json_schema())?);
+        }
+        Commands::Set {
+            key,
+            value,
+            config,
+            reload,
+        } => {
+            config_edit::run_set(config, &key, &value, reload)?;
+        }
+        Commands::AddBackend {
+            address,
+            weight,
+            config,
+            reload,
+        } => {
+            config_edit::run_add_backend(config, &address, weight, reload)?;
+        }
+        Commands::RemoveBackend {
+            address,
+            config,
+            reload,
+        } => {
+            config_edit::run_remove_backend(config, &address, reload)?;
         }
     }
 
@@ -142,12 +262,35 @@ fn main() -> Result<()> {
     // Determine if running in daemon mode
     let daemon_mode = matches!(cli.command, Commands::Start { daemon: true, .. });
 
+    if let Some(dir) = cli.runtime_dir.clone() {
+        constants::set_runtime_dir_override(dir);
+    }
+    if let Some(name) = cli.instance.clone() {
+        constants::set_instance_name(name);
+    }
+    // Pin the runtime dir now: a relative BAL_RUNTIME_DIR must keep meaning
+    // the launch directory after fork_daemon() changes into /tmp
+    constants::set_runtime_dir_override(constants::get_runtime_dir());
+
+    // Check the runtime dir while errors can still reach the terminal
+    if matches!(cli.command, Commands::Start { .. }) {
+        ProcessManager::ensure_runtime_dir_writable(&constants::get_runtime_dir())?;
+    }
+
     // Fork to background if daemon mode (BEFORE initializing tokio runtime)
     if daemon_mode {
         fork_daemon()?;
     }
 
+    // Broken backend writes must surface as EPIPE, not terminate bal
+    supervisor::ignore_sigpipe()?;
+
     // Create tokio runtime manually after potential fork
     let rt = tokio::runtime::Runtime::new()?;
-    rt.block_on(run_with_command(cli.command, daemon_mode))
+    rt.block_on(run_with_command(
+        cli.command,
+        daemon_mode,
+        cli.log_level,
+        cli.verbose,
+    ))
 }
@@ -0,0 +1,134 @@
+//! Report output formats
+//!
+//! `check`, `status` and `doctor` print either their human text report or
+//! the report struct serialized for tooling. `--format` picks one;
+//! the older per-command `--json` flag still maps to `--format json`.
+
+use anyhow::Result;
+use clap::ValueEnum;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable report
+    #[default]
+    Text,
+    Json,
+    Yaml,
+}
+
+impl OutputFormat {
+    /// Combine `--format` with the deprecated `--json` flag (an explicit `--format` wins)
+    pub fn resolve(format: Option<OutputFormat>, json: bool) -> Self {
+        match format {
+            Some(format) => format,
+            None if json => OutputFormat::Json,
+            None => OutputFormat::Text,
+        }
+    }
+
+    /// Serialize `report` for machine formats; `None` means print the text report
+    pub fn serialize<T: Serialize>(self, report: &T) -> Result<Option<String>> {
+        match self {
+            OutputFormat::Text => Ok(None),
+            OutputFormat::Json => Ok(Some(serde_json::to_string_pretty(report)?)),
+            OutputFormat::Yaml => Ok(Some(serde_yaml::to_string(report)?.trim_end().to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::{BackendCheck, CheckReport, Connectivity};
+    use crate::doctor::{CheckLevel, DoctorCheck, DoctorReport};
+    use crate::process::{BackendStatusSummary, ProcessStatusSummary, ProtectionModeSummary};
+
+    fn yaml_round_trip<T: Serialize + serde::de::DeserializeOwned>(report: &T) -> T {
+        let rendered = OutputFormat::Yaml
+            .serialize(report)
+            .unwrap()
+            .expect("yaml output");
+        serde_yaml::from_str(&rendered).expect("yaml output should parse back")
+    }
+
+    #[test]
+    fn json_flag_is_an_alias_unless_format_is_given() {
+        assert_eq!(OutputFormat::resolve(None, false), OutputFormat::Text);
+        assert_eq!(OutputFormat::resolve(None, true), OutputFormat::Json);
+        assert_eq!(
+            OutputFormat::resolve(Some(OutputFormat::Yaml), true),
+            OutputFormat::Yaml
+        );
+        assert!(OutputFormat::Text.serialize(&1).unwrap().is_none());
+    }
+
+    #[test]
+    fn yaml_output_round_trips_into_report_structs() {
+        let check = CheckReport {
+            config_path: "/tmp/bal.yaml".to_string(),
+            errors: Vec::new(),
+            warnings: vec!["bind_address is 0.0.0.0".to_string()],
+            backend_count: 1,
+            backends: vec![BackendCheck {
+                address: "127.0.0.1:9000".to_string(),
+                connectivity: Connectivity::Unreachable,
+                error: Some("Connection timeout".to_string()),
+            }],
+            runtime: Default::default(),
+            runtime_source: Default::default(),
+        };
+        let parsed = yaml_round_trip(&check);
+        assert_eq!(parsed.warnings, check.warnings);
+        assert_eq!(parsed.backends[0].connectivity, Connectivity::Unreachable);
+        assert_eq!(parsed.backends[0].error, check.backends[0].error);
+
+        let doctor = DoctorReport {
+            checks: vec![DoctorCheck {
+                name: "bind".to_string(),
+                level: CheckLevel::Critical,
+                summary: "address is already in use".to_string(),
+                hint: None,
+            }],
+            protection_mode: ProtectionModeSummary {
+                enabled: false,
+                reason: None,
+            },
+        };
+        assert_eq!(yaml_round_trip(&doctor).checks, doctor.checks);
+
+        let status = ProcessStatusSummary {
+            running: true,
+            pid: Some(42),
+            config_path: Some("/tmp/bal.yaml".to_string()),
+            bind_address: "0.0.0.0".to_string(),
+            port: Some(9295),
+            method: Some("round_robin".to_string()),
+            backend_total: Some(1),
+            backend_reachable: Some(1),
+            backends: vec![BackendStatusSummary {
+                active_connections: 3,
+                ..BackendStatusSummary::idle("127.0.0.1:9000")
+            }],
+            draining_backends: Vec::new(),
+            active_connections: 3,
+            overload_rejections_total: 0,
+            connection_utilization: Some(0.25),
+            connections_total: 0,
+            bytes_total: 0,
+            rates: None,
+            maintenance: false,
+            active_tier: None,
+            last_check_time: "2026-01-01T00:00:00Z".to_string(),
+            protection_mode: ProtectionModeSummary {
+                enabled: false,
+                reason: None,
+            },
+        };
+        let parsed = yaml_round_trip(&status);
+        assert_eq!(parsed.pid, Some(42));
+        assert_eq!(parsed.backends[0].address, "127.0.0.1:9000");
+        assert_eq!(parsed.backends[0].active_connections, 3);
+        assert_eq!(parsed.connection_utilization, Some(0.25));
+    }
+}
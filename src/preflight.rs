@@ -0,0 +1,201 @@
+//! `bal preflight`
+//!
+//! One deploy gate: runs `bal check` (static config validation) and
+//! `bal doctor` (bindability, backend connectivity) and folds them into a
+//! single report and exit code.
+
+use anyhow::Result;
+use serde::Serialize;
+use std::path::PathBuf;
+
+use crate::check::{self, CheckReport, ExitStatus};
+use crate::doctor::{self, CheckLevel, DoctorReport};
+use crate::output::OutputFormat;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PreflightReport {
+    pub passed: bool,
+    /// Static check results; absent when the config couldn't be checked at all
+    pub check: Option<CheckReport>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub check_error: Option<String>,
+    pub doctor: DoctorReport,
+    #[serde(skip)]
+    status: ExitStatus,
+}
+
+impl PreflightReport {
+    /// Merge both results; the worst outcome decides the exit status
+    pub fn new(check: Result<CheckReport>, doctor: DoctorReport) -> Self {
+        let statuses = [ExitStatus::for_check(&check, false), doctor.exit_status()];
+        let status = if statuses.contains(&ExitStatus::ConfigNotFound) {
+            ExitStatus::ConfigNotFound
+        } else if statuses.contains(&ExitStatus::Errors) {
+            ExitStatus::Errors
+        } else {
+            ExitStatus::Ok
+        };
+
+        let (check, check_error) = match check {
+            Ok(report) => (Some(report), None),
+            Err(err) => (None, Some(format!("{:#}", err))),
+        };
+        Self {
+            passed: status == ExitStatus::Ok,
+            check,
+            check_error,
+            doctor,
+            status,
+        }
+    }
+
+    pub fn exit_status(&self) -> ExitStatus {
+        self.status
+    }
+
+    pub fn to_plain_text(&self, verbose: bool) -> String {
+        let mut lines = vec![
+            "bal preflight".to_string(),
+            format!(
+                "  overall: {}",
+                if self.passed { "PASSED" } else { "FAILED" }
+            ),
+        ];
+
+        match (&self.check, &self.check_error) {
+            (Some(report), _) => lines.push(format!(
+                "  check: {} error(s), {} warning(s)",
+                report.errors.len(),
+                report.warnings.len()
+            )),
+            (None, Some(err)) => lines.push(format!("  check: failed: {}", err)),
+            (None, None) => {}
+        }
+
+        let count = |level: CheckLevel| {
+            self.doctor
+                .checks
+                .iter()
+                .filter(|check| check.level == level)
+                .count()
+        };
+        lines.push(format!(
+            "  doctor: {} critical, {} warning(s)",
+            count(CheckLevel::Critical),
+            count(CheckLevel::Warn)
+        ));
+
+        if verbose {
+            if let Some(report) = &self.check {
+                lines.push(String::new());
+                lines.push(report.to_plain_text(true));
+            }
+            lines.push(String::new());
+            lines.push(self.doctor.to_plain_text(true));
+        } else if !self.passed {
+            lines.push("  next: run 'bal preflight --verbose' for the failing checks".to_string());
+        }
+
+        lines.join("\n")
+    }
+}
+
+/// Run check and doctor against the same config, probing each backend once
+pub async fn run_preflight(config_path: Option<PathBuf>) -> PreflightReport {
+    let check = check::run_check(config_path.clone(), false).await;
+    let probes = check.as_ref().ok().map(|report| report.backends.clone());
+    let doctor = doctor::run_doctor_with(config_path, probes).await;
+    PreflightReport::new(check, doctor)
+}
+
+/// Print the preflight report and return the exit status `main` should use
+pub async fn run_and_print(
+    config_path: Option<PathBuf>,
+    format: OutputFormat,
+    verbose: bool,
+) -> Result<ExitStatus> {
+    let report = run_preflight(config_path).await;
+
+    match format.serialize(&report)? {
+        Some(rendered) => println!("{}", rendered),
+        None => println!("{}", report.to_plain_text(verbose)),
+    }
+
+    let status = report.exit_status();
+    if status != ExitStatus::Ok {
+        eprintln!("preflight failed");
+    }
+    Ok(status)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::doctor::DoctorCheck;
+    use crate::process::ProtectionModeSummary;
+
+    fn check_report(errors: Vec<String>) -> CheckReport {
+        CheckReport {
+            config_path: "/tmp/bal.yaml".to_string(),
+            errors,
+            warnings: vec!["bind_address is 0.0.0.0".to_string()],
+            backend_count: 1,
+            backends: Vec::new(),
+            runtime: Default::default(),
+            runtime_source: Default::default(),
+        }
+    }
+
+    fn doctor_report(levels: &[(&str, CheckLevel)]) -> DoctorReport {
+        DoctorReport {
+            checks: levels
+                .iter()
+                .map(|(name, level)| DoctorCheck {
+                    name: name.to_string(),
+                    level: level.clone(),
+                    summary: "summary".to_string(),
+                    hint: None,
+                })
+                .collect(),
+            protection_mode: ProtectionModeSummary {
+                enabled: false,
+                reason: None,
+            },
+        }
+    }
+
+    #[test]
+    fn healthy_environment_passes_and_any_critical_failure_fails() {
+        let healthy = [
+            ("config", CheckLevel::Ok),
+            ("bind", CheckLevel::Ok),
+            ("backends", CheckLevel::Warn),
+        ];
+        let report = PreflightReport::new(Ok(check_report(Vec::new())), doctor_report(&healthy));
+        assert!(report.passed);
+        assert_eq!(report.exit_status(), ExitStatus::Ok);
+        assert!(report.to_plain_text(false).contains("overall: PASSED"));
+
+        let unbindable = [("config", CheckLevel::Ok), ("bind", CheckLevel::Critical)];
+        let report = PreflightReport::new(Ok(check_report(Vec::new())), doctor_report(&unbindable));
+        assert!(!report.passed);
+        assert_eq!(report.exit_status(), ExitStatus::Errors);
+
+        let invalid = check_report(vec!["port must be greater than 0".to_string()]);
+        let report = PreflightReport::new(Ok(invalid), doctor_report(&healthy));
+        assert_eq!(report.exit_status(), ExitStatus::Errors);
+
+        let report = PreflightReport::new(
+            Err(anyhow::anyhow!("Configuration file load failed")),
+            doctor_report(&healthy),
+        );
+        assert!(!report.passed);
+        let json: serde_json::Value = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["passed"], false);
+        assert!(json["check"].is_null());
+        assert!(json["check_error"]
+            .as_str()
+            .unwrap()
+            .contains("load failed"));
+    }
+}
@@ -7,46 +7,111 @@
 use anyhow::{bail, Result};
 use nix::sys::signal::{self, Signal};
 use nix::unistd::Pid;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process;
 
-use crate::config::Config;
-use crate::constants::{get_pid_file_path, get_runtime_dir};
+use crate::config::{is_dns_no_addresses, unix_listen_path, Config, LabelSelector};
+use crate::constants::{
+    get_log_level_request_path, get_maintenance_path, get_pid_file_path, get_reload_request_path,
+    get_reset_stats_request_path, get_runtime_dir, instance_name, RUNTIME_DIR_ENV,
+};
 use crate::error::ResultExt;
 use crate::operator_message::render_operator_message;
+use crate::output::OutputFormat;
 use crate::protection;
+use crate::runtime_stats::{self, Rates, RuntimeSnapshot};
 
 /// Process manager
 ///
 /// Identifies and controls daemon process via PID file.
 pub struct ProcessManager;
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct BackendErrorCounters {
     pub timeout: u64,
     pub refused: u64,
     pub other: u64,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BackendStatusSummary {
     pub address: String,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub labels: BTreeMap<String, String>,
     pub reachable: bool,
     pub active_connections: usize,
+    /// Skipped by the balancer after recent connect failures, even if reachable
+    pub in_cooldown: bool,
+    pub cooldown_remaining_ms: u64,
+    /// Reachable but deprioritized after sustained saturation
+    pub degraded: bool,
+    /// Most recent connect/health probe error seen by the daemon
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_error_at: Option<String>,
+    /// Why the probe failed when it's not a plain connect error (e.g. `dns_no_addresses`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unhealthy_reason: Option<String>,
     pub last_check_time: String,
     pub counters: BackendErrorCounters,
+    /// Bytes relayed by finished sessions to this backend
+    #[serde(default)]
+    pub bytes_total: u64,
+    /// When the daemon last saw this backend flip healthy <-> unhealthy
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_transition_at: Option<String>,
+    /// Health transitions within `flap_window_ms`; high = flapping
+    #[serde(default)]
+    pub flap_count: u32,
+    /// Failover tier from the config (0 = primary)
+    #[serde(default)]
+    pub tier: u32,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[cfg(test)]
+impl BackendStatusSummary {
+    /// Reachable, unlabeled backend with no connections or errors
+    pub(crate) fn idle(address: &str) -> Self {
+        Self {
+            address: address.to_string(),
+            labels: BTreeMap::new(),
+            reachable: true,
+            active_connections: 0,
+            in_cooldown: false,
+            cooldown_remaining_ms: 0,
+            degraded: false,
+            last_error: None,
+            last_error_at: None,
+            unhealthy_reason: None,
+            last_check_time: "2026-01-01T00:00:00Z".to_string(),
+            counters: BackendErrorCounters::default(),
+            bytes_total: 0,
+            last_transition_at: None,
+            flap_count: 0,
+            tier: 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DrainingBackendSummary {
+    pub address: String,
+    pub active_connections: usize,
+    pub draining_since: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProtectionModeSummary {
     pub enabled: bool,
     pub reason: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProcessStatusSummary {
     pub running: bool,
     pub pid: Option<i32>,
@@ -57,18 +122,88 @@ pub struct ProcessStatusSummary {
     pub backend_total: Option<usize>,
     pub backend_reachable: Option<usize>,
     pub backends: Vec<BackendStatusSummary>,
+    pub draining_backends: Vec<DrainingBackendSummary>,
     pub active_connections: usize,
+    pub overload_rejections_total: u64,
+    /// active_connections / max_concurrent_connections, when the daemon reports it
+    pub connection_utilization: Option<f64>,
+    #[serde(default)]
+    pub connections_total: u64,
+    #[serde(default)]
+    pub bytes_total: u64,
+    /// Averaged over `rate_window_ms`; absent until the daemon has two samples
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rates: Option<Rates>,
+    /// New clients are refused while `bal maintenance on` is in effect
+    #[serde(default)]
+    pub maintenance: bool,
+    /// Lowest failover tier with a reachable backend; only set when the
+    /// config uses tiers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active_tier: Option<u32>,
     pub last_check_time: String,
     pub protection_mode: ProtectionModeSummary,
 }
 
+/// Exit codes of `bal status --quiet`, for monitoring without JSON parsing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatusExitCode {
+    /// 0: running and every (shown) backend is reachable
+    Healthy,
+    /// 1: running, but a backend is unreachable or the backends are unknown
+    Degraded,
+    /// 2: no daemon is running
+    NotRunning,
+}
+
+impl StatusExitCode {
+    pub fn code(self) -> i32 {
+        match self {
+            StatusExitCode::Healthy => 0,
+            StatusExitCode::Degraded => 1,
+            StatusExitCode::NotRunning => 2,
+        }
+    }
+
+    pub fn for_summary(summary: &ProcessStatusSummary) -> Self {
+        if !summary.running {
+            StatusExitCode::NotRunning
+        } else if !summary.backends.is_empty()
+            && summary.backends.iter().all(|backend| backend.reachable)
+        {
+            StatusExitCode::Healthy
+        } else {
+            StatusExitCode::Degraded
+        }
+    }
+}
+
 impl ProcessManager {
+    /// Fail early with an actionable error when `dir` can't hold bal's state
+    ///
+    /// PID, log and snapshot writes all land in the runtime directory; a
+    /// missing `$HOME` or a read-only home (common in containers) would
+    /// otherwise surface later as an opaque I/O error.
+    pub fn ensure_runtime_dir_writable(dir: &Path) -> Result<()> {
+        let probe = dir.join(format!(".write_probe.{}", process::id()));
+        let result = fs::create_dir_all(dir).and_then(|_| fs::write(&probe, b""));
+        let _ = fs::remove_file(&probe);
+        if let Err(e) = result {
+            bail!(
+                "Runtime directory {} is not writable ({}). Set {} to a writable directory, e.g. {}=/tmp/bal",
+                dir.display(),
+                e,
+                RUNTIME_DIR_ENV,
+                RUNTIME_DIR_ENV
+            );
+        }
+        Ok(())
+    }
+
     /// Write current process PID to file
     ///
     /// If PID file already exists, considers it a duplicate execution and returns error.
     pub fn write_pid_file() -> Result<()> {
-        let pid_path = get_pid_file_path();
-
         // Create runtime directory
         let runtime_dir = get_runtime_dir();
         std::fs::create_dir_all(&runtime_dir).context_process(&format!(
@@ -76,24 +211,29 @@ impl ProcessManager {
             runtime_dir.display()
         ))?;
 
+        Self::write_pid_file_at(&get_pid_file_path())
+    }
+
+    fn write_pid_file_at(pid_path: &Path) -> Result<()> {
         // Check existing PID file
         if pid_path.exists() {
             // Check if existing process is running
-            if let Ok(old_pid) = Self::read_pid_file() {
+            if let Ok(old_pid) = Self::read_pid_file_at(pid_path) {
                 if Self::is_process_running(old_pid) {
                     bail!(
-                        "bal is already running (PID: {}). Run 'bal stop' first.",
-                        old_pid
+                        "bal is already running (PID: {}). Run '{}' first.",
+                        old_pid,
+                        instance_command("bal stop")
                     );
                 }
             }
             // Remove file if not running
-            let _ = fs::remove_file(&pid_path);
+            let _ = fs::remove_file(pid_path);
         }
 
         // Write new PID file
         let pid = process::id();
-        let mut file = fs::File::create(&pid_path).context_process(&format!(
+        let mut file = fs::File::create(pid_path).context_process(&format!(
             "Failed to create PID file: {}",
             pid_path.display()
         ))?;
@@ -107,9 +247,11 @@ impl ProcessManager {
 
     /// Read PID from PID file
     pub fn read_pid_file() -> Result<i32> {
-        let pid_path = get_pid_file_path();
+        Self::read_pid_file_at(&get_pid_file_path())
+    }
 
-        let content = fs::read_to_string(&pid_path)
+    fn read_pid_file_at(pid_path: &Path) -> Result<i32> {
+        let content = fs::read_to_string(pid_path)
             .context_process(&format!("Failed to read PID file: {}", pid_path.display()))?;
 
         let pid: i32 = content
@@ -122,10 +264,12 @@ impl ProcessManager {
 
     /// Remove PID file
     pub fn remove_pid_file() -> Result<()> {
-        let pid_path = get_pid_file_path();
+        Self::remove_pid_file_at(&get_pid_file_path())
+    }
 
+    fn remove_pid_file_at(pid_path: &Path) -> Result<()> {
         if pid_path.exists() {
-            fs::remove_file(&pid_path).context_process(&format!(
+            fs::remove_file(pid_path).context_process(&format!(
                 "Failed to remove PID file: {}",
                 pid_path.display()
             ))?;
@@ -177,14 +321,30 @@ impl ProcessManager {
     /// Send configuration reload signal (SIGHUP)
     ///
     /// Sends SIGHUP signal to running daemon to reload configuration
-    /// without downtime.
-    pub fn send_reload_signal() -> Result<()> {
+    /// without downtime. When `new_config` is given, its absolute path is
+    /// handed to the daemon via the reload request file first, so the daemon
+    /// switches to that file instead of re-reading the current one.
+    pub fn send_reload_signal(new_config: Option<&Path>) -> Result<()> {
         let pid = Self::read_pid_file().context_process("Cannot find running bal process.")?;
 
         if !Self::is_process_running(pid) {
             bail!("bal is not running. Clean up the PID file and try again.");
         }
 
+        if let Some(path) = new_config {
+            // The daemon runs from a different working directory, so pass an absolute path
+            let absolute = fs::canonicalize(path).context_process(&format!(
+                "Cannot access configuration file: {}",
+                path.display()
+            ))?;
+            let request_path = get_reload_request_path();
+            fs::write(&request_path, absolute.display().to_string()).context_process(&format!(
+                "Failed to write reload request: {}",
+                request_path.display()
+            ))?;
+            log::info!("Requested config switch to {}", absolute.display());
+        }
+
         // Send SIGHUP signal
         let nix_pid = Pid::from_raw(pid);
         signal::kill(nix_pid, Signal::SIGHUP)
@@ -197,6 +357,89 @@ impl ProcessManager {
         Ok(())
     }
 
+    /// Turn maintenance mode on or off for the running daemon
+    ///
+    /// Toggles the maintenance flag file; the daemon picks the change up
+    /// within `MAINTENANCE_POLL_INTERVAL_MS`.
+    pub fn set_maintenance(enabled: bool) -> Result<()> {
+        let pid = Self::read_pid_file().context_process("Cannot find running bal process.")?;
+
+        if !Self::is_process_running(pid) {
+            bail!("bal is not running. Clean up the PID file and try again.");
+        }
+
+        let flag_path = get_maintenance_path();
+        if enabled {
+            fs::write(&flag_path, pid.to_string()).context_process(&format!(
+                "Failed to write maintenance flag: {}",
+                flag_path.display()
+            ))?;
+            println!("Maintenance mode on: new client connections will be refused");
+        } else {
+            match fs::remove_file(&flag_path) {
+                Ok(()) => {}
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => bail!(
+                    "Failed to remove maintenance flag {}: {}",
+                    flag_path.display(),
+                    e
+                ),
+            }
+            println!("Maintenance mode off: accepting client connections");
+        }
+        Ok(())
+    }
+
+    /// Change the running daemon's log level (SIGUSR2)
+    ///
+    /// With `level`, the daemon switches to it via the log level request
+    /// file; without, it toggles debug logging on or back off.
+    pub fn send_log_level_signal(level: Option<&str>) -> Result<()> {
+        let pid = Self::read_pid_file().context_process("Cannot find running bal process.")?;
+
+        if !Self::is_process_running(pid) {
+            bail!("bal is not running. Clean up the PID file and try again.");
+        }
+
+        if let Some(level) = level {
+            let request_path = get_log_level_request_path();
+            fs::write(&request_path, level).context_process(&format!(
+                "Failed to write log level request: {}",
+                request_path.display()
+            ))?;
+        }
+
+        let nix_pid = Pid::from_raw(pid);
+        signal::kill(nix_pid, Signal::SIGUSR2)
+            .map_err(|e| anyhow::anyhow!("Failed to send SIGUSR2 to process {}: {}", pid, e))?;
+
+        match level {
+            Some(level) => println!("Requested log level {} (PID: {})", level, pid),
+            None => println!("Toggled debug logging (PID: {})", pid),
+        }
+        Ok(())
+    }
+
+    /// Ask the running daemon to zero its error and byte counters
+    ///
+    /// Drops a request file that the daemon consumes within
+    /// `MAINTENANCE_POLL_INTERVAL_MS`; health state is untouched.
+    pub fn request_reset_stats() -> Result<()> {
+        let pid = Self::read_pid_file().context_process("Cannot find running bal process.")?;
+
+        if !Self::is_process_running(pid) {
+            bail!("bal is not running. Clean up the PID file and try again.");
+        }
+
+        let request_path = get_reset_stats_request_path();
+        fs::write(&request_path, pid.to_string()).context_process(&format!(
+            "Failed to write stats reset request: {}",
+            request_path.display()
+        ))?;
+        println!("Requested stats reset: error and byte counters will restart from zero");
+        Ok(())
+    }
+
     /// Check daemon running status
     pub fn is_daemon_running() -> bool {
         match Self::read_pid_file() {
@@ -231,9 +474,17 @@ impl ProcessManager {
             backend_total: None,
             backend_reachable: None,
             backends: Vec::new(),
+            draining_backends: Vec::new(),
             active_connections: 0,
+            overload_rejections_total: 0,
+            connection_utilization: None,
+            connections_total: 0,
+            bytes_total: 0,
+            rates: None,
+            maintenance: false,
+            active_tier: None,
             last_check_time: chrono::Utc::now().to_rfc3339(),
-            protection_mode: current_protection_mode(),
+            protection_mode: current_protection_mode(running),
         };
 
         if let Some(path) = resolved_config_path {
@@ -243,8 +494,14 @@ impl ProcessManager {
                     let mut backend_summaries = Vec::new();
                     let check_time = chrono::Utc::now().to_rfc3339();
 
-                    for backend in &config.backends {
+                    for backend in config.enabled_backends() {
                         let result = backend.check_connectivity().await;
+                        let unhealthy_reason = match &result {
+                            Err(err) if is_dns_no_addresses(err) => {
+                                Some("dns_no_addresses".to_string())
+                            }
+                            _ => None,
+                        };
                         let (is_reachable, counters) = match result {
                             Ok(_) => (
                                 true,
@@ -292,24 +549,42 @@ impl ProcessManager {
                         }
 
                         backend_summaries.push(BackendStatusSummary {
-                            address: format!("{}:{}", backend.host, backend.port),
+                            address: backend.address(),
+                            labels: backend.labels.clone(),
                             reachable: is_reachable,
                             active_connections: 0,
+                            in_cooldown: false,
+                            cooldown_remaining_ms: 0,
+                            degraded: false,
+                            last_error: None,
+                            last_error_at: None,
+                            unhealthy_reason,
                             last_check_time: check_time.clone(),
                             counters,
+                            bytes_total: 0,
+                            last_transition_at: None,
+                            flap_count: 0,
+                            tier: backend.tier,
                         });
                     }
 
                     summary.bind_address = config.bind_address;
                     summary.port = Some(config.port);
                     summary.method = Some(config.method.to_string());
-                    summary.backend_total = Some(config.backends.len());
+                    summary.backend_total = Some(backend_summaries.len());
                     summary.backend_reachable = Some(reachable);
                     summary.backends = backend_summaries;
                 }
             }
         }
 
+        if running {
+            if let Some(snapshot) = runtime_stats::read_snapshot() {
+                apply_runtime_snapshot(&mut summary, snapshot);
+            }
+        }
+        summary.active_tier = active_tier(&summary.backends);
+
         Ok(summary)
     }
 
@@ -323,10 +598,13 @@ impl ProcessManager {
             .pid
             .map(|pid| format!("{} (pid={})", running_text, pid))
             .unwrap_or_else(|| running_text.to_string());
-        let listen_text = summary
-            .port
-            .map(|port| format!("{}:{}", summary.bind_address, port))
-            .unwrap_or_else(|| "-".to_string());
+        let listen_text = match summary.port {
+            Some(_) if unix_listen_path(&summary.bind_address).is_some() => {
+                summary.bind_address.clone()
+            }
+            Some(port) => format!("{}:{}", summary.bind_address, port),
+            None => "-".to_string(),
+        };
         let method_text = summary.method.clone().unwrap_or_else(|| "-".to_string());
         let backend_text = match (summary.backend_reachable, summary.backend_total) {
             (Some(reachable), Some(total)) => format!("{}/{} reachable", reachable, total),
@@ -364,6 +642,22 @@ impl ProcessManager {
                     .unwrap_or_default()
             ),
         ];
+        if let Some(tier) = summary.active_tier {
+            lines.push(if tier == 0 {
+                "  active_tier: 0 (primary)".to_string()
+            } else {
+                format!(
+                    "  active_tier: {} (failover; every lower tier is down)",
+                    tier
+                )
+            });
+        }
+        if summary.maintenance {
+            lines.push(
+                "  maintenance: on (new clients are refused; 'bal maintenance off' resumes)"
+                    .to_string(),
+            );
+        }
 
         if !verbose {
             if !summary.running {
@@ -407,21 +701,78 @@ impl ProcessManager {
             "  active_connections: {}",
             summary.active_connections
         ));
+        lines.push(format!(
+            "  overload_rejections_total: {}",
+            summary.overload_rejections_total
+        ));
+        if let Some(utilization) = summary.connection_utilization {
+            lines.push(format!(
+                "  connection_utilization: {:.1}%",
+                utilization * 100.0
+            ));
+        }
+        if let Some(rates) = summary.rates {
+            lines.push(format!(
+                "  rates: {:.1} conn/s, {:.0} bytes/s (over {:.1}s)",
+                rates.connections_per_sec,
+                rates.bytes_per_sec,
+                rates.window_ms as f64 / 1000.0
+            ));
+        }
         lines.push(format!("  last_check_time: {}", summary.last_check_time));
 
         if !summary.backends.is_empty() {
             lines.push("  backend_details:".to_string());
             for backend in &summary.backends {
+                let mut cooldown = if backend.in_cooldown {
+                    format!(" cooldown_remaining={}ms", backend.cooldown_remaining_ms)
+                } else {
+                    String::new()
+                };
+                if backend.degraded {
+                    cooldown.push_str(" degraded=true");
+                }
+                if backend.tier > 0 {
+                    cooldown.push_str(&format!(" tier={}", backend.tier));
+                }
+                if let Some(reason) = &backend.unhealthy_reason {
+                    cooldown.push_str(&format!(" reason={}", reason));
+                }
+                if backend.flap_count > 0 {
+                    cooldown.push_str(&format!(" flaps={}", backend.flap_count));
+                }
+                if let Some(at) = &backend.last_transition_at {
+                    cooldown.push_str(&format!(" last_transition={}", at));
+                }
                 lines.push(format!(
-                    "    - {} reachable={} active={} last_check={} counters(timeout={}, refused={}, other={})",
+                    "    - {}{} reachable={} active={}{} last_check={} counters(timeout={}, refused={}, other={})",
                     backend.address,
+                    format_labels(&backend.labels),
                     backend.reachable,
                     backend.active_connections,
+                    cooldown,
                     backend.last_check_time,
                     backend.counters.timeout,
                     backend.counters.refused,
                     backend.counters.other
                 ));
+                if let Some(last_error) = &backend.last_error {
+                    lines.push(format!(
+                        "      last_error: {} at {}",
+                        last_error,
+                        backend.last_error_at.as_deref().unwrap_or("-")
+                    ));
+                }
+            }
+        }
+
+        if !summary.draining_backends.is_empty() {
+            lines.push("  draining_backends:".to_string());
+            for draining in &summary.draining_backends {
+                lines.push(format!(
+                    "    - {} active={} since={}",
+                    draining.address, draining.active_connections, draining.draining_since
+                ));
             }
         }
 
@@ -450,23 +801,117 @@ impl ProcessManager {
         lines.join("\n")
     }
 
+    /// Keep only backend details matching the label selector
+    ///
+    /// Totals and reachability counts still describe the whole fleet.
+    pub fn filter_backends_by_label(summary: &mut ProcessStatusSummary, selector: &LabelSelector) {
+        summary
+            .backends
+            .retain(|backend| selector.matches(&backend.labels));
+    }
+
+    /// Keep only the backend whose `host:port` matches `address`
+    ///
+    /// Fails, leaving the summary untouched, when no backend matches.
+    pub fn filter_backends_by_address(
+        summary: &mut ProcessStatusSummary,
+        address: &str,
+    ) -> Result<()> {
+        if !summary
+            .backends
+            .iter()
+            .any(|backend| backend.address == address)
+        {
+            let known: Vec<&str> = summary
+                .backends
+                .iter()
+                .map(|backend| backend.address.as_str())
+                .collect();
+            bail!(
+                "No backend matches '{}'. Configured backends: {}",
+                address,
+                if known.is_empty() {
+                    "(none)".to_string()
+                } else {
+                    known.join(", ")
+                }
+            );
+        }
+
+        summary
+            .backends
+            .retain(|backend| backend.address == address);
+        Ok(())
+    }
+
+    /// Status narrowed to the backends `--label`/`--backend` select
+    async fn collect_filtered_status(
+        config_path: Option<PathBuf>,
+        label: Option<&LabelSelector>,
+        backend: Option<&str>,
+    ) -> Result<ProcessStatusSummary> {
+        let mut summary = Self::collect_status(config_path).await?;
+        if let Some(selector) = label {
+            Self::filter_backends_by_label(&mut summary, selector);
+        }
+        if let Some(address) = backend {
+            Self::filter_backends_by_address(&mut summary, address)?;
+        }
+        Ok(summary)
+    }
+
+    /// `bal status --quiet`: health of the (filtered) status as an exit code
+    ///
+    /// A status that can't be collected counts as degraded while the daemon
+    /// runs, so scripts never mistake it for healthy.
+    pub async fn status_exit_code(
+        config_path: Option<PathBuf>,
+        label: Option<&LabelSelector>,
+        backend: Option<&str>,
+    ) -> StatusExitCode {
+        match Self::collect_filtered_status(config_path, label, backend).await {
+            Ok(summary) => StatusExitCode::for_summary(&summary),
+            Err(_) if Self::is_daemon_running() => StatusExitCode::Degraded,
+            Err(_) => StatusExitCode::NotRunning,
+        }
+    }
+
     pub async fn print_status(
         config_path: Option<PathBuf>,
-        json: bool,
+        format: OutputFormat,
         verbose: bool,
+        label: Option<&LabelSelector>,
+        backend: Option<&str>,
     ) -> Result<()> {
-        let summary = Self::collect_status(config_path).await?;
-        if json {
-            println!("{}", serde_json::to_string_pretty(&summary)?);
-        } else {
-            println!("{}", Self::build_status_report(summary, verbose));
+        let summary = Self::collect_filtered_status(config_path, label, backend).await?;
+        match format.serialize(&summary)? {
+            Some(rendered) => println!("{}", rendered),
+            None => println!("{}", Self::build_status_report(summary, verbose)),
         }
         Ok(())
     }
 }
 
-fn current_protection_mode() -> ProtectionModeSummary {
-    if let Some(snapshot) = protection::read_snapshot() {
+/// `command` plus the `--instance` this process targets, for operator hints
+fn instance_command(command: &str) -> String {
+    match instance_name() {
+        Some(name) => format!("{} --instance {}", command, name),
+        None => command.to_string(),
+    }
+}
+
+/// Render labels as ` [k=v, k=v]`, or nothing when unlabeled
+fn format_labels(labels: &BTreeMap<String, String>) -> String {
+    if labels.is_empty() {
+        return String::new();
+    }
+
+    let pairs: Vec<String> = labels.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+    format!(" [{}]", pairs.join(", "))
+}
+
+fn current_protection_mode(daemon_running: bool) -> ProtectionModeSummary {
+    if let Some(snapshot) = protection::read_current_snapshot(daemon_running) {
         return ProtectionModeSummary {
             enabled: snapshot.enabled,
             reason: snapshot.reason,
@@ -479,6 +924,73 @@ fn current_protection_mode() -> ProtectionModeSummary {
     }
 }
 
+/// Tier currently taking traffic: the lowest one with a reachable backend
+///
+/// None when every backend is in tier 0 (no tiers configured) or nothing is
+/// reachable.
+fn active_tier(backends: &[BackendStatusSummary]) -> Option<u32> {
+    if backends.iter().all(|backend| backend.tier == 0) {
+        return None;
+    }
+    backends
+        .iter()
+        .filter(|backend| backend.reachable)
+        .map(|backend| backend.tier)
+        .min()
+}
+
+/// Fill in live values only the daemon knows from its runtime stats snapshot
+fn apply_runtime_snapshot(summary: &mut ProcessStatusSummary, snapshot: RuntimeSnapshot) {
+    summary.active_connections = snapshot.active_connections;
+    summary.overload_rejections_total = snapshot.overload_rejections_total;
+    summary.connections_total = snapshot.connections_total;
+    summary.bytes_total = snapshot.bytes_total;
+    summary.rates = snapshot.rates;
+    summary.maintenance = snapshot.maintenance;
+    if snapshot.max_concurrent_connections > 0 {
+        summary.connection_utilization =
+            Some(snapshot.active_connections as f64 / snapshot.max_concurrent_connections as f64);
+    }
+    summary.draining_backends = snapshot
+        .draining
+        .into_iter()
+        .map(|entry| DrainingBackendSummary {
+            address: entry.address,
+            active_connections: entry.active_connections,
+            draining_since: chrono::DateTime::from_timestamp_millis(entry.since_ms as i64)
+                .map(|ts| ts.to_rfc3339())
+                .unwrap_or_else(|| "-".to_string()),
+        })
+        .collect();
+
+    let now_ms = crate::backend_pool::BackendState::now_unix_ms();
+    for backend in &mut summary.backends {
+        if let Some(live) = snapshot
+            .backends
+            .iter()
+            .find(|entry| entry.address == backend.address)
+        {
+            backend.active_connections = live.active_connections;
+            backend.cooldown_remaining_ms = live.cooldown_until_ms.saturating_sub(now_ms);
+            backend.in_cooldown = backend.cooldown_remaining_ms > 0;
+            backend.degraded = live.degraded;
+            backend.bytes_total = live.bytes_total;
+            backend.flap_count = live.flap_count;
+            if live.last_transition_ms > 0 {
+                backend.last_transition_at =
+                    chrono::DateTime::from_timestamp_millis(live.last_transition_ms as i64)
+                        .map(|ts| ts.to_rfc3339());
+            }
+            if let Some(last_error) = &live.last_error {
+                backend.last_error = Some(last_error.message.clone());
+                backend.last_error_at =
+                    chrono::DateTime::from_timestamp_millis(last_error.at_ms as i64)
+                        .map(|ts| ts.to_rfc3339());
+            }
+        }
+    }
+}
+
 /// Cleanup guard - PID file auto-cleanup using RAII pattern
 ///
 /// Automatically cleans up PID file on normal/abnormal process termination.
@@ -516,17 +1028,22 @@ mod tests {
             backend_total: Some(1),
             backend_reachable: Some(0),
             backends: vec![BackendStatusSummary {
-                address: "127.0.0.1:9000".to_string(),
                 reachable: false,
-                active_connections: 0,
-                last_check_time: "2026-01-01T00:00:00Z".to_string(),
                 counters: BackendErrorCounters {
                     timeout: 1,
-                    refused: 0,
-                    other: 0,
+                    ..Default::default()
                 },
+                ..BackendStatusSummary::idle("127.0.0.1:9000")
             }],
+            draining_backends: Vec::new(),
             active_connections: 0,
+            overload_rejections_total: 0,
+            connection_utilization: None,
+            connections_total: 0,
+            bytes_total: 0,
+            rates: None,
+            maintenance: false,
+            active_tier: None,
             last_check_time: "2026-01-01T00:00:00Z".to_string(),
             protection_mode: ProtectionModeSummary {
                 enabled: false,
@@ -556,17 +1073,18 @@ mod tests {
                 backend_total: Some(2),
                 backend_reachable: Some(1),
                 backends: vec![BackendStatusSummary {
-                    address: "127.0.0.1:9000".to_string(),
-                    reachable: true,
                     active_connections: 3,
-                    last_check_time: "2026-01-01T00:00:00Z".to_string(),
-                    counters: BackendErrorCounters {
-                        timeout: 0,
-                        refused: 0,
-                        other: 0,
-                    },
+                    ..BackendStatusSummary::idle("127.0.0.1:9000")
                 }],
+                draining_backends: Vec::new(),
                 active_connections: 3,
+                overload_rejections_total: 0,
+                connection_utilization: None,
+                connections_total: 0,
+                bytes_total: 0,
+                rates: None,
+                maintenance: false,
+                active_tier: None,
                 last_check_time: "2026-01-01T00:00:00Z".to_string(),
             },
             true,
@@ -596,7 +1114,15 @@ mod tests {
                 backend_total: Some(2),
                 backend_reachable: Some(0),
                 backends: Vec::new(),
+                draining_backends: Vec::new(),
                 active_connections: 0,
+                overload_rejections_total: 0,
+                connection_utilization: None,
+                connections_total: 0,
+                bytes_total: 0,
+                rates: None,
+                maintenance: false,
+                active_tier: None,
                 last_check_time: "2026-01-01T00:00:00Z".to_string(),
             },
             true,
@@ -624,18 +1150,16 @@ mod tests {
                 method: Some("round_robin".to_string()),
                 backend_total: Some(1),
                 backend_reachable: Some(1),
-                backends: vec![BackendStatusSummary {
-                    address: "127.0.0.1:9000".to_string(),
-                    reachable: true,
-                    active_connections: 0,
-                    last_check_time: "2026-01-01T00:00:00Z".to_string(),
-                    counters: BackendErrorCounters {
-                        timeout: 0,
-                        refused: 0,
-                        other: 0,
-                    },
-                }],
+                backends: vec![BackendStatusSummary::idle("127.0.0.1:9000")],
+                draining_backends: Vec::new(),
                 active_connections: 0,
+                overload_rejections_total: 0,
+                connection_utilization: None,
+                connections_total: 0,
+                bytes_total: 0,
+                rates: None,
+                maintenance: false,
+                active_tier: None,
                 last_check_time: "2026-01-01T00:00:00Z".to_string(),
             },
             false,
@@ -666,7 +1190,15 @@ mod tests {
                 backend_total: Some(1),
                 backend_reachable: Some(0),
                 backends: Vec::new(),
+                draining_backends: Vec::new(),
                 active_connections: 0,
+                overload_rejections_total: 0,
+                connection_utilization: None,
+                connections_total: 0,
+                bytes_total: 0,
+                rates: None,
+                maintenance: false,
+                active_tier: None,
                 last_check_time: "2026-01-01T00:00:00Z".to_string(),
             },
             false,
@@ -676,4 +1208,231 @@ mod tests {
         assert!(report.contains("why_likely:"));
         assert!(report.contains("do_this_now:"));
     }
+
+    /// Running daemon with one us-east and one us-west backend
+    fn two_zone_summary() -> ProcessStatusSummary {
+        let backend = |address: &str, zone: &str| BackendStatusSummary {
+            labels: BTreeMap::from([("zone".to_string(), zone.to_string())]),
+            ..BackendStatusSummary::idle(address)
+        };
+        ProcessStatusSummary {
+            protection_mode: ProtectionModeSummary {
+                enabled: false,
+                reason: None,
+            },
+            running: true,
+            pid: Some(4242),
+            config_path: None,
+            bind_address: "0.0.0.0".to_string(),
+            port: Some(9295),
+            method: Some("round_robin".to_string()),
+            backend_total: Some(2),
+            backend_reachable: Some(2),
+            backends: vec![
+                backend("10.0.0.1:9000", "us-east"),
+                backend("10.0.1.1:9000", "us-west"),
+            ],
+            draining_backends: Vec::new(),
+            active_connections: 0,
+            overload_rejections_total: 0,
+            connection_utilization: None,
+            connections_total: 0,
+            bytes_total: 0,
+            rates: None,
+            maintenance: false,
+            active_tier: None,
+            last_check_time: "2026-01-01T00:00:00Z".to_string(),
+        }
+    }
+
+    #[test]
+    fn quiet_status_exit_code_encodes_health() {
+        let healthy = two_zone_summary();
+        assert_eq!(StatusExitCode::for_summary(&healthy).code(), 0);
+
+        let mut degraded = two_zone_summary();
+        degraded.backends[1].reachable = false;
+        assert_eq!(StatusExitCode::for_summary(&degraded).code(), 1);
+        // Backends unknown (e.g. config unreadable) is not "all reachable"
+        degraded.backends.clear();
+        assert_eq!(StatusExitCode::for_summary(&degraded).code(), 1);
+
+        let mut stopped = two_zone_summary();
+        stopped.running = false;
+        assert_eq!(StatusExitCode::for_summary(&stopped).code(), 2);
+    }
+
+    #[test]
+    fn filter_backends_by_label_keeps_matching_details_only() {
+        let mut summary = two_zone_summary();
+
+        let selector: LabelSelector = "zone=us-east".parse().unwrap();
+        ProcessManager::filter_backends_by_label(&mut summary, &selector);
+
+        assert_eq!(summary.backends.len(), 1);
+        assert_eq!(summary.backend_total, Some(2));
+        let report = ProcessManager::build_status_report(summary, true);
+        assert!(report.contains("10.0.0.1:9000 [zone=us-east] reachable=true"));
+        assert!(!report.contains("10.0.1.1:9000"));
+    }
+
+    #[test]
+    fn active_tier_is_the_lowest_tier_with_a_reachable_backend() {
+        let mut summary = two_zone_summary();
+        assert_eq!(active_tier(&summary.backends), None);
+
+        summary.backends[1].tier = 1;
+        assert_eq!(active_tier(&summary.backends), Some(0));
+
+        summary.backends[0].reachable = false;
+        summary.active_tier = active_tier(&summary.backends);
+        assert_eq!(summary.active_tier, Some(1));
+        let report = ProcessManager::build_status_report(summary, true);
+        assert!(report.contains("active_tier: 1 (failover"));
+        assert!(report.contains("10.0.1.1:9000 [zone=us-west] reachable=true active=0 tier=1"));
+    }
+
+    #[test]
+    fn filter_backends_by_address_keeps_one_backend() {
+        let mut summary = two_zone_summary();
+        ProcessManager::filter_backends_by_address(&mut summary, "10.0.1.1:9000")
+            .expect("backend should match");
+
+        assert_eq!(summary.backends.len(), 1);
+        assert_eq!(summary.backends[0].address, "10.0.1.1:9000");
+        assert_eq!(summary.backend_total, Some(2));
+    }
+
+    #[test]
+    fn filter_backends_by_address_errors_when_nothing_matches() {
+        let mut summary = two_zone_summary();
+        let err = ProcessManager::filter_backends_by_address(&mut summary, "10.9.9.9:9000")
+            .expect_err("unknown backend should be an error");
+
+        let message = err.to_string();
+        assert!(message.contains("10.9.9.9:9000"));
+        assert!(message.contains("10.0.0.1:9000, 10.0.1.1:9000"));
+        assert_eq!(summary.backends.len(), 2);
+    }
+
+    #[test]
+    fn apply_runtime_snapshot_reports_backend_cooldown_remaining() {
+        use crate::backend_pool::{BackendErrorKind, BackendState};
+        use crate::runtime_stats::BackendSnapshot;
+
+        let config: crate::config::BackendConfig =
+            serde_yaml::from_str("host: 127.0.0.1\nport: 9000").unwrap();
+        let backend = BackendState::new(config);
+        backend.mark_connect_failure(
+            BackendErrorKind::ConnectionRefused,
+            3,
+            100,
+            1000,
+            5000,
+            false,
+        );
+
+        let mut summary = ProcessStatusSummary {
+            protection_mode: ProtectionModeSummary {
+                enabled: false,
+                reason: None,
+            },
+            running: true,
+            pid: Some(4242),
+            config_path: None,
+            bind_address: "0.0.0.0".to_string(),
+            port: Some(9295),
+            method: Some("round_robin".to_string()),
+            backend_total: Some(1),
+            backend_reachable: Some(1),
+            backends: Vec::new(),
+            draining_backends: Vec::new(),
+            active_connections: 0,
+            overload_rejections_total: 0,
+            connection_utilization: None,
+            connections_total: 0,
+            bytes_total: 0,
+            rates: None,
+            maintenance: false,
+            active_tier: None,
+            last_check_time: "2026-01-01T00:00:00Z".to_string(),
+        };
+        summary.backends = vec![BackendStatusSummary::idle("127.0.0.1:9000")];
+        let snapshot = RuntimeSnapshot {
+            backends: vec![BackendSnapshot {
+                address: backend.address(),
+                active_connections: 2,
+                cooldown_until_ms: backend.cooldown_until_ms(),
+                degraded: true,
+                last_error: Some(crate::backend_pool::LastError {
+                    message: "Connection failed: Connection refused (os error 111)".to_string(),
+                    at_ms: 1_767_225_600_000,
+                }),
+                bytes_total: 4096,
+                last_transition_ms: 1_767_225_600_000,
+                flap_count: 4,
+            }],
+            ..RuntimeSnapshot::default()
+        };
+
+        apply_runtime_snapshot(&mut summary, snapshot);
+
+        let reported = &summary.backends[0];
+        assert!(reported.in_cooldown);
+        assert!(reported.cooldown_remaining_ms > 0 && reported.cooldown_remaining_ms <= 5000);
+        assert_eq!(reported.active_connections, 2);
+        assert_eq!(reported.bytes_total, 4096);
+        assert!(reported.degraded);
+        let report = ProcessManager::build_status_report(summary, true);
+        assert!(report.contains("cooldown_remaining="));
+        assert!(report.contains("degraded=true"));
+        assert!(report.contains("flaps=4 last_transition=2026-01-01T00:00:00+00:00"));
+        assert!(report.contains(
+            "last_error: Connection failed: Connection refused (os error 111) at 2026-01-01T00:00:00+00:00"
+        ));
+    }
+
+    #[test]
+    fn runtime_dir_writability_check_names_the_override() {
+        let dir = tempfile::tempdir().unwrap();
+        let runtime_dir = dir.path().join("nested").join(".bal");
+        ProcessManager::ensure_runtime_dir_writable(&runtime_dir).unwrap();
+        assert!(runtime_dir.is_dir());
+        // The probe file doesn't linger
+        assert_eq!(fs::read_dir(&runtime_dir).unwrap().count(), 0);
+
+        // A file where the directory should be can't be written under
+        let blocker = dir.path().join("not-a-dir");
+        fs::write(&blocker, "").unwrap();
+        let err = ProcessManager::ensure_runtime_dir_writable(&blocker.join(".bal"))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("is not writable"));
+        assert!(err.contains(RUNTIME_DIR_ENV));
+    }
+
+    #[test]
+    fn named_instances_keep_independent_pid_files() {
+        use crate::constants::{instance_file_name, PID_FILENAME};
+
+        let dir = tempfile::tempdir().unwrap();
+        let pid_path = |instance| dir.path().join(instance_file_name(PID_FILENAME, instance));
+        let (web, db) = (pid_path(Some("web")), pid_path(Some("db")));
+
+        // This process is "running" as web; db can still start alongside it
+        ProcessManager::write_pid_file_at(&web).unwrap();
+        fs::write(&db, "999999999\n").unwrap();
+        ProcessManager::write_pid_file_at(&db).unwrap();
+        let err = ProcessManager::write_pid_file_at(&web).unwrap_err();
+        assert!(err.to_string().contains("already running"));
+
+        // Stopping one instance leaves the other's PID file untouched
+        ProcessManager::remove_pid_file_at(&db).unwrap();
+        assert!(ProcessManager::read_pid_file_at(&db).is_err());
+        assert_eq!(
+            ProcessManager::read_pid_file_at(&web).unwrap(),
+            process::id() as i32
+        );
+        assert!(!pid_path(None).exists());
+    }
 }
@@ -3,7 +3,9 @@ use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
 
 use crate::backend_pool::BackendErrorKind;
-use crate::constants::get_runtime_dir;
+use crate::constants::{
+    get_runtime_dir, get_runtime_file_path, PROTECTION_STATE_FILENAME, STALE_SNAPSHOT_AFTER_MS,
+};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct ProtectionSnapshot {
@@ -12,60 +14,116 @@ pub struct ProtectionSnapshot {
     pub updated_at_ms: u64,
 }
 
+impl ProtectionSnapshot {
+    /// Left behind by a run that's gone: no daemon is running and the
+    /// snapshot hasn't been written for `STALE_SNAPSHOT_AFTER_MS`
+    pub fn is_stale(&self, now_ms: u64, daemon_running: bool) -> bool {
+        !daemon_running && now_ms.saturating_sub(self.updated_at_ms) > STALE_SNAPSHOT_AFTER_MS
+    }
+}
+
+/// Trip point for one kind of failure storm
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StormLimits {
+    pub threshold: u32,
+    pub window_ms: u64,
+}
+
+/// Failures counted in a fixed window that restarts once it has elapsed
+#[derive(Debug)]
+struct StormCounter {
+    count: AtomicU32,
+    window_started_ms: AtomicU64,
+    limits: StormLimits,
+}
+
+impl StormCounter {
+    fn new(limits: StormLimits) -> Self {
+        Self {
+            count: AtomicU32::new(0),
+            window_started_ms: AtomicU64::new(now_unix_ms()),
+            limits,
+        }
+    }
+
+    /// Count one failure; `true` once the window reaches the threshold
+    fn hit(&self, now: u64) -> bool {
+        let window_start = self.window_started_ms.load(Ordering::Relaxed);
+        if now.saturating_sub(window_start) > self.limits.window_ms {
+            self.window_started_ms.store(now, Ordering::Relaxed);
+            self.count.store(0, Ordering::Relaxed);
+        }
+
+        self.count.fetch_add(1, Ordering::Relaxed) + 1 >= self.limits.threshold
+    }
+
+    fn reset(&self, now: u64) {
+        self.count.store(0, Ordering::Relaxed);
+        self.window_started_ms.store(now, Ordering::Relaxed);
+    }
+}
+
 #[derive(Debug)]
 pub struct ProtectionMode {
     enabled: AtomicBool,
-    timeout_refused_window_count: AtomicU32,
-    window_started_ms: AtomicU64,
+    /// Timeouts and refusals together
+    timeout_refused: StormCounter,
+    /// Timeouts alone (network trouble), when configured
+    timeout: Option<StormCounter>,
+    /// Refusals alone (service down), when configured
+    refused: Option<StormCounter>,
     stable_success_count: AtomicU32,
     reason_code: AtomicU32,
-    threshold: u32,
-    window_ms: u64,
     stable_recoveries_required: u32,
 }
 
 const REASON_NONE: u32 = 0;
 const REASON_TIMEOUT_REFUSED_STORM: u32 = 1;
 const REASON_ALL_BACKENDS_UNAVAILABLE: u32 = 2;
+const REASON_TIMEOUT_STORM: u32 = 3;
+const REASON_REFUSED_STORM: u32 = 4;
 
 impl ProtectionMode {
-    pub fn new(threshold: u32, window_ms: u64, stable_recoveries_required: u32) -> Self {
+    pub fn new(
+        threshold: u32,
+        window_ms: u64,
+        stable_recoveries_required: u32,
+        timeout: Option<StormLimits>,
+        refused: Option<StormLimits>,
+    ) -> Self {
         Self {
             enabled: AtomicBool::new(false),
-            timeout_refused_window_count: AtomicU32::new(0),
-            window_started_ms: AtomicU64::new(now_unix_ms()),
+            timeout_refused: StormCounter::new(StormLimits {
+                threshold,
+                window_ms,
+            }),
+            timeout: timeout.map(StormCounter::new),
+            refused: refused.map(StormCounter::new),
             stable_success_count: AtomicU32::new(0),
             reason_code: AtomicU32::new(REASON_NONE),
-            threshold,
-            window_ms,
             stable_recoveries_required,
         }
     }
 
     pub fn record_failure(&self, kind: BackendErrorKind) -> bool {
-        if matches!(
-            kind,
-            BackendErrorKind::Timeout | BackendErrorKind::ConnectionRefused
-        ) {
-            let now = now_unix_ms();
-            let window_start = self.window_started_ms.load(Ordering::Relaxed);
-            if now.saturating_sub(window_start) > self.window_ms {
-                self.window_started_ms.store(now, Ordering::Relaxed);
-                self.timeout_refused_window_count
-                    .store(0, Ordering::Relaxed);
-            }
-
-            let storm_count = self
-                .timeout_refused_window_count
-                .fetch_add(1, Ordering::Relaxed)
-                + 1;
-            self.stable_success_count.store(0, Ordering::Relaxed);
-
-            if storm_count >= self.threshold {
-                return self.enable(REASON_TIMEOUT_REFUSED_STORM);
-            }
-        } else {
-            self.stable_success_count.store(0, Ordering::Relaxed);
+        self.stable_success_count.store(0, Ordering::Relaxed);
+
+        let (own_counter, own_reason) = match kind {
+            BackendErrorKind::Timeout => (&self.timeout, REASON_TIMEOUT_STORM),
+            BackendErrorKind::ConnectionRefused => (&self.refused, REASON_REFUSED_STORM),
+            _ => return false,
+        };
+
+        let now = now_unix_ms();
+        // Both counters see every failure; the kind-specific one names the cause
+        let own_storm = own_counter.as_ref().is_some_and(|counter| counter.hit(now));
+        let combined_storm = self.timeout_refused.hit(now);
+
+        if own_storm {
+            return self.enable(own_reason);
+        }
+        if combined_storm {
+            return self.enable(REASON_TIMEOUT_REFUSED_STORM);
         }
 
         false
@@ -110,16 +168,28 @@ impl ProtectionMode {
     fn disable(&self) {
         self.enabled.store(false, Ordering::Relaxed);
         self.reason_code.store(REASON_NONE, Ordering::Relaxed);
-        self.timeout_refused_window_count
-            .store(0, Ordering::Relaxed);
-        self.window_started_ms
-            .store(now_unix_ms(), Ordering::Relaxed);
+        self.reset_window();
+    }
+
+    /// Restart every storm window from zero; the enabled flag is left alone
+    pub fn reset_window(&self) {
+        let now = now_unix_ms();
+        for counter in [
+            Some(&self.timeout_refused),
+            self.timeout.as_ref(),
+            self.refused.as_ref(),
+        ]
+        .into_iter()
+        .flatten()
+        {
+            counter.reset(now);
+        }
         self.stable_success_count.store(0, Ordering::Relaxed);
     }
 }
 
 pub fn protection_state_path() -> PathBuf {
-    get_runtime_dir().join("protection_state.json")
+    get_runtime_file_path(PROTECTION_STATE_FILENAME)
 }
 
 pub fn write_snapshot(snapshot: &ProtectionSnapshot) {
@@ -140,10 +210,21 @@ pub fn read_snapshot() -> Option<ProtectionSnapshot> {
     serde_json::from_slice(&content).ok()
 }
 
+/// The snapshot, unless it is a stale leftover (see `ProtectionSnapshot::is_stale`)
+pub fn read_current_snapshot(daemon_running: bool) -> Option<ProtectionSnapshot> {
+    read_snapshot().filter(|snapshot| !snapshot.is_stale(now_unix_ms(), daemon_running))
+}
+
+pub fn remove_snapshot() {
+    let _ = std::fs::remove_file(protection_state_path());
+}
+
 fn reason_label(code: u32) -> Option<String> {
     match code {
         REASON_TIMEOUT_REFUSED_STORM => Some("timeout_or_refused_storm".to_string()),
         REASON_ALL_BACKENDS_UNAVAILABLE => Some("all_backends_unavailable".to_string()),
+        REASON_TIMEOUT_STORM => Some("timeout_storm".to_string()),
+        REASON_REFUSED_STORM => Some("refused_storm".to_string()),
         _ => None,
     }
 }
@@ -159,9 +240,28 @@ fn now_unix_ms() -> u64 {
 mod tests {
     use super::*;
 
+    #[test]
+    fn stale_snapshots_are_ignored_only_without_a_daemon() {
+        let now = 1_767_225_600_000;
+        let snapshot = |age_ms: u64| ProtectionSnapshot {
+            enabled: true,
+            reason: Some("timeout_storm".to_string()),
+            updated_at_ms: now - age_ms,
+        };
+
+        // A crashed run's snapshot no longer reports protection mode
+        assert!(snapshot(STALE_SNAPSHOT_AFTER_MS + 1).is_stale(now, false));
+        // A recent one is honored, and so is any age while the daemon runs
+        // (it only rewrites the file when the mode flips)
+        assert!(!snapshot(1_000).is_stale(now, false));
+        assert!(!snapshot(STALE_SNAPSHOT_AFTER_MS * 10).is_stale(now, true));
+        // Clock skew putting the snapshot in the future isn't stale
+        assert!(!snapshot(0).is_stale(now - 5_000, false));
+    }
+
     #[test]
     fn enables_on_timeout_storm_and_recovers_after_stable_successes() {
-        let mode = ProtectionMode::new(2, 60_000, 2);
+        let mode = ProtectionMode::new(2, 60_000, 2, None, None);
 
         assert!(!mode.is_enabled());
         mode.record_failure(BackendErrorKind::Timeout);
@@ -178,11 +278,43 @@ mod tests {
 
     #[test]
     fn enables_immediately_when_all_backends_are_unavailable() {
-        let mode = ProtectionMode::new(10, 60_000, 3);
+        let mode = ProtectionMode::new(10, 60_000, 3, None, None);
         mode.record_global_unavailable();
 
         let snapshot = mode.snapshot();
         assert!(snapshot.enabled);
         assert_eq!(snapshot.reason.as_deref(), Some("all_backends_unavailable"));
     }
+
+    #[test]
+    fn timeout_and_refusal_storms_trip_on_their_own_thresholds() {
+        let timeout = Some(StormLimits {
+            threshold: 2,
+            window_ms: 60_000,
+        });
+        let refused = Some(StormLimits {
+            threshold: 3,
+            window_ms: 60_000,
+        });
+
+        // Timeout-only storm trips at the timeout threshold, well below the combined one
+        let mode = ProtectionMode::new(100, 60_000, 1, timeout, refused);
+        assert!(!mode.record_failure(BackendErrorKind::Timeout));
+        assert!(mode.record_failure(BackendErrorKind::Timeout));
+        assert_eq!(mode.snapshot().reason.as_deref(), Some("timeout_storm"));
+
+        // Refusal-only storm needs its own (higher) count; timeouts don't add to it
+        let mode = ProtectionMode::new(100, 60_000, 1, timeout, refused);
+        assert!(!mode.record_failure(BackendErrorKind::ConnectionRefused));
+        assert!(!mode.record_failure(BackendErrorKind::ConnectionRefused));
+        assert!(mode.record_failure(BackendErrorKind::ConnectionRefused));
+        assert_eq!(mode.snapshot().reason.as_deref(), Some("refused_storm"));
+
+        let mode = ProtectionMode::new(100, 60_000, 1, None, refused);
+        for _ in 0..10 {
+            mode.record_failure(BackendErrorKind::Timeout);
+        }
+        mode.record_failure(BackendErrorKind::ConnectionRefused);
+        assert!(!mode.is_enabled());
+    }
 }
@@ -0,0 +1,261 @@
+//! Idle session reaper
+//!
+//! Every proxy session registers its last-activity time here when it starts.
+//! A background task periodically closes sessions that moved no bytes for
+//! `connection_idle_timeout_ms`, independent of the relay's own timeout,
+//! which only fires while the relay future is being polled.
+
+use log::{debug, warn};
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio::sync::{broadcast, Notify};
+
+use crate::backend_pool::BackendState;
+use crate::state::AppState;
+
+/// Scan cadence bounds; the scan runs at a quarter of the idle timeout
+const MIN_SCAN_INTERVAL_MS: u64 = 10;
+const MAX_SCAN_INTERVAL_MS: u64 = 1000;
+
+/// Activity of one live proxy session
+#[derive(Debug)]
+pub struct SessionActivity {
+    last_activity_ms: AtomicU64,
+    reaped: AtomicBool,
+    forwarded: AtomicBool,
+    notify: Notify,
+}
+
+impl SessionActivity {
+    fn new() -> Self {
+        Self {
+            last_activity_ms: AtomicU64::new(BackendState::now_unix_ms()),
+            reaped: AtomicBool::new(false),
+            forwarded: AtomicBool::new(false),
+            notify: Notify::new(),
+        }
+    }
+
+    /// Record that bytes moved on the session
+    pub fn touch(&self) {
+        self.last_activity_ms
+            .store(BackendState::now_unix_ms(), Ordering::Relaxed);
+    }
+
+    /// Milliseconds since the session last moved bytes
+    pub fn idle_ms(&self, now_ms: u64) -> u64 {
+        now_ms.saturating_sub(self.last_activity_ms.load(Ordering::Relaxed))
+    }
+
+    /// Record that client bytes are about to reach a backend
+    ///
+    /// From here on the session is committed to that backend: a retry
+    /// elsewhere could deliver the same bytes twice.
+    pub fn mark_forwarded(&self) {
+        self.forwarded.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether any client bytes were handed to a backend yet
+    pub fn forwarded(&self) -> bool {
+        self.forwarded.load(Ordering::Relaxed)
+    }
+
+    /// Resolves once the reaper closed the session
+    pub async fn reaped(&self) {
+        if self.reaped.load(Ordering::Relaxed) {
+            return;
+        }
+        self.notify.notified().await;
+    }
+
+    fn reap(&self) -> bool {
+        if self.reaped.swap(true, Ordering::Relaxed) {
+            return false;
+        }
+        // notify_one keeps a permit if the session isn't waiting yet
+        self.notify.notify_one();
+        true
+    }
+}
+
+/// Live sessions keyed by registration order
+#[derive(Debug, Default)]
+pub struct SessionRegistry {
+    next_id: AtomicU64,
+    sessions: Mutex<HashMap<u64, Arc<SessionActivity>>>,
+}
+
+impl SessionRegistry {
+    /// Track a new session until the returned guard is dropped
+    pub fn register(self: &Arc<Self>) -> SessionGuard {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let activity = Arc::new(SessionActivity::new());
+        self.lock().insert(id, Arc::clone(&activity));
+        SessionGuard {
+            registry: Arc::clone(self),
+            id,
+            activity,
+        }
+    }
+
+    /// Number of sessions currently tracked
+    #[cfg(test)]
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Close sessions idle for `idle_timeout_ms` or longer; returns how many
+    pub fn reap_idle(&self, idle_timeout_ms: u64) -> usize {
+        let now = BackendState::now_unix_ms();
+        self.lock()
+            .values()
+            .filter(|activity| activity.idle_ms(now) >= idle_timeout_ms)
+            .filter(|activity| activity.reap())
+            .count()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<u64, Arc<SessionActivity>>> {
+        self.sessions.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Keeps a session registered for the reaper while it is alive
+#[derive(Debug)]
+pub struct SessionGuard {
+    registry: Arc<SessionRegistry>,
+    id: u64,
+    activity: Arc<SessionActivity>,
+}
+
+impl SessionGuard {
+    pub fn activity(&self) -> &Arc<SessionActivity> {
+        &self.activity
+    }
+}
+
+impl Drop for SessionGuard {
+    fn drop(&mut self) {
+        self.registry.lock().remove(&self.id);
+    }
+}
+
+/// Stream wrapper that marks its session active whenever bytes are read
+pub struct ActivityStream<S> {
+    inner: S,
+    activity: Arc<SessionActivity>,
+}
+
+impl<S> ActivityStream<S> {
+    pub fn new(inner: S, activity: Arc<SessionActivity>) -> Self {
+        Self { inner, activity }
+    }
+}
+
+impl<S: AsyncRead + Unpin> AsyncRead for ActivityStream<S> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        let this = self.get_mut();
+        let before = buf.filled().len();
+        let result = Pin::new(&mut this.inner).poll_read(cx, buf);
+        if buf.filled().len() > before {
+            this.activity.touch();
+        }
+        result
+    }
+}
+
+impl<S: AsyncWrite + Unpin> AsyncWrite for ActivityStream<S> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        Pin::new(&mut self.get_mut().inner).poll_write(cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_shutdown(cx)
+    }
+}
+
+/// Scan for idle sessions until shutdown
+pub async fn run(state: Arc<AppState>, mut shutdown: broadcast::Receiver<()>) {
+    loop {
+        let idle_timeout_ms = state.config().runtime_tuning.connection_idle_timeout_ms;
+        let scan_interval = Duration::from_millis(
+            (idle_timeout_ms / 4).clamp(MIN_SCAN_INTERVAL_MS, MAX_SCAN_INTERVAL_MS),
+        );
+
+        tokio::select! {
+            _ = tokio::time::sleep(scan_interval) => {
+                let reaped = state.sessions().reap_idle(idle_timeout_ms);
+                if reaped > 0 {
+                    warn!(
+                        "Closing {} session(s) idle for {}ms (connection_idle_timeout_ms)",
+                        reaped, idle_timeout_ms
+                    );
+                }
+            }
+            _ = shutdown.recv() => {
+                debug!("Idle session reaper received shutdown signal");
+                break;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::RuntimeTuning;
+    use crate::state::RuntimeConfig;
+    use tokio::time::timeout;
+
+    #[tokio::test]
+    async fn stalled_session_is_reaped_by_the_background_scanner() {
+        let state = AppState::for_test(RuntimeConfig::for_test(
+            Vec::new(),
+            RuntimeTuning {
+                connection_idle_timeout_ms: 100,
+                ..RuntimeTuning::default()
+            },
+        ));
+
+        let stalled = state.sessions().register();
+        let busy = state.sessions().register();
+        let reaper = tokio::spawn(run(Arc::clone(&state), state.subscribe_shutdown()));
+
+        // The busy session keeps moving bytes while the stalled one sits idle
+        let stalled_activity = Arc::clone(stalled.activity());
+        let reaped = timeout(Duration::from_secs(2), async {
+            loop {
+                busy.activity().touch();
+                tokio::select! {
+                    _ = stalled_activity.reaped() => break,
+                    _ = tokio::time::sleep(Duration::from_millis(20)) => {}
+                }
+            }
+        })
+        .await;
+        assert!(reaped.is_ok(), "stalled session should be reaped");
+        assert_eq!(state.sessions().reap_idle(100), 0);
+
+        drop(stalled);
+        assert_eq!(state.sessions().len(), 1);
+
+        state.trigger_shutdown();
+        reaper.await.unwrap();
+    }
+}
@@ -0,0 +1,251 @@
+//! Runtime stats snapshot
+//!
+//! The daemon periodically writes live state that only it can observe
+//! (e.g. draining backends) to the runtime directory so `bal status`,
+//! running as a separate process, can report it.
+//!
+//! Connection and byte totals only ever grow, so the daemon also keeps a
+//! short history of samples and publishes per-second rates averaged over
+//! `rate_window_ms`.
+
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::path::PathBuf;
+
+use crate::backend_pool::LastError;
+use crate::constants::{get_runtime_dir, get_runtime_file_path, RUNTIME_STATS_FILENAME};
+use crate::state::AppState;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DrainingSnapshot {
+    pub address: String,
+    pub active_connections: usize,
+    pub since_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BackendSnapshot {
+    pub address: String,
+    pub active_connections: usize,
+    /// Unix ms until which the backend is skipped after connect failures (0 = none)
+    pub cooldown_until_ms: u64,
+    /// Deprioritized after sustained saturation (see `backend_max_connections`)
+    #[serde(default)]
+    pub degraded: bool,
+    #[serde(default)]
+    pub last_error: Option<LastError>,
+    /// Bytes relayed by finished sessions
+    #[serde(default)]
+    pub bytes_total: u64,
+    /// Last healthy <-> unhealthy transition (unix ms, 0 = none)
+    #[serde(default)]
+    pub last_transition_ms: u64,
+    /// Transitions within `flap_window_ms`
+    #[serde(default)]
+    pub flap_count: u32,
+}
+
+/// Cumulative counters at one point in time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateSample {
+    pub at_ms: u64,
+    pub connections_total: u64,
+    pub bytes_total: u64,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct Rates {
+    pub connections_per_sec: f64,
+    pub bytes_per_sec: f64,
+    /// Time actually covered by the two samples (at most `rate_window_ms` plus one interval)
+    pub window_ms: u64,
+}
+
+impl Rates {
+    /// Per-second deltas between two samples; `None` when no time has passed
+    ///
+    /// Counters that went backwards (daemon restarted between samples) count as 0.
+    pub fn between(previous: &RateSample, current: &RateSample) -> Option<Self> {
+        let elapsed_ms = current.at_ms.checked_sub(previous.at_ms)?;
+        if elapsed_ms == 0 {
+            return None;
+        }
+        let per_sec = |before: u64, after: u64| {
+            after.saturating_sub(before) as f64 * 1000.0 / elapsed_ms as f64
+        };
+        Some(Self {
+            connections_per_sec: per_sec(previous.connections_total, current.connections_total),
+            bytes_per_sec: per_sec(previous.bytes_total, current.bytes_total),
+            window_ms: elapsed_ms,
+        })
+    }
+}
+
+/// Rolling sample history used to smooth rates over `rate_window_ms`
+#[derive(Debug, Default)]
+pub struct RateTracker {
+    samples: VecDeque<RateSample>,
+}
+
+impl RateTracker {
+    /// Record `sample` and return rates against the oldest sample still in the window
+    ///
+    /// The newest sample at or before the window start is kept as the baseline,
+    /// so the rate always covers at least `window_ms` once enough history exists.
+    pub fn observe(&mut self, sample: RateSample, window_ms: u64) -> Option<Rates> {
+        while self.samples.len() >= 2 && self.samples[1].at_ms + window_ms <= sample.at_ms {
+            self.samples.pop_front();
+        }
+        let rates = self
+            .samples
+            .front()
+            .and_then(|baseline| Rates::between(baseline, &sample));
+        self.samples.push_back(sample);
+        rates
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct RuntimeSnapshot {
+    #[serde(default)]
+    pub active_connections: usize,
+    #[serde(default)]
+    pub max_concurrent_connections: usize,
+    #[serde(default)]
+    pub overload_rejections_total: u64,
+    #[serde(default)]
+    pub connections_total: u64,
+    #[serde(default)]
+    pub bytes_total: u64,
+    #[serde(default)]
+    pub rates: Option<Rates>,
+    #[serde(default)]
+    pub backends: Vec<BackendSnapshot>,
+    #[serde(default)]
+    pub draining: Vec<DrainingSnapshot>,
+    /// `bal maintenance on` is in effect and new clients are refused
+    #[serde(default)]
+    pub maintenance: bool,
+    pub updated_at_ms: u64,
+}
+
+impl RuntimeSnapshot {
+    pub async fn capture(state: &AppState) -> Self {
+        let draining = state
+            .draining_backends()
+            .into_iter()
+            .map(|entry| DrainingSnapshot {
+                address: entry.backend.address(),
+                active_connections: entry.backend.active_connections(),
+                since_ms: entry.since_ms,
+            })
+            .collect();
+        let now_ms = crate::backend_pool::BackendState::now_unix_ms();
+        let flap_window_ms = state.config().runtime_tuning.flap_window_ms;
+        let backends = state
+            .backend_pool()
+            .all_backends()
+            .iter()
+            .map(|backend| BackendSnapshot {
+                address: backend.address(),
+                active_connections: backend.active_connections(),
+                cooldown_until_ms: backend.cooldown_until_ms(),
+                degraded: backend.is_degraded(),
+                last_error: backend.last_error(),
+                bytes_total: backend.bytes_total(),
+                last_transition_ms: backend.last_transition_ms(),
+                flap_count: backend.flap_count(flap_window_ms, now_ms),
+            })
+            .collect();
+
+        let sample = RateSample {
+            at_ms: now_ms,
+            connections_total: state.connections_total(),
+            bytes_total: state.bytes_total(),
+        };
+        let rates = state.observe_rates(sample, state.config().runtime_tuning.rate_window_ms);
+
+        Self {
+            active_connections: state.active_connections().await,
+            max_concurrent_connections: state.config().runtime_tuning.max_concurrent_connections,
+            overload_rejections_total: state.overload_rejections(),
+            connections_total: sample.connections_total,
+            bytes_total: sample.bytes_total,
+            rates,
+            backends,
+            draining,
+            maintenance: state.is_maintenance(),
+            updated_at_ms: now_ms,
+        }
+    }
+}
+
+pub fn runtime_stats_path() -> PathBuf {
+    get_runtime_file_path(RUNTIME_STATS_FILENAME)
+}
+
+pub fn write_snapshot(snapshot: &RuntimeSnapshot) {
+    let runtime_dir = get_runtime_dir();
+    if std::fs::create_dir_all(&runtime_dir).is_err() {
+        return;
+    }
+
+    let path = runtime_stats_path();
+    if let Ok(encoded) = serde_json::to_vec_pretty(snapshot) {
+        let _ = std::fs::write(path, encoded);
+    }
+}
+
+pub fn read_snapshot() -> Option<RuntimeSnapshot> {
+    let path = runtime_stats_path();
+    let content = std::fs::read(path).ok()?;
+    serde_json::from_slice(&content).ok()
+}
+
+pub fn remove_snapshot() {
+    let _ = std::fs::remove_file(runtime_stats_path());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(at_ms: u64, connections_total: u64, bytes_total: u64) -> RateSample {
+        RateSample {
+            at_ms,
+            connections_total,
+            bytes_total,
+        }
+    }
+
+    #[test]
+    fn rates_come_from_two_successive_snapshots() {
+        let mut tracker = RateTracker::default();
+        assert_eq!(tracker.observe(sample(1_000, 10, 4_000), 10_000), None);
+
+        let rates = tracker
+            .observe(sample(3_000, 30, 14_000), 10_000)
+            .expect("second sample yields a rate");
+        assert_eq!(rates.connections_per_sec, 10.0);
+        assert_eq!(rates.bytes_per_sec, 5_000.0);
+        assert_eq!(rates.window_ms, 2_000);
+
+        // Same timestamp or counters that went backwards never produce nonsense
+        assert_eq!(Rates::between(&sample(5, 1, 1), &sample(5, 2, 2)), None);
+        let reset = Rates::between(&sample(0, 50, 50), &sample(1_000, 5, 5)).unwrap();
+        assert_eq!(reset.connections_per_sec, 0.0);
+    }
+
+    #[test]
+    fn rate_window_drops_samples_older_than_the_window() {
+        let mut tracker = RateTracker::default();
+        tracker.observe(sample(0, 0, 0), 2_000);
+        tracker.observe(sample(1_000, 100, 0), 2_000);
+        tracker.observe(sample(2_000, 110, 0), 2_000);
+
+        // Baseline moves to t=1000: (120 - 100) over 2s, not (120 - 0) over 3s
+        let rates = tracker.observe(sample(3_000, 120, 0), 2_000).unwrap();
+        assert_eq!(rates.window_ms, 2_000);
+        assert_eq!(rates.connections_per_sec, 10.0);
+    }
+}
@@ -0,0 +1,266 @@
+//! TLS ClientHello SNI peeking
+//!
+//! With `sni_routes`, bal reads the client's first TLS record without
+//! terminating TLS, extracts the server name (SNI) and routes the session to
+//! the backends mapped to that hostname. The bytes read are replayed to the
+//! chosen backend before the relay starts, so the handshake is untouched.
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+use crate::config::LabelSelector;
+
+/// TLS record header: content type, legacy version, length
+const RECORD_HEADER_LEN: usize = 5;
+const CONTENT_TYPE_HANDSHAKE: u8 = 0x16;
+const HANDSHAKE_CLIENT_HELLO: u8 = 0x01;
+const EXTENSION_SERVER_NAME: u16 = 0x0000;
+const NAME_TYPE_HOST_NAME: u8 = 0x00;
+
+/// Largest TLS record plaintext (RFC 8446 5.1); a ClientHello fits in one
+const MAX_RECORD_LEN: usize = 16_384;
+
+/// What the client's first bytes say about the server name
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClientHelloSni {
+    /// The ClientHello record isn't complete yet
+    Incomplete,
+    /// Not a TLS ClientHello, or one without a host_name SNI
+    Absent,
+    /// Lowercased SNI hostname
+    Hostname(String),
+}
+
+/// Extract the SNI hostname from the start of a client stream
+pub fn parse_client_hello(data: &[u8]) -> ClientHelloSni {
+    if data.len() < RECORD_HEADER_LEN {
+        return if data.first().is_some_and(|t| *t != CONTENT_TYPE_HANDSHAKE) {
+            ClientHelloSni::Absent
+        } else {
+            ClientHelloSni::Incomplete
+        };
+    }
+    if data[0] != CONTENT_TYPE_HANDSHAKE {
+        return ClientHelloSni::Absent;
+    }
+
+    let record_len = usize::from(u16::from_be_bytes([data[3], data[4]]));
+    if record_len > MAX_RECORD_LEN {
+        return ClientHelloSni::Absent;
+    }
+    let Some(record) = data.get(RECORD_HEADER_LEN..RECORD_HEADER_LEN + record_len) else {
+        return ClientHelloSni::Incomplete;
+    };
+
+    match server_name(record) {
+        Some(name) => ClientHelloSni::Hostname(name),
+        None => ClientHelloSni::Absent,
+    }
+}
+
+/// Walk a ClientHello handshake message down to the server_name extension
+fn server_name(record: &[u8]) -> Option<String> {
+    let mut reader = Reader(record);
+    if reader.u8()? != HANDSHAKE_CLIENT_HELLO {
+        return None;
+    }
+    let hello_len = reader.u24()?;
+    let mut hello = Reader(reader.bytes(hello_len)?);
+
+    // legacy_version, random, session id, cipher suites, compression methods
+    hello.bytes(2 + 32)?;
+    let session_id_len = usize::from(hello.u8()?);
+    hello.bytes(session_id_len)?;
+    let cipher_suites_len = usize::from(hello.u16()?);
+    hello.bytes(cipher_suites_len)?;
+    let compression_len = usize::from(hello.u8()?);
+    hello.bytes(compression_len)?;
+
+    let extensions_len = usize::from(hello.u16()?);
+    let mut extensions = Reader(hello.bytes(extensions_len)?);
+    while !extensions.0.is_empty() {
+        let kind = extensions.u16()?;
+        let len = usize::from(extensions.u16()?);
+        let body = extensions.bytes(len)?;
+        if kind != EXTENSION_SERVER_NAME {
+            continue;
+        }
+
+        let mut list = Reader(body);
+        let list_len = usize::from(list.u16()?);
+        let mut names = Reader(list.bytes(list_len)?);
+        while !names.0.is_empty() {
+            let name_type = names.u8()?;
+            let name_len = usize::from(names.u16()?);
+            let name = names.bytes(name_len)?;
+            if name_type == NAME_TYPE_HOST_NAME {
+                let name = std::str::from_utf8(name).ok()?;
+                return Some(name.trim_end_matches('.').to_ascii_lowercase());
+            }
+        }
+        return None;
+    }
+    None
+}
+
+/// Bounds-checked big-endian cursor
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn bytes(&mut self, len: usize) -> Option<&'a [u8]> {
+        if self.0.len() < len {
+            return None;
+        }
+        let (head, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Some(head)
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        self.bytes(1).map(|b| b[0])
+    }
+
+    fn u16(&mut self) -> Option<u16> {
+        self.bytes(2).map(|b| u16::from_be_bytes([b[0], b[1]]))
+    }
+
+    fn u24(&mut self) -> Option<usize> {
+        self.bytes(3)
+            .map(|b| usize::from(b[0]) << 16 | usize::from(b[1]) << 8 | usize::from(b[2]))
+    }
+}
+
+/// Read the client's ClientHello, returning the bytes read and its SNI
+///
+/// Stops once the first record is complete, the client stops sending or
+/// `wait` runs out; anything but a hostname leaves `None`. The returned
+/// bytes must be forwarded to the backend before relaying.
+pub async fn read_client_hello<S>(client: &mut S, wait: Duration) -> (Vec<u8>, Option<String>)
+where
+    S: AsyncRead + Unpin,
+{
+    let mut buffered = Vec::with_capacity(1024);
+    let mut chunk = [0u8; 4096];
+    let deadline = tokio::time::Instant::now() + wait;
+
+    loop {
+        match parse_client_hello(&buffered) {
+            ClientHelloSni::Incomplete => {}
+            ClientHelloSni::Absent => return (buffered, None),
+            ClientHelloSni::Hostname(name) => return (buffered, Some(name)),
+        }
+
+        match tokio::time::timeout_at(deadline, client.read(&mut chunk)).await {
+            Ok(Ok(n)) if n > 0 => buffered.extend_from_slice(&chunk[..n]),
+            _ => return (buffered, None),
+        }
+    }
+}
+
+/// Backend selector `sni_routes` maps `hostname` to, if any
+pub fn route_for<'a>(
+    routes: &'a BTreeMap<String, LabelSelector>,
+    hostname: &str,
+) -> Option<&'a LabelSelector> {
+    routes
+        .iter()
+        .find(|(host, _)| host.trim_end_matches('.').eq_ignore_ascii_case(hostname))
+        .map(|(_, selector)| selector)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// Minimal TLS 1.2-style ClientHello record carrying `host` as SNI
+    pub(crate) fn client_hello(host: Option<&str>) -> Vec<u8> {
+        let mut extensions = Vec::new();
+        // An unrelated extension first (supported_groups: x25519)
+        extensions.extend_from_slice(&[0x00, 0x0a, 0x00, 0x04, 0x00, 0x02, 0x00, 0x1d]);
+        if let Some(host) = host {
+            let name = host.as_bytes();
+            let entry_len = 3 + name.len();
+            extensions.extend_from_slice(&EXTENSION_SERVER_NAME.to_be_bytes());
+            extensions.extend_from_slice(&((entry_len + 2) as u16).to_be_bytes());
+            extensions.extend_from_slice(&(entry_len as u16).to_be_bytes());
+            extensions.push(NAME_TYPE_HOST_NAME);
+            extensions.extend_from_slice(&(name.len() as u16).to_be_bytes());
+            extensions.extend_from_slice(name);
+        }
+
+        let mut hello = vec![0x03, 0x03];
+        hello.extend_from_slice(&[0x11; 32]);
+        hello.push(0); // empty session id
+        hello.extend_from_slice(&[0x00, 0x02, 0x13, 0x01]);
+        hello.extend_from_slice(&[0x01, 0x00]);
+        hello.extend_from_slice(&(extensions.len() as u16).to_be_bytes());
+        hello.extend_from_slice(&extensions);
+
+        let mut handshake = vec![HANDSHAKE_CLIENT_HELLO];
+        handshake.extend_from_slice(&(hello.len() as u32).to_be_bytes()[1..]);
+        handshake.extend_from_slice(&hello);
+
+        let mut record = vec![CONTENT_TYPE_HANDSHAKE, 0x03, 0x01];
+        record.extend_from_slice(&(handshake.len() as u16).to_be_bytes());
+        record.extend_from_slice(&handshake);
+        record
+    }
+
+    #[test]
+    fn parses_sni_from_a_client_hello() {
+        let hello = client_hello(Some("API.Example.com."));
+        assert_eq!(
+            parse_client_hello(&hello),
+            ClientHelloSni::Hostname("api.example.com".to_string())
+        );
+
+        // Split across reads: incomplete until the whole record arrived
+        assert_eq!(parse_client_hello(&hello[..3]), ClientHelloSni::Incomplete);
+        assert_eq!(
+            parse_client_hello(&hello[..hello.len() - 1]),
+            ClientHelloSni::Incomplete
+        );
+
+        assert_eq!(
+            parse_client_hello(&client_hello(None)),
+            ClientHelloSni::Absent
+        );
+        assert_eq!(
+            parse_client_hello(b"GET / HTTP/1.1\r\n"),
+            ClientHelloSni::Absent
+        );
+        // Handshake length overrunning its record: malformed, not incomplete
+        let mut bogus = hello.clone();
+        bogus[6] = 0x01;
+        assert_eq!(parse_client_hello(&bogus), ClientHelloSni::Absent);
+    }
+
+    #[tokio::test]
+    async fn reads_a_client_hello_split_across_segments() {
+        let hello = client_hello(Some("db.example.com"));
+        let (mut client, mut server) = tokio::io::duplex(64);
+        let sent = hello.clone();
+        tokio::spawn(async move {
+            use tokio::io::AsyncWriteExt;
+            for piece in sent.chunks(7) {
+                client.write_all(piece).await.unwrap();
+                tokio::time::sleep(Duration::from_millis(1)).await;
+            }
+            // Keep the stream open; the reader must stop at the record end
+            tokio::time::sleep(Duration::from_secs(5)).await;
+        });
+
+        let (buffered, name) = read_client_hello(&mut server, Duration::from_secs(2)).await;
+        assert_eq!(name.as_deref(), Some("db.example.com"));
+        assert_eq!(buffered, hello);
+
+        let mut routes = BTreeMap::new();
+        routes.insert("DB.example.com".to_string(), "role=db".parse().unwrap());
+        assert_eq!(
+            route_for(&routes, "db.example.com").map(ToString::to_string),
+            Some("role=db".to_string())
+        );
+        assert!(route_for(&routes, "web.example.com").is_none());
+    }
+}
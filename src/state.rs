@@ -5,15 +5,19 @@
 
 use log::{info, warn};
 use std::path::PathBuf;
-use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 
 use arc_swap::ArcSwap;
-use tokio::sync::RwLock;
+use tokio::sync::{OwnedSemaphorePermit, RwLock, Semaphore};
 
-use crate::backend_pool::BackendPool;
+use crate::backend_pool::{BackendPool, BackendState};
 use crate::config::{BalanceMethod, RuntimeTuning};
+use crate::dns_cache::DnsCache;
 use crate::load_balancer::LoadBalancer;
 use crate::protection::ProtectionMode;
+use crate::reaper::SessionRegistry;
+use crate::runtime_stats::{RateSample, RateTracker, Rates};
 
 /// Runtime configuration
 ///
@@ -51,6 +55,32 @@ impl RuntimeConfig {
     }
 }
 
+#[cfg(test)]
+impl RuntimeConfig {
+    /// Loopback listener on 9295 over `backends`, for tests
+    pub(crate) fn for_test(
+        backends: Vec<crate::config::BackendConfig>,
+        runtime_tuning: RuntimeTuning,
+    ) -> Self {
+        Self {
+            port: 9295,
+            method: BalanceMethod::RoundRobin,
+            bind_address: "127.0.0.1".to_string(),
+            runtime_tuning,
+            backend_pool: Arc::new(BackendPool::new(backends)),
+            config_path: PathBuf::from("/tmp/test-config.yaml"),
+        }
+    }
+}
+
+/// Backend removed by a reload that still has open connections
+#[derive(Debug, Clone)]
+pub struct DrainingBackend {
+    pub backend: Arc<BackendState>,
+    /// When the reload removed it (unix epoch ms)
+    pub since_ms: u64,
+}
+
 /// Application global state
 ///
 /// Manages state shared by all components.
@@ -66,8 +96,29 @@ pub struct AppState {
     reload: tokio::sync::mpsc::Sender<()>,
     /// Current active connection count
     active_connections: Arc<RwLock<usize>>,
+    /// Connections rejected because max_concurrent_connections was reached
+    overload_rejections: AtomicU64,
+    /// Client connections relayed to a backend since start
+    connections_total: AtomicU64,
+    /// Bytes relayed in both directions by finished connections since start
+    bytes_total: AtomicU64,
+    /// Recent counter samples for `bal status` rates
+    rates: Mutex<RateTracker>,
     /// Automatic protection mode state
     protection_mode: Arc<ProtectionMode>,
+    /// Removed backends whose connections are still draining
+    draining: Mutex<Vec<DrainingBackend>>,
+    /// Set once the proxy listener is bound and accepting
+    listener_bound: AtomicBool,
+    /// Maintenance mode: new clients are closed right away, everything else runs
+    maintenance: AtomicBool,
+    /// Slots for in-flight backend connects, keyed by the `max_pending_connects`
+    /// they were sized for so a reload that changes the limit gets a fresh one
+    connect_slots: Mutex<Option<(usize, Arc<Semaphore>)>>,
+    /// Live proxy sessions, scanned by the idle reaper
+    sessions: Arc<SessionRegistry>,
+    /// Backend hostname lookups reused for `dns_cache_ttl_ms`
+    dns_cache: DnsCache,
 }
 
 impl AppState {
@@ -82,14 +133,18 @@ impl AppState {
         let load_balancer = LoadBalancer::new(
             runtime_config.method,
             Arc::clone(&runtime_config.backend_pool),
+            runtime_config.runtime_tuning.slow_start_ms,
+            runtime_config.runtime_tuning.stabilization_window_ms,
+            runtime_config.runtime_tuning.prefer_label.clone(),
         );
 
+        let tuning = &runtime_config.runtime_tuning;
         let protection_mode = Arc::new(ProtectionMode::new(
-            runtime_config.runtime_tuning.protection_trigger_threshold,
-            runtime_config.runtime_tuning.protection_window_ms,
-            runtime_config
-                .runtime_tuning
-                .protection_stable_success_threshold,
+            tuning.protection_trigger_threshold,
+            tuning.protection_window_ms,
+            tuning.protection_stable_success_threshold,
+            tuning.protection_timeout_limits(),
+            tuning.protection_refused_limits(),
         ));
 
         Self {
@@ -98,7 +153,17 @@ impl AppState {
             shutdown,
             reload,
             active_connections: Arc::new(RwLock::new(0)),
+            overload_rejections: AtomicU64::new(0),
+            connections_total: AtomicU64::new(0),
+            bytes_total: AtomicU64::new(0),
+            rates: Mutex::new(RateTracker::default()),
             protection_mode,
+            draining: Mutex::new(Vec::new()),
+            listener_bound: AtomicBool::new(false),
+            maintenance: AtomicBool::new(false),
+            connect_slots: Mutex::new(None),
+            sessions: Arc::new(SessionRegistry::default()),
+            dns_cache: DnsCache::default(),
         }
     }
 
@@ -112,11 +177,30 @@ impl AppState {
     /// Replace configuration (hot-swap)
     ///
     /// Atomically replaces configuration. Does not affect existing connections.
-    pub fn swap_config(&self, new_config: RuntimeConfig) {
-        let old_port = self.config.load().port;
+    pub fn swap_config(&self, mut new_config: RuntimeConfig) {
+        let old_config = self.config.load_full();
+        // Keep health of backends that survive the reload so a backend known
+        // to be down isn't treated as up until the next probe cycle
+        new_config.backend_pool = Arc::new(
+            new_config
+                .backend_pool
+                .carry_over_from(&old_config.backend_pool),
+        );
+        let old_port = old_config.port;
         let new_port = new_config.port;
 
-        let new_lb = LoadBalancer::new(new_config.method, Arc::clone(&new_config.backend_pool));
+        self.track_removed_backends(&old_config.backend_pool, &new_config.backend_pool);
+
+        let new_lb = LoadBalancer::new(
+            new_config.method,
+            Arc::clone(&new_config.backend_pool),
+            new_config.runtime_tuning.slow_start_ms,
+            new_config.runtime_tuning.stabilization_window_ms,
+            new_config.runtime_tuning.prefer_label.clone(),
+        );
+        if !new_config.runtime_tuning.reset_rr_on_reload {
+            new_lb.continue_rotation_from(&self.load_balancer.load());
+        }
         self.config.store(Arc::new(new_config));
         self.load_balancer.store(Arc::new(new_lb));
 
@@ -130,6 +214,113 @@ impl AppState {
         }
     }
 
+    /// Move backends dropped by a reload into the draining set
+    ///
+    /// Only backends with open connections are kept; they leave the set once
+    /// those connections close (see `draining_backends`).
+    fn track_removed_backends(&self, old_pool: &BackendPool, new_pool: &BackendPool) {
+        let now = BackendState::now_unix_ms();
+        let mut draining = self.draining.lock().unwrap_or_else(|e| e.into_inner());
+
+        for backend in old_pool.all_backends() {
+            let removed = new_pool.find_backend(&backend.address()).is_none();
+            if !removed || backend.active_connections() == 0 {
+                continue;
+            }
+
+            info!(
+                "Backend {} removed by reload; draining {} active connections",
+                backend.address(),
+                backend.active_connections()
+            );
+            draining.push(DrainingBackend {
+                backend: Arc::clone(backend),
+                since_ms: now,
+            });
+        }
+    }
+
+    /// Get removed backends that still have open connections
+    ///
+    /// Entries whose connections have all closed are dropped here.
+    pub fn draining_backends(&self) -> Vec<DrainingBackend> {
+        let mut draining = self.draining.lock().unwrap_or_else(|e| e.into_inner());
+        draining.retain(|entry| {
+            let open = entry.backend.active_connections() > 0;
+            if !open {
+                info!("Backend {} finished draining", entry.backend.address());
+            }
+            open
+        });
+        draining.clone()
+    }
+
+    /// Close connections of removed backends that exceeded `drain_timeout_ms`
+    ///
+    /// A timeout of 0 waits for connections to close on their own.
+    pub fn enforce_drain_deadline(&self) {
+        let drain_timeout_ms = self.config.load().runtime_tuning.drain_timeout_ms;
+        if drain_timeout_ms == 0 {
+            return;
+        }
+
+        let now = BackendState::now_unix_ms();
+        for entry in self.draining_backends() {
+            if entry.backend.is_retired() || now.saturating_sub(entry.since_ms) < drain_timeout_ms {
+                continue;
+            }
+
+            warn!(
+                "Backend {} exceeded drain timeout ({}ms); closing {} remaining connections",
+                entry.backend.address(),
+                drain_timeout_ms,
+                entry.backend.active_connections()
+            );
+            entry.backend.retire();
+        }
+    }
+
+    /// Live proxy sessions and their last activity
+    pub fn sessions(&self) -> &Arc<SessionRegistry> {
+        &self.sessions
+    }
+
+    /// Resolver cache for the proxy connect path
+    pub fn dns_cache(&self) -> &DnsCache {
+        &self.dns_cache
+    }
+
+    /// Record that the proxy listener is bound
+    pub fn mark_listener_bound(&self) {
+        self.listener_bound.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether the proxy listener is bound
+    pub fn is_listener_bound(&self) -> bool {
+        self.listener_bound.load(Ordering::Relaxed)
+    }
+
+    /// Enter or leave maintenance mode; returns whether the mode changed
+    pub fn set_maintenance(&self, enabled: bool) -> bool {
+        self.maintenance.swap(enabled, Ordering::Relaxed) != enabled
+    }
+
+    /// Whether new client connections are being refused for maintenance
+    pub fn is_maintenance(&self) -> bool {
+        self.maintenance.load(Ordering::Relaxed)
+    }
+
+    /// Zero per-backend counters and the protection storm windows
+    ///
+    /// Health, cooldown and protection enabled state are left as they are so
+    /// a reset never changes routing.
+    pub fn reset_stats(&self) {
+        for backend in self.backend_pool().all_backends() {
+            backend.reset_counters();
+        }
+        self.protection_mode.reset_window();
+    }
+
     /// Subscribe to shutdown signal
     ///
     /// Creates broadcast channel receiver for graceful shutdown.
@@ -156,6 +347,7 @@ impl AppState {
     }
 
     /// Get reload channel sender
+    #[allow(dead_code)]
     pub fn reload_receiver(&self) -> &tokio::sync::mpsc::Sender<()> {
         &self.reload
     }
@@ -183,6 +375,61 @@ impl AppState {
         *self.active_connections.read().await
     }
 
+    /// Count one connection rejected by the overload policy
+    pub fn record_overload_rejection(&self) {
+        self.overload_rejections.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Total connections rejected by the overload policy since start
+    pub fn overload_rejections(&self) -> u64 {
+        self.overload_rejections.load(Ordering::Relaxed)
+    }
+
+    /// Count one client connection handed to a backend
+    pub fn record_connection(&self) {
+        self.connections_total.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Add the bytes a finished connection relayed in both directions
+    pub fn record_bytes(&self, bytes: u64) {
+        self.bytes_total.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    pub fn connections_total(&self) -> u64 {
+        self.connections_total.load(Ordering::Relaxed)
+    }
+
+    pub fn bytes_total(&self) -> u64 {
+        self.bytes_total.load(Ordering::Relaxed)
+    }
+
+    /// Feed a counter sample to the rate history and get the smoothed rates
+    pub fn observe_rates(&self, sample: RateSample, window_ms: u64) -> Option<Rates> {
+        self.rates
+            .lock()
+            .ok()
+            .and_then(|mut tracker| tracker.observe(sample, window_ms))
+    }
+
+    /// Wait for a backend connect slot, held until the connect resolves
+    ///
+    /// `None` means `max_pending_connects` is unset and connects are unbounded.
+    pub async fn acquire_connect_slot(&self) -> Option<OwnedSemaphorePermit> {
+        let limit = self.config().runtime_tuning.max_pending_connects?;
+        let slots = {
+            let mut current = self.connect_slots.lock().unwrap_or_else(|e| e.into_inner());
+            match &*current {
+                Some((sized_for, slots)) if *sized_for == limit => Arc::clone(slots),
+                _ => {
+                    let slots = Arc::new(Semaphore::new(limit));
+                    *current = Some((limit, Arc::clone(&slots)));
+                    slots
+                }
+            }
+        };
+        slots.acquire_owned().await.ok()
+    }
+
     /// Get backend pool reference
     pub fn backend_pool(&self) -> Arc<BackendPool> {
         Arc::clone(&self.config.load().backend_pool)
@@ -198,51 +445,111 @@ impl AppState {
     }
 
     /// Get listen port
+    #[allow(dead_code)]
     pub fn port(&self) -> u16 {
         self.config.load().port
     }
 
     /// Get load balancing method
+    #[allow(dead_code)]
     pub fn method(&self) -> BalanceMethod {
         self.config.load().method
     }
 }
 
+#[cfg(test)]
+impl AppState {
+    /// Shared state over `runtime_config` with nobody listening for
+    /// shutdown or reload requests
+    pub(crate) fn for_test(runtime_config: RuntimeConfig) -> Arc<Self> {
+        let (shutdown_tx, _) = tokio::sync::broadcast::channel(4);
+        let (reload_tx, _) = tokio::sync::mpsc::channel(4);
+        Arc::new(Self::new(runtime_config, shutdown_tx, reload_tx))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::backend_pool::{BackendErrorKind, ConnectionGuard};
     use crate::config::{BackendConfig, RuntimeTuning};
-    use tokio::sync::{broadcast, mpsc};
 
     fn runtime_config_with_ports(ports: &[u16]) -> RuntimeConfig {
-        let backends = ports
-            .iter()
-            .map(|p| BackendConfig {
-                host: "127.0.0.1".to_string(),
-                port: *p,
+        let backends = ports.iter().map(|p| BackendConfig::local(*p)).collect();
+        RuntimeConfig::for_test(backends, RuntimeTuning::default())
+    }
+
+    #[tokio::test]
+    async fn connect_slots_bound_concurrent_connects() {
+        use std::sync::atomic::AtomicUsize;
+
+        let mut config = runtime_config_with_ports(&[9000]);
+        config.runtime_tuning.max_pending_connects = Some(3);
+        let state = AppState::for_test(config);
+
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let tasks: Vec<_> = (0..12)
+            .map(|_| {
+                let state = Arc::clone(&state);
+                let in_flight = Arc::clone(&in_flight);
+                let peak = Arc::clone(&peak);
+                tokio::spawn(async move {
+                    let _slot = state.acquire_connect_slot().await;
+                    let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(now, Ordering::SeqCst);
+                    tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+                    in_flight.fetch_sub(1, Ordering::SeqCst);
+                })
             })
-            .collect::<Vec<_>>();
+            .collect();
+        for task in tasks {
+            task.await.unwrap();
+        }
+        assert_eq!(peak.load(Ordering::SeqCst), 3);
 
-        RuntimeConfig {
-            port: 9295,
-            method: BalanceMethod::RoundRobin,
-            bind_address: "0.0.0.0".to_string(),
-            runtime_tuning: RuntimeTuning::default(),
-            backend_pool: Arc::new(BackendPool::new(backends)),
-            config_path: PathBuf::from("/tmp/test-config.yaml"),
+        state.swap_config(runtime_config_with_ports(&[9000]));
+        assert!(state.acquire_connect_slot().await.is_none());
+    }
+
+    #[test]
+    fn reset_stats_zeroes_counters_but_keeps_health() {
+        let state = AppState::for_test(runtime_config_with_ports(&[9000, 9100]));
+
+        let pool = state.backend_pool();
+        let [first, second] = pool.all_backends() else {
+            panic!("expected two backends");
+        };
+        first.mark_connect_failure(BackendErrorKind::Timeout, 1, 100, 1000, 1000, false);
+        first.mark_connect_failure(
+            BackendErrorKind::ConnectionRefused,
+            1,
+            100,
+            1000,
+            1000,
+            false,
+        );
+        second.mark_connect_failure(BackendErrorKind::Other, 5, 100, 1000, 0, false);
+        second.record_bytes(4096);
+        assert!(!first.is_healthy());
+        assert!(first.is_in_cooldown());
+
+        state.reset_stats();
+
+        for backend in [first, second] {
+            assert_eq!(backend.timeout_count(), 0);
+            assert_eq!(backend.refused_count(), 0);
+            assert_eq!(backend.other_error_count(), 0);
+            assert_eq!(backend.bytes_total(), 0);
         }
+        assert!(!first.is_healthy());
+        assert!(first.is_in_cooldown());
+        assert!(second.is_healthy());
     }
 
     #[test]
     fn swap_config_updates_load_balancer_backend_pool() {
-        let (shutdown_tx, _) = broadcast::channel(4);
-        let (reload_tx, _reload_rx) = mpsc::channel(4);
-
-        let state = AppState::new(
-            runtime_config_with_ports(&[9000, 9100]),
-            shutdown_tx,
-            reload_tx,
-        );
+        let state = AppState::for_test(runtime_config_with_ports(&[9000, 9100]));
 
         let initial = state
             .load_balancer()
@@ -258,4 +565,89 @@ mod tests {
             .expect("backend should exist after swap");
         assert_eq!(after.config.port, 9200);
     }
+
+    #[test]
+    fn round_robin_restarts_or_continues_after_reload_per_flag() {
+        let ports = [9000, 9100, 9200];
+        for reset in [true, false] {
+            let state = AppState::for_test(runtime_config_with_ports(&ports));
+            let first = state.load_balancer().select_backend().unwrap().config.port;
+            let second = state.load_balancer().select_backend().unwrap().config.port;
+
+            let mut reloaded = runtime_config_with_ports(&ports);
+            reloaded.runtime_tuning.reset_rr_on_reload = reset;
+            state.swap_config(reloaded);
+
+            let next = state.load_balancer().select_backend().unwrap().config.port;
+            if reset {
+                assert_eq!(next, first);
+            } else {
+                assert_ne!(next, first);
+                assert_ne!(next, second);
+            }
+        }
+    }
+
+    #[test]
+    fn removed_backend_with_open_connections_drains_after_swap() {
+        let state = AppState::for_test(runtime_config_with_ports(&[9000, 9100]));
+        let removed = state
+            .backend_pool()
+            .find_backend("127.0.0.1:9100")
+            .expect("backend should exist");
+        let guard = ConnectionGuard::new(Arc::clone(&removed));
+
+        state.swap_config(runtime_config_with_ports(&[9000]));
+
+        let draining = state.draining_backends();
+        assert_eq!(draining.len(), 1);
+        assert_eq!(draining[0].backend.address(), "127.0.0.1:9100");
+        assert_eq!(draining[0].backend.active_connections(), 1);
+
+        drop(guard);
+        assert!(state.draining_backends().is_empty());
+    }
+
+    #[test]
+    fn drain_deadline_retires_removed_backend() {
+        let state = AppState::for_test(runtime_config_with_ports(&[9000, 9100]));
+        let removed = state
+            .backend_pool()
+            .find_backend("127.0.0.1:9100")
+            .expect("backend should exist");
+        let _guard = ConnectionGuard::new(Arc::clone(&removed));
+
+        let mut next = runtime_config_with_ports(&[9000]);
+        next.runtime_tuning.drain_timeout_ms = 1;
+        state.swap_config(next);
+        assert!(!removed.is_retired());
+
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        state.enforce_drain_deadline();
+        assert!(removed.is_retired());
+    }
+
+    #[test]
+    fn unhealthy_backend_stays_unhealthy_across_reload() {
+        let state = AppState::for_test(runtime_config_with_ports(&[9000, 9100]));
+        let down = state
+            .backend_pool()
+            .find_backend("127.0.0.1:9100")
+            .expect("backend should exist");
+        down.mark_failure(1);
+        assert!(!down.is_healthy());
+
+        state.swap_config(runtime_config_with_ports(&[9100, 9200]));
+
+        let pool = state.backend_pool();
+        let kept = pool
+            .find_backend("127.0.0.1:9100")
+            .expect("kept backend should exist");
+        assert!(!kept.is_healthy());
+        assert_eq!(kept.consecutive_failures(), 1);
+        assert!(pool
+            .find_backend("127.0.0.1:9200")
+            .expect("new backend should exist")
+            .is_healthy());
+    }
 }
@@ -6,18 +6,28 @@
 
 use anyhow::{Context, Result};
 use log::{debug, error, info, warn};
+use nix::sys::signal as nix_signal;
 use std::path::Path;
 use std::sync::Arc;
+use std::time::Instant;
 use tokio::signal::unix::{signal, SignalKind};
 use tokio::sync::{broadcast, mpsc};
 use tokio::time::{timeout, Duration};
 
-use crate::config_store::ConfigStore;
-use crate::constants::GRACEFUL_SHUTDOWN_TIMEOUT_SECS;
+use crate::config_store::{ConfigStore, StartupRetry};
+use crate::config_watch;
+use crate::constants::{
+    get_log_level_request_path, get_maintenance_path, get_reset_stats_request_path,
+    GRACEFUL_SHUTDOWN_TIMEOUT_SECS, MAINTENANCE_POLL_INTERVAL_MS, MIN_HEARTBEAT_INTERVAL_MS,
+};
+use crate::control;
 use crate::health::HealthChecker;
-use crate::process::PidFileGuard;
+use crate::logging;
+use crate::process::{PidFileGuard, ProcessManager};
 use crate::protection;
 use crate::proxy::ProxyServer;
+use crate::reaper;
+use crate::runtime_stats::{self, RuntimeSnapshot};
 use crate::state::AppState;
 
 /// Supervisor
@@ -33,16 +43,17 @@ impl Supervisor {
     /// 3. Register signal handlers
     /// 4. Start tasks (proxy, health checker)
     /// 5. Main loop (wait for signals/reload)
-    pub async fn run_daemon(cli_config_path: Option<&Path>) -> Result<()> {
+    pub async fn run_daemon(cli_config_path: Option<&Path>, retry: StartupRetry) -> Result<()> {
         // Create PID file (prevent duplicate execution)
         let _pid_guard =
             PidFileGuard::new().context("Failed to create PID file - check if already running")?;
 
         info!("bal daemon starting (PID: {})", std::process::id());
+        remove_orphaned_snapshots();
 
         // Load initial configuration
         let (runtime_config, config_path) =
-            ConfigStore::load_initial_config(cli_config_path).await?;
+            ConfigStore::load_initial_config(cli_config_path, retry).await?;
 
         info!("Configuration loaded: {}", config_path.display());
         info!("  - Listen port: {}", runtime_config.port);
@@ -58,6 +69,7 @@ impl Supervisor {
 
         let state = Arc::new(AppState::new(runtime_config, shutdown_tx, reload_tx));
         protection::write_snapshot(&state.protection_mode().snapshot());
+        runtime_stats::write_snapshot(&RuntimeSnapshot::capture(&state).await);
 
         // Register signal handlers
         let mut sigterm =
@@ -66,15 +78,22 @@ impl Supervisor {
             signal(SignalKind::interrupt()).context("Failed to register SIGINT handler")?;
         let mut sighup =
             signal(SignalKind::hangup()).context("Failed to register SIGHUP handler")?;
+        let mut sigusr1 =
+            signal(SignalKind::user_defined1()).context("Failed to register SIGUSR1 handler")?;
+        let mut sigusr2 =
+            signal(SignalKind::user_defined2()).context("Failed to register SIGUSR2 handler")?;
+        let mut sigquit =
+            signal(SignalKind::quit()).context("Failed to register SIGQUIT handler")?;
 
-        info!("Signal handlers registered (SIGTERM, SIGINT, SIGHUP)");
+        info!("Signal handlers registered (SIGTERM, SIGINT, SIGQUIT, SIGHUP, SIGUSR1, SIGUSR2)");
 
         // Start background tasks
         let proxy_state = Arc::clone(&state);
         let health_state = Arc::clone(&state);
 
         let mut proxy_shutdown = state.subscribe_shutdown();
-        let health_shutdown = state.subscribe_shutdown();
+        // Health checks get their own stop signal so shutdown can end them first
+        let (health_stop, health_shutdown) = broadcast::channel(1);
 
         // Proxy server task
         let proxy_handle = tokio::spawn(async move {
@@ -92,31 +111,86 @@ impl Supervisor {
             }
         });
 
+        // Idle session reaper task
+        let reaper_state = Arc::clone(&state);
+        let reaper_shutdown = state.subscribe_shutdown();
+        tokio::spawn(reaper::run(reaper_state, reaper_shutdown));
+
+        // Config file watcher task (watch_config)
+        if state.config().runtime_tuning.watch_config {
+            let watch_state = Arc::clone(&state);
+            let watch_shutdown = state.subscribe_shutdown();
+            tokio::spawn(async move {
+                if let Err(e) = config_watch::run(watch_state, watch_shutdown).await {
+                    error!("Config watcher error: {}", e);
+                }
+            });
+        }
+
+        // Control endpoint task (/livez, /readyz)
+        if let Some(address) = state.config().runtime_tuning.control_address.clone() {
+            let control_state = Arc::clone(&state);
+            let control_shutdown = state.subscribe_shutdown();
+            tokio::spawn(async move {
+                if let Err(e) = control::run(control_state, address, control_shutdown).await {
+                    error!("Control endpoint error: {}", e);
+                }
+            });
+        }
+
+        // Heartbeat file / systemd watchdog task
+        spawn_heartbeat(&state);
+
         info!("All service tasks started");
 
-        // Main loop
-        loop {
+        let mut maintenance_poll = tokio::time::interval(MAINTENANCE_POLL_INTERVAL);
+
+        // Main loop; evaluates to true for an immediate (SIGQUIT) stop
+        let immediate = loop {
             tokio::select! {
                 // SIGTERM (stop command)
                 _ = sigterm.recv() => {
                     info!("SIGTERM received - starting graceful shutdown");
-                    break;
+                    break false;
                 }
 
                 // SIGINT (Ctrl+C)
                 _ = sigint.recv() => {
                     info!("SIGINT received - starting graceful shutdown");
-                    break;
+                    break false;
+                }
+
+                // SIGQUIT (stop now, no drain)
+                _ = sigquit.recv() => {
+                    warn!("SIGQUIT received - shutting down immediately without draining");
+                    break true;
                 }
 
                 // SIGHUP (graceful reload)
                 _ = sighup.recv() => {
                     info!("SIGHUP received - reloading configuration");
-                    if let Err(e) = ConfigStore::reload_config(&state, None).await {
+                    let target = ConfigStore::take_reload_request();
+                    if let Err(e) = ConfigStore::reload_config(&state, target.as_deref()).await {
                         error!("Configuration reload failed: {}", e);
                     }
                 }
 
+                // `bal maintenance on/off` flag file and `bal reset-stats` requests
+                _ = maintenance_poll.tick() => {
+                    sync_maintenance(&state, get_maintenance_path().exists());
+                    take_reset_stats_request(&state);
+                }
+
+                // SIGUSR1 (runtime diagnostic dump)
+                _ = sigusr1.recv() => {
+                    log_runtime_dump(&state).await;
+                }
+
+                // SIGUSR2 (log level toggle / `bal log-level`)
+                _ = sigusr2.recv() => {
+                    apply_log_level_signal();
+                }
+
                 // Reload channel (programmatic)
                 Some(()) = reload_rx.recv() => {
                     info!("Reload request received");
@@ -125,34 +199,42 @@ impl Supervisor {
                     }
                 }
             }
+        };
+
+        if immediate {
+            immediate_shutdown(&state, vec![proxy_handle, health_handle]).await;
+        } else {
+            // Graceful shutdown
+            info!("Starting graceful shutdown...");
+            Self::graceful_shutdown(state, proxy_handle, health_handle, &health_stop).await?;
         }
 
-        // Graceful shutdown
-        info!("Starting graceful shutdown...");
-        Self::graceful_shutdown(state, proxy_handle, health_handle).await?;
-
         info!("bal daemon shutdown complete");
         Ok(())
     }
 
     /// Perform graceful shutdown
     ///
-    /// 1. Send shutdown signal to all background tasks
-    /// 2. Wait for existing connections to complete (up to timeout)
-    /// 3. Confirm task termination
+    /// 1. Stop health checks so backend state stays put during the drain
+    /// 2. Send shutdown signal to the proxy and other background tasks
+    /// 3. Wait for existing connections to complete (up to timeout)
     async fn graceful_shutdown(
         state: Arc<AppState>,
         proxy_handle: tokio::task::JoinHandle<()>,
         health_handle: tokio::task::JoinHandle<()>,
+        health_stop: &broadcast::Sender<()>,
     ) -> Result<()> {
+        stop_health_checks(health_stop, health_handle).await;
+
         // Broadcast shutdown signal
         info!("Sending shutdown signal to all services");
         state.trigger_shutdown();
 
         // Check active connections
-        let active = state.active_connections().await;
-        if active > 0 {
-            info!("Waiting for {} active connections...", active);
+        let drain_started = Instant::now();
+        let initial = state.active_connections().await;
+        if initial > 0 {
+            info!("Waiting for {} active connections...", initial);
         }
 
         // Wait for task termination with timeout
@@ -162,31 +244,42 @@ impl Supervisor {
                 error!("Proxy task termination error: {}", e);
             }
 
-            // Wait for health checker to stop
-            if let Err(e) = health_handle.await {
-                error!("Health check task termination error: {}", e);
-            }
-
             // Additional wait for all existing connections to close
+            let mut last_progress = drain_started;
             loop {
                 let active = state.active_connections().await;
                 if active == 0 {
                     break;
                 }
-                debug!("{} active connections remaining...", active);
+                if last_progress.elapsed() >= DRAIN_PROGRESS_INTERVAL {
+                    info!(
+                        "Draining: {} active connections remaining ({}s elapsed)",
+                        active,
+                        drain_started.elapsed().as_secs()
+                    );
+                    last_progress = Instant::now();
+                } else {
+                    debug!("{} active connections remaining...", active);
+                }
                 tokio::time::sleep(Duration::from_millis(100)).await;
             }
         })
         .await;
 
+        let remaining = state.active_connections().await;
+        let summary = drain_summary(
+            initial.saturating_sub(remaining),
+            drain_started,
+            Instant::now(),
+        );
         match shutdown_result {
             Ok(()) => {
-                info!("All connections closed successfully");
+                info!("All connections closed successfully. {}", summary);
             }
             Err(_) => {
                 warn!(
-                    "Graceful shutdown timeout ({} seconds). Force stopping.",
-                    GRACEFUL_SHUTDOWN_TIMEOUT_SECS
+                    "Graceful shutdown timeout ({} seconds). Force stopping with {} connections open. {}",
+                    GRACEFUL_SHUTDOWN_TIMEOUT_SECS, remaining, summary
                 );
             }
         }
@@ -195,20 +288,272 @@ impl Supervisor {
     }
 }
 
+/// First shutdown step: end health checks before the proxy stops
+///
+/// A check failing against a backend that is itself shutting down would
+/// otherwise mark it unhealthy and churn state while sessions drain.
+async fn stop_health_checks(
+    health_stop: &broadcast::Sender<()>,
+    health_handle: tokio::task::JoinHandle<()>,
+) {
+    let _ = health_stop.send(());
+    match timeout(HEALTH_STOP_TIMEOUT, health_handle).await {
+        Ok(Ok(())) => debug!("Health checker stopped"),
+        Ok(Err(e)) => error!("Health check task termination error: {}", e),
+        Err(_) => warn!("Health checker did not stop in time; continuing shutdown"),
+    }
+}
+
+/// Ignore SIGPIPE for the whole process
+///
+/// A write to a backend (notably a Unix socket) whose peer already closed
+/// must come back as `EPIPE` for the relay to handle, not kill the daemon.
+/// The Rust runtime usually does this already; bal doesn't rely on it.
+pub fn ignore_sigpipe() -> Result<()> {
+    // SAFETY: SigIgn installs no handler code, so nothing runs in signal context
+    unsafe { nix_signal::signal(nix_signal::Signal::SIGPIPE, nix_signal::SigHandler::SigIgn) }
+        .context("Failed to ignore SIGPIPE")?;
+    Ok(())
+}
+
+/// Apply the maintenance flag, logging only when the mode actually flips
+fn sync_maintenance(state: &AppState, enabled: bool) {
+    if !state.set_maintenance(enabled) {
+        return;
+    }
+    if enabled {
+        warn!("Maintenance mode on - refusing new client connections (health checks continue)");
+    } else {
+        info!("Maintenance mode off - accepting client connections again");
+    }
+}
+
+/// Switch the log level on SIGUSR2, honoring a pending `bal log-level` request
+fn apply_log_level_signal() {
+    let requested = std::fs::read_to_string(get_log_level_request_path())
+        .ok()
+        .map(|level| logging::parse_log_level(level.trim()));
+    let _ = std::fs::remove_file(get_log_level_request_path());
+    let level = logging::apply_log_level_change(requested);
+    // warn so the change is visible even when dropping to warn
+    warn!("SIGUSR2 received - log level is now {}", level);
+}
+
+/// Consume a pending `bal reset-stats` request, if any
+fn take_reset_stats_request(state: &AppState) {
+    if std::fs::remove_file(get_reset_stats_request_path()).is_ok() {
+        state.reset_stats();
+        info!("Stats reset requested - backend error/byte counters and protection window zeroed");
+    }
+}
+
+/// SIGQUIT path: stop without waiting for anything
+///
+/// Unlike the graceful path (SIGTERM/SIGINT), in-flight connections are not
+/// drained: the proxy and health tasks are aborted and the caller returns,
+/// dropping open sessions with the process and releasing the PID file guard.
+/// Returns how many connections were cut.
+async fn immediate_shutdown(state: &AppState, handles: Vec<tokio::task::JoinHandle<()>>) -> usize {
+    state.trigger_shutdown();
+    for handle in &handles {
+        handle.abort();
+    }
+    for handle in handles {
+        let _ = handle.await;
+    }
+
+    let dropped = state.active_connections().await;
+    warn!(
+        "Immediate shutdown: dropped {} active connection{}",
+        dropped,
+        if dropped == 1 { "" } else { "s" }
+    );
+    dropped
+}
+
+/// Start the heartbeat task; with systemd's watchdog it pings that too
+fn spawn_heartbeat(state: &Arc<AppState>) {
+    let watchdog = SystemdWatchdog::from_env(|name| std::env::var(name).ok(), std::process::id());
+    if let Some(watchdog) = &watchdog {
+        info!(
+            "systemd watchdog enabled (WATCHDOG_USEC={}ms)",
+            watchdog.timeout.as_millis()
+        );
+    }
+    tokio::spawn(run_heartbeat(
+        Arc::clone(state),
+        watchdog,
+        state.subscribe_shutdown(),
+    ));
+}
+
+/// Touch `heartbeat_file` and ping the systemd watchdog until shutdown
+///
+/// Runs as its own task, so a wedged runtime stops the heartbeat and the
+/// watchdog notices. Reads the config each beat, so reloads apply.
+async fn run_heartbeat(
+    state: Arc<AppState>,
+    watchdog: Option<SystemdWatchdog>,
+    mut shutdown: broadcast::Receiver<()>,
+) {
+    loop {
+        let runtime = state.config().runtime_tuning.clone();
+        let interval = heartbeat_interval(
+            runtime.heartbeat_interval_ms,
+            watchdog.as_ref().map(|watchdog| watchdog.timeout),
+        );
+
+        if let Some(path) = &runtime.heartbeat_file {
+            if let Err(e) = touch_heartbeat(Path::new(path)) {
+                warn!("Failed to touch heartbeat_file {}: {}", path, e);
+            }
+        }
+        if let Some(watchdog) = &watchdog {
+            if let Err(e) = watchdog.notify("WATCHDOG=1") {
+                warn!("Failed to notify systemd watchdog: {}", e);
+            }
+        }
+
+        tokio::select! {
+            _ = tokio::time::sleep(interval) => {}
+            _ = shutdown.recv() => {
+                debug!("Heartbeat task received shutdown signal");
+                break;
+            }
+        }
+    }
+}
+
+/// Pause between beats: `heartbeat_interval_ms`, or half the watchdog
+/// timeout (systemd's recommendation) if shorter, but never zero
+fn heartbeat_interval(interval_ms: u64, watchdog_timeout: Option<Duration>) -> Duration {
+    let interval = Duration::from_millis(interval_ms);
+    let interval = match watchdog_timeout {
+        Some(timeout) => interval.min(timeout / 2),
+        None => interval,
+    };
+    interval.max(Duration::from_millis(MIN_HEARTBEAT_INTERVAL_MS))
+}
+
+/// Create `path` if needed and set its mtime to now
+fn touch_heartbeat(path: &Path) -> std::io::Result<()> {
+    std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?
+        .set_modified(std::time::SystemTime::now())
+}
+
+/// systemd service watchdog (`WatchdogSec=`), from `WATCHDOG_USEC`
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SystemdWatchdog {
+    timeout: Duration,
+    /// `NOTIFY_SOCKET`: a path, or `@name` for an abstract socket
+    socket: String,
+}
+
+impl SystemdWatchdog {
+    /// Present when `WATCHDOG_USEC` and `NOTIFY_SOCKET` are set and
+    /// `WATCHDOG_PID` (if set) names this process
+    fn from_env<F>(lookup: F, pid: u32) -> Option<Self>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let usec: u64 = lookup("WATCHDOG_USEC")?.trim().parse().ok()?;
+        if usec == 0 {
+            return None;
+        }
+        if let Some(watchdog_pid) = lookup("WATCHDOG_PID") {
+            if watchdog_pid.trim().parse::<u32>().ok() != Some(pid) {
+                return None;
+            }
+        }
+        Some(Self {
+            timeout: Duration::from_micros(usec),
+            socket: lookup("NOTIFY_SOCKET")?,
+        })
+    }
+
+    /// Send one sd_notify message
+    fn notify(&self, message: &str) -> std::io::Result<()> {
+        let socket = std::os::unix::net::UnixDatagram::unbound()?;
+        match self.socket.strip_prefix('@') {
+            Some(name) => send_abstract(&socket, name, message.as_bytes()),
+            None => socket.send_to(message.as_bytes(), &self.socket).map(drop),
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn send_abstract(
+    socket: &std::os::unix::net::UnixDatagram,
+    name: &str,
+    message: &[u8],
+) -> std::io::Result<()> {
+    use std::os::linux::net::SocketAddrExt;
+    let addr = std::os::unix::net::SocketAddr::from_abstract_name(name)?;
+    socket.send_to_addr(message, &addr).map(drop)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn send_abstract(
+    _socket: &std::os::unix::net::UnixDatagram,
+    _name: &str,
+    _message: &[u8],
+) -> std::io::Result<()> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "abstract NOTIFY_SOCKET requires Linux",
+    ))
+}
+
+/// Cadence of the maintenance flag check in the main loop
+const MAINTENANCE_POLL_INTERVAL: Duration = Duration::from_millis(MAINTENANCE_POLL_INTERVAL_MS);
+
+/// How long shutdown waits for an in-flight health round before moving on
+const HEALTH_STOP_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// How often the drain loop reports progress at info level
+const DRAIN_PROGRESS_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Final shutdown line: how many connections drained and how long it took
+fn drain_summary(drained: usize, started: Instant, finished: Instant) -> String {
+    format!(
+        "Drained {} connection{} in {} ms",
+        drained,
+        if drained == 1 { "" } else { "s" },
+        finished.saturating_duration_since(started).as_millis()
+    )
+}
+
+/// Drop protection/stats snapshots a previous (possibly crashed) run left
+/// behind, so nothing reports them before this run writes its own
+fn remove_orphaned_snapshots() {
+    protection::remove_snapshot();
+    runtime_stats::remove_snapshot();
+}
+
 /// Public API for main.rs
-pub async fn run_daemon(cli_config_path: Option<&Path>) -> Result<()> {
-    Supervisor::run_daemon(cli_config_path).await
+pub async fn run_daemon(cli_config_path: Option<&Path>, retry: StartupRetry) -> Result<()> {
+    Supervisor::run_daemon(cli_config_path, retry).await
 }
 
 /// Run daemon in foreground mode (for `bal start` without -d)
 ///
 /// Same as run_daemon but without PID file creation.
 /// Logs go to stdout.
-pub async fn run_foreground(cli_config_path: Option<&std::path::Path>) -> Result<()> {
+pub async fn run_foreground(
+    cli_config_path: Option<&std::path::Path>,
+    retry: StartupRetry,
+) -> Result<()> {
     info!("bal foreground mode starting (PID: {})", std::process::id());
+    if !ProcessManager::is_daemon_running() {
+        remove_orphaned_snapshots();
+    }
 
     // Load initial configuration
-    let (runtime_config, config_path) = ConfigStore::load_initial_config(cli_config_path).await?;
+    let (runtime_config, config_path) =
+        ConfigStore::load_initial_config(cli_config_path, retry).await?;
 
     info!("Configuration loaded: {}", config_path.display());
     info!("  - Listen port: {}", runtime_config.port);
@@ -224,6 +569,7 @@ pub async fn run_foreground(cli_config_path: Option<&std::path::Path>) -> Result
 
     let state = Arc::new(AppState::new(runtime_config, shutdown_tx, reload_tx));
     protection::write_snapshot(&state.protection_mode().snapshot());
+    runtime_stats::write_snapshot(&RuntimeSnapshot::capture(&state).await);
 
     // Register signal handlers
     let mut sigterm =
@@ -231,15 +577,21 @@ pub async fn run_foreground(cli_config_path: Option<&std::path::Path>) -> Result
     let mut sigint =
         signal(SignalKind::interrupt()).context("Failed to register SIGINT handler")?;
     let mut sighup = signal(SignalKind::hangup()).context("Failed to register SIGHUP handler")?;
+    let mut sigusr1 =
+        signal(SignalKind::user_defined1()).context("Failed to register SIGUSR1 handler")?;
+    let mut sigusr2 =
+        signal(SignalKind::user_defined2()).context("Failed to register SIGUSR2 handler")?;
+    let mut sigquit = signal(SignalKind::quit()).context("Failed to register SIGQUIT handler")?;
 
-    info!("Signal handlers registered (SIGTERM, SIGINT, SIGHUP)");
+    info!("Signal handlers registered (SIGTERM, SIGINT, SIGQUIT, SIGHUP, SIGUSR1, SIGUSR2)");
 
     // Start background tasks
     let proxy_state = Arc::clone(&state);
     let health_state = Arc::clone(&state);
 
     let mut proxy_shutdown = state.subscribe_shutdown();
-    let health_shutdown = state.subscribe_shutdown();
+    // Health checks get their own stop signal so shutdown can end them first
+    let (health_stop, health_shutdown) = broadcast::channel(1);
 
     // Proxy server task
     let proxy_handle = tokio::spawn(async move {
@@ -257,31 +609,86 @@ pub async fn run_foreground(cli_config_path: Option<&std::path::Path>) -> Result
         }
     });
 
+    // Idle session reaper task
+    let reaper_state = Arc::clone(&state);
+    let reaper_shutdown = state.subscribe_shutdown();
+    tokio::spawn(reaper::run(reaper_state, reaper_shutdown));
+
+    // Config file watcher task (watch_config)
+    if state.config().runtime_tuning.watch_config {
+        let watch_state = Arc::clone(&state);
+        let watch_shutdown = state.subscribe_shutdown();
+        tokio::spawn(async move {
+            if let Err(e) = config_watch::run(watch_state, watch_shutdown).await {
+                error!("Config watcher error: {}", e);
+            }
+        });
+    }
+
+    // Control endpoint task (/livez, /readyz)
+    if let Some(address) = state.config().runtime_tuning.control_address.clone() {
+        let control_state = Arc::clone(&state);
+        let control_shutdown = state.subscribe_shutdown();
+        tokio::spawn(async move {
+            if let Err(e) = control::run(control_state, address, control_shutdown).await {
+                error!("Control endpoint error: {}", e);
+            }
+        });
+    }
+
+    // Heartbeat file / systemd watchdog task
+    spawn_heartbeat(&state);
+
     info!("All service tasks started");
 
-    // Main loop
-    loop {
+    let mut maintenance_poll = tokio::time::interval(MAINTENANCE_POLL_INTERVAL);
+
+    // Main loop; evaluates to true for an immediate (SIGQUIT) stop
+    let immediate = loop {
         tokio::select! {
             // SIGTERM (stop command)
             _ = sigterm.recv() => {
                 info!("SIGTERM received - starting graceful shutdown");
-                break;
+                break false;
             }
 
             // SIGINT (Ctrl+C)
             _ = sigint.recv() => {
                 info!("SIGINT received - starting graceful shutdown");
-                break;
+                break false;
+            }
+
+            // SIGQUIT (stop now, no drain)
+            _ = sigquit.recv() => {
+                warn!("SIGQUIT received - shutting down immediately without draining");
+                break true;
             }
 
             // SIGHUP (graceful reload)
             _ = sighup.recv() => {
                 info!("SIGHUP received - reloading configuration");
-                if let Err(e) = ConfigStore::reload_config(&state, None).await {
+                let target = ConfigStore::take_reload_request();
+                if let Err(e) = ConfigStore::reload_config(&state, target.as_deref()).await {
                     error!("Failed to reload configuration: {}", e);
                 }
             }
 
+            // `bal maintenance on/off` flag file and `bal reset-stats` requests
+            _ = maintenance_poll.tick() => {
+                sync_maintenance(&state, get_maintenance_path().exists());
+                take_reset_stats_request(&state);
+            }
+
+            // SIGUSR1 (runtime diagnostic dump)
+            _ = sigusr1.recv() => {
+                log_runtime_dump(&state).await;
+            }
+
+            // SIGUSR2 (log level toggle / `bal log-level`)
+            _ = sigusr2.recv() => {
+                apply_log_level_signal();
+            }
+
             // Configuration reload channel
             Some(_) = reload_rx.recv() => {
                 info!("Configuration reload triggered via channel");
@@ -290,22 +697,97 @@ pub async fn run_foreground(cli_config_path: Option<&std::path::Path>) -> Result
                 }
             }
         }
+    };
+
+    if immediate {
+        immediate_shutdown(&state, vec![proxy_handle, health_handle]).await;
+        return Ok(());
     }
 
     // Graceful shutdown
-    graceful_shutdown(state, proxy_handle, health_handle).await
+    graceful_shutdown(state, proxy_handle, health_handle, &health_stop).await
 }
 
-/// Graceful shutdown sequence
+/// Log the SIGUSR1 runtime dump, one log record per line
+async fn log_runtime_dump(state: &AppState) {
+    info!("SIGUSR1 received - dumping runtime state");
+    for line in format_runtime_dump(state).await.lines() {
+        info!("{}", line);
+    }
+}
+
+/// Build an on-demand diagnostic snapshot of the running balancer
+async fn format_runtime_dump(state: &AppState) -> String {
+    let config = state.config();
+    let pool = &config.backend_pool;
+    let protection = state.protection_mode().snapshot();
+
+    let mut lines = vec![
+        "runtime dump".to_string(),
+        format!("  config: {}", config.config_path.display()),
+        format!(
+            "  listen: {}:{} method={}",
+            config.bind_address, config.port, config.method
+        ),
+        format!("  active_connections: {}", state.active_connections().await),
+        format!(
+            "  overload_rejections_total: {}",
+            state.overload_rejections()
+        ),
+        format!(
+            "  backends: {}/{} healthy",
+            pool.healthy_count(),
+            pool.total_count()
+        ),
+        format!(
+            "  protection_mode: {}{}",
+            if protection.enabled { "on" } else { "off" },
+            protection
+                .reason
+                .map(|reason| format!(" ({})", reason))
+                .unwrap_or_default()
+        ),
+    ];
+
+    for backend in pool.all_backends() {
+        lines.push(format!(
+            "    - {} healthy={} active={} consecutive_failures={} cooldown={} degraded={} counters(timeout={}, refused={}, other={})",
+            backend.address(),
+            backend.is_healthy(),
+            backend.active_connections(),
+            backend.consecutive_failures(),
+            backend.is_in_cooldown(),
+            backend.is_degraded(),
+            backend.timeout_count(),
+            backend.refused_count(),
+            backend.other_error_count()
+        ));
+    }
+
+    for draining in state.draining_backends() {
+        lines.push(format!(
+            "    - {} draining active={}",
+            draining.backend.address(),
+            draining.backend.active_connections()
+        ));
+    }
+
+    lines.join("\n")
+}
+
+/// Graceful shutdown sequence (health checks first, then the proxy)
 async fn graceful_shutdown(
     state: Arc<AppState>,
     proxy_handle: tokio::task::JoinHandle<()>,
     health_handle: tokio::task::JoinHandle<()>,
+    health_stop: &broadcast::Sender<()>,
 ) -> Result<()> {
     info!("Starting graceful shutdown...");
 
-    // Send shutdown signal to all tasks
-    let _ = state.trigger_shutdown();
+    stop_health_checks(health_stop, health_handle).await;
+
+    // Send shutdown signal to the proxy and remaining tasks
+    state.trigger_shutdown();
 
     // Wait for tasks to complete with timeout
     let shutdown_timeout = Duration::from_secs(GRACEFUL_SHUTDOWN_TIMEOUT_SECS);
@@ -315,13 +797,190 @@ async fn graceful_shutdown(
         Err(_) => warn!("Proxy server shutdown timeout"),
     }
 
-    match timeout(shutdown_timeout, health_handle).await {
-        Ok(_) => info!("Health checker stopped gracefully"),
-        Err(_) => warn!("Health checker shutdown timeout"),
-    }
-
     info!("All connections closed successfully");
     info!("bal shutdown complete");
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{BackendConfig, RuntimeTuning};
+    use crate::state::RuntimeConfig;
+
+    #[tokio::test]
+    async fn immediate_shutdown_does_not_wait_for_active_connections() {
+        let state = AppState::for_test(RuntimeConfig::for_test(
+            Vec::new(),
+            RuntimeTuning::default(),
+        ));
+        assert!(state.try_acquire_connection(10).await);
+        assert!(state.try_acquire_connection(10).await);
+
+        // Stand-ins for proxy/health tasks that would otherwise never finish
+        let stuck = (0..2)
+            .map(|_| tokio::spawn(std::future::pending::<()>()))
+            .collect();
+
+        let dropped = timeout(
+            Duration::from_millis(500),
+            immediate_shutdown(&state, stuck),
+        )
+        .await
+        .expect("immediate shutdown must not wait on connections or tasks");
+        assert_eq!(dropped, 2);
+    }
+
+    #[tokio::test]
+    async fn graceful_shutdown_stops_health_checks_before_the_proxy() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+
+        let state = AppState::for_test(RuntimeConfig::for_test(
+            Vec::new(),
+            RuntimeTuning::default(),
+        ));
+
+        // Stand-in health task that takes a moment to finish its round
+        let health_stopped = Arc::new(AtomicBool::new(false));
+        let (health_stop, mut health_rx) = broadcast::channel(1);
+        let stopped = Arc::clone(&health_stopped);
+        let health_handle = tokio::spawn(async move {
+            let _ = health_rx.recv().await;
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            stopped.store(true, Ordering::SeqCst);
+        });
+
+        // Stand-in proxy task records whether health was done when it was told to stop
+        let mut proxy_rx = state.subscribe_shutdown();
+        let stopped = Arc::clone(&health_stopped);
+        let health_done_first = Arc::new(AtomicBool::new(false));
+        let done_first = Arc::clone(&health_done_first);
+        let proxy_handle = tokio::spawn(async move {
+            let _ = proxy_rx.recv().await;
+            done_first.store(stopped.load(Ordering::SeqCst), Ordering::SeqCst);
+        });
+
+        timeout(
+            Duration::from_secs(5),
+            Supervisor::graceful_shutdown(
+                Arc::clone(&state),
+                proxy_handle,
+                health_handle,
+                &health_stop,
+            ),
+        )
+        .await
+        .expect("graceful shutdown should finish")
+        .unwrap();
+        assert!(
+            health_done_first.load(Ordering::SeqCst),
+            "proxy was signalled before health checks stopped"
+        );
+    }
+
+    #[test]
+    fn drain_summary_reports_count_and_elapsed_ms() {
+        let started = Instant::now();
+        assert_eq!(
+            drain_summary(3, started, started + Duration::from_millis(1500)),
+            "Drained 3 connections in 1500 ms"
+        );
+        assert_eq!(
+            drain_summary(1, started, started + Duration::from_millis(42)),
+            "Drained 1 connection in 42 ms"
+        );
+        // Timestamps passed in the wrong order clamp to 0 instead of panicking
+        assert_eq!(
+            drain_summary(0, started + Duration::from_millis(5), started),
+            "Drained 0 connections in 0 ms"
+        );
+    }
+
+    #[tokio::test]
+    async fn runtime_dump_lists_backends_and_protection_state() {
+        let backends = [9000, 9100].map(BackendConfig::local).into();
+        let mut runtime_config = RuntimeConfig::for_test(backends, RuntimeTuning::default());
+        runtime_config.bind_address = "0.0.0.0".to_string();
+        runtime_config.config_path = std::path::PathBuf::from("/etc/bal/config.yaml");
+        let state = AppState::for_test(runtime_config);
+        state.backend_pool().all_backends()[1].mark_failure(1);
+        state.protection_mode().record_global_unavailable();
+
+        let dump = format_runtime_dump(&state).await;
+
+        assert!(dump.contains("config: /etc/bal/config.yaml"));
+        assert!(dump.contains("listen: 0.0.0.0:9295 method=round_robin"));
+        assert!(dump.contains("backends: 1/2 healthy"));
+        assert!(dump.contains("protection_mode: on (all_backends_unavailable)"));
+        assert!(dump.contains("127.0.0.1:9000 healthy=true active=0"));
+        assert!(dump.contains("127.0.0.1:9100 healthy=false active=0 consecutive_failures=1"));
+    }
+
+    #[test]
+    fn heartbeat_interval_follows_the_watchdog_but_never_spins() {
+        assert_eq!(heartbeat_interval(5000, None), Duration::from_secs(5));
+        assert_eq!(
+            heartbeat_interval(5000, Some(Duration::from_secs(4))),
+            Duration::from_secs(2)
+        );
+        // WATCHDOG_USEC=1 would halve to zero
+        assert_eq!(
+            heartbeat_interval(5000, Some(Duration::from_micros(1))),
+            Duration::from_millis(MIN_HEARTBEAT_INTERVAL_MS)
+        );
+    }
+
+    #[tokio::test]
+    async fn heartbeat_file_mtime_advances_and_watchdog_is_pinged() {
+        let dir = tempfile::tempdir().unwrap();
+        let heartbeat = dir.path().join("bal.heartbeat");
+        let state = AppState::for_test(RuntimeConfig::for_test(
+            Vec::new(),
+            RuntimeTuning {
+                heartbeat_file: Some(heartbeat.display().to_string()),
+                heartbeat_interval_ms: 20,
+                ..RuntimeTuning::default()
+            },
+        ));
+
+        let notify_path = dir.path().join("notify.sock");
+        let notify = tokio::net::UnixDatagram::bind(&notify_path).unwrap();
+        let env = |name: &str| match name {
+            "WATCHDOG_USEC" => Some("30000000".to_string()),
+            "NOTIFY_SOCKET" => Some(notify_path.display().to_string()),
+            _ => None,
+        };
+        let watchdog = SystemdWatchdog::from_env(env, 4242).unwrap();
+        assert_eq!(watchdog.timeout, Duration::from_secs(30));
+        let for_other_pid = |name: &str| match name {
+            "WATCHDOG_PID" => Some("1".to_string()),
+            other => env(other),
+        };
+        assert!(SystemdWatchdog::from_env(for_other_pid, 4242).is_none());
+
+        let task = tokio::spawn(run_heartbeat(
+            Arc::clone(&state),
+            Some(watchdog),
+            state.subscribe_shutdown(),
+        ));
+
+        let mut buf = [0u8; 32];
+        let n = timeout(Duration::from_secs(2), notify.recv(&mut buf))
+            .await
+            .expect("watchdog ping should arrive")
+            .unwrap();
+        assert_eq!(&buf[..n], b"WATCHDOG=1");
+
+        let mtime = || std::fs::metadata(&heartbeat).unwrap().modified().unwrap();
+        let first = mtime();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(mtime() > first, "heartbeat mtime should advance");
+
+        state.trigger_shutdown();
+        timeout(Duration::from_secs(1), task)
+            .await
+            .unwrap()
+            .unwrap();
+    }
+}
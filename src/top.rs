@@ -0,0 +1,126 @@
+//! `bal top-backends`
+//!
+//! Ranks backends by live traffic from the daemon's runtime stats snapshot,
+//! a quick "who's hot" view during incidents.
+
+use anyhow::Result;
+use clap::ValueEnum;
+use std::path::PathBuf;
+
+use crate::process::{BackendStatusSummary, ProcessManager};
+
+/// What `top-backends` ranks by
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum TrafficMetric {
+    /// Currently open client sessions
+    #[default]
+    Connections,
+    /// Bytes relayed by finished sessions
+    Bytes,
+}
+
+/// Busiest backends first, at most `limit`
+///
+/// Ties fall back to the other metric, then the address, so the order is
+/// stable between runs.
+pub fn rank_backends(
+    backends: &[BackendStatusSummary],
+    by: TrafficMetric,
+    limit: usize,
+) -> Vec<&BackendStatusSummary> {
+    let mut ranked: Vec<&BackendStatusSummary> = backends.iter().collect();
+    ranked.sort_by(|a, b| {
+        let connections = b.active_connections.cmp(&a.active_connections);
+        let bytes = b.bytes_total.cmp(&a.bytes_total);
+        match by {
+            TrafficMetric::Connections => connections.then(bytes),
+            TrafficMetric::Bytes => bytes.then(connections),
+        }
+        .then_with(|| a.address.cmp(&b.address))
+    });
+    ranked.truncate(limit);
+    ranked
+}
+
+fn render_table(ranked: &[&BackendStatusSummary]) -> String {
+    let width = ranked
+        .iter()
+        .map(|backend| backend.address.len())
+        .max()
+        .unwrap_or(0)
+        .max("BACKEND".len());
+
+    let mut lines = vec![format!(
+        "{:<4}  {:<width$}  {:>11}  {:>14}",
+        "RANK", "BACKEND", "CONNECTIONS", "BYTES"
+    )];
+    for (rank, backend) in ranked.iter().enumerate() {
+        lines.push(format!(
+            "{:<4}  {:<width$}  {:>11}  {:>14}",
+            rank + 1,
+            backend.address,
+            backend.active_connections,
+            backend.bytes_total
+        ));
+    }
+    lines.join("\n")
+}
+
+/// Print the ranking for the running daemon
+pub async fn run(config: Option<PathBuf>, by: TrafficMetric, limit: usize) -> Result<()> {
+    let summary = ProcessManager::collect_status(config).await?;
+    if !summary.running {
+        println!("bal is not running - no live traffic to rank");
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        render_table(&rank_backends(&summary.backends, by, limit))
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn backend(address: &str, active_connections: usize, bytes_total: u64) -> BackendStatusSummary {
+        BackendStatusSummary {
+            active_connections,
+            bytes_total,
+            ..BackendStatusSummary::idle(address)
+        }
+    }
+
+    #[test]
+    fn ranks_by_the_chosen_metric_and_applies_the_limit() {
+        let backends = vec![
+            backend("10.0.0.1:9000", 3, 500),
+            backend("10.0.0.2:9000", 9, 100),
+            backend("10.0.0.3:9000", 3, 9_000),
+            backend("10.0.0.4:9000", 0, 0),
+        ];
+        let addresses = |ranked: Vec<&BackendStatusSummary>| -> Vec<String> {
+            ranked.iter().map(|b| b.address.clone()).collect()
+        };
+
+        assert_eq!(
+            addresses(rank_backends(&backends, TrafficMetric::Connections, 10)),
+            [
+                "10.0.0.2:9000",
+                "10.0.0.3:9000",
+                "10.0.0.1:9000",
+                "10.0.0.4:9000"
+            ]
+        );
+        assert_eq!(
+            addresses(rank_backends(&backends, TrafficMetric::Bytes, 2)),
+            ["10.0.0.3:9000", "10.0.0.1:9000"]
+        );
+
+        let table = render_table(&rank_backends(&backends, TrafficMetric::Bytes, 1));
+        assert!(table.starts_with("RANK"));
+        assert!(table.lines().nth(1).unwrap().contains("10.0.0.3:9000"));
+    }
+}
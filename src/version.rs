@@ -0,0 +1,89 @@
+//! `bal version`
+//!
+//! Prints the crate version plus the build metadata captured by `build.rs`,
+//! so operators can check exactly which build an image ships.
+
+use anyhow::Result;
+use chrono::{TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Build identity reported by `bal version`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VersionInfo {
+    pub version: String,
+    /// Short commit hash, or `unknown` when built outside a git checkout
+    pub git_commit: String,
+    /// RFC 3339 build time (UTC)
+    pub build_date: String,
+    /// Enabled cargo features
+    pub features: Vec<String>,
+}
+
+impl VersionInfo {
+    /// Metadata of the running binary
+    pub fn current() -> Self {
+        let build_date = env!("BAL_BUILD_EPOCH")
+            .parse::<i64>()
+            .ok()
+            .and_then(|epoch| Utc.timestamp_opt(epoch, 0).single())
+            .map(|date| date.to_rfc3339())
+            .unwrap_or_else(|| "unknown".to_string());
+
+        Self {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            git_commit: env!("BAL_GIT_COMMIT").to_string(),
+            build_date,
+            features: env!("BAL_BUILD_FEATURES")
+                .split(',')
+                .filter(|feature| !feature.is_empty())
+                .map(str::to_string)
+                .collect(),
+        }
+    }
+
+    fn render_text(&self) -> String {
+        let features = if self.features.is_empty() {
+            "none".to_string()
+        } else {
+            self.features.join(", ")
+        };
+        format!(
+            "bal {}\n  commit: {}\n  built: {}\n  features: {}",
+            self.version, self.git_commit, self.build_date, features
+        )
+    }
+}
+
+/// Print the version report as text, or as JSON with `--json`
+pub fn print(json: bool) -> Result<()> {
+    let info = VersionInfo::current();
+    if json {
+        println!("{}", serde_json::to_string_pretty(&info)?);
+    } else {
+        println!("{}", info.render_text());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn version_info_serializes_with_build_metadata() {
+        let info = VersionInfo::current();
+        assert_eq!(info.version, env!("CARGO_PKG_VERSION"));
+        assert!(chrono::DateTime::parse_from_rfc3339(&info.build_date).is_ok());
+
+        let value = serde_json::to_value(&info).unwrap();
+        let object = value.as_object().expect("version info is a JSON object");
+        let mut keys: Vec<&str> = object.keys().map(String::as_str).collect();
+        keys.sort();
+        assert_eq!(keys, ["build_date", "features", "git_commit", "version"]);
+        assert!(object["features"].is_array());
+        assert!(!object["git_commit"].as_str().unwrap().is_empty());
+
+        let parsed: VersionInfo = serde_json::from_value(value).unwrap();
+        assert_eq!(parsed, info);
+    }
+}